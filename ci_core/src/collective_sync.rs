@@ -8,8 +8,8 @@
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
 
-use std::collections::HashMap;
-use qublis_qnum::{QNum, entangle, qadd};
+use std::collections::{HashMap, HashSet};
+use qublis_qnum::{JointState, QNum, entangle, entangle_all, measure_all, qadd};
 use crate::{
     config::CiCoreConfig,
     error::CiCoreError,
@@ -25,6 +25,11 @@ pub struct CollectiveSync {
     metrics: CiCoreMetrics,
     /// Map from agent ID to its current quantum state
     agents: HashMap<AgentId, AgentState>,
+    /// Agents touched by messages or registration since the last sync
+    dirty: HashSet<AgentId>,
+    /// Agents entangled together by a global sync, in subsystem order, with
+    /// the joint state that keeps them correlated
+    groups: Vec<(Vec<AgentId>, JointState)>,
 }
 
 impl CollectiveSync {
@@ -36,6 +41,8 @@ impl CollectiveSync {
             config: config.clone(),
             metrics,
             agents: HashMap::new(),
+            dirty: HashSet::new(),
+            groups: Vec::new(),
         }
     }
 
@@ -52,6 +59,7 @@ impl CollectiveSync {
             )));
         }
         self.agents.insert(id.clone(), state);
+        self.dirty.insert(id);
         self.metrics.inc_counter("agents_registered", 1);
         Ok(())
     }
@@ -78,6 +86,9 @@ impl CollectiveSync {
             // also entangle with sender state for tighter sync
             entangle(&mut recipient.state, &mut sender_state.clone());
         }
+        self.dissolve_groups(std::slice::from_ref(to));
+        self.dirty.insert(from.clone());
+        self.dirty.insert(to.clone());
         self.metrics.inc_counter("messages_sent", 1);
         Ok(())
     }
@@ -86,58 +97,106 @@ impl CollectiveSync {
     ///
     /// If `enable_global_average` is set in config, computes the QNum average
     /// of all agent states (via repeated `qadd` and normalization) and replaces
-    /// each agent’s state with that summary; otherwise entangles every pair.
+    /// each agent’s state with that summary; otherwise entangles all agents
+    /// in one GHZ‐like step.
     pub fn synchronize(&mut self) -> Result<(), CiCoreError> {
-        let n = self.agents.len();
-        if n == 0 {
+        if self.agents.is_empty() {
             return Err(CiCoreError::SyncError("no agents to synchronize".into()));
         }
+        let ids: Vec<AgentId> = self.agents.keys().cloned().collect();
+        self.synchronize_agents(&ids)?;
+        self.dirty.clear();
+        Ok(())
+    }
+
+    /// Perform an incremental synchronization over only the agents touched
+    /// since the last sync (via `register_agent` or `send_message`).
+    ///
+    /// Uses the same averaging/entangling strategy as `synchronize`, but
+    /// restricted to the dirty set; untouched agents keep their state.
+    /// A no-op if nothing changed since the last sync.
+    pub fn synchronize_incremental(&mut self) -> Result<(), CiCoreError> {
+        if self.agents.is_empty() {
+            return Err(CiCoreError::SyncError("no agents to synchronize".into()));
+        }
+        let ids: Vec<AgentId> = self.dirty.drain()
+            .filter(|id| self.agents.contains_key(id))
+            .collect();
+        if ids.is_empty() {
+            return Ok(());
+        }
+        self.synchronize_agents(&ids)?;
+        self.metrics.inc_counter("incremental_syncs", 1);
+        Ok(())
+    }
+
+    /// Returns the IDs of agents pending an incremental synchronization.
+    pub fn dirty_agents(&self) -> Vec<AgentId> {
+        self.dirty.iter().cloned().collect()
+    }
+
+    /// Average or collectively entangle the states of the given agents.
+    fn synchronize_agents(&mut self, ids: &[AgentId]) -> Result<(), CiCoreError> {
         if self.config.enable_global_average {
             // compute summary
-            let mut iter = self.agents.values();
+            let mut iter = ids.iter().map(|id| &self.agents[id]);
             let first = iter.next().unwrap().state.clone();
+            let width = first.len();
             let mut summary = first;
             for agent in iter {
                 summary = qadd(&summary, &agent.state);
             }
-            // normalize by measuring and re-encoding
-            let measured = summary.measure();
-            let new_state = AgentState { state: QNum::from_digits(&measured) };
-            for (_id, agent) in self.agents.iter_mut() {
-                agent.state = new_state.state.clone();
+            // normalize by measuring, dividing by the agent count and
+            // re-encoding at the agents' width
+            let radix = summary.radix();
+            let mean = summary.measure_u64() / ids.len() as u64;
+            let new_state = AgentState { state: QNum::from_u64_in(radix, mean, width) };
+            for id in ids {
+                self.agents.get_mut(id).unwrap().state = new_state.state.clone();
             }
+            self.dissolve_groups(ids);
             self.metrics.inc_counter("global_averages", 1);
         } else {
-            // entangle each pair (clone, entangle, write back)
-            let ids: Vec<AgentId> = self.agents.keys().cloned().collect();
-            for i in 0..ids.len() {
-                for j in (i+1)..ids.len() {
-                    let ai = &ids[i];
-                    let aj = &ids[j];
-
-                    // Clone current states
-                    let state_i = self.agents.get(ai).unwrap().state.clone();
-                    let state_j = self.agents.get(aj).unwrap().state.clone();
-
-                    // Entangle clones
-                    let (mut entangled_i, mut entangled_j) = (state_i, state_j);
-                    entangle(&mut entangled_i, &mut entangled_j);
-
-                    // Write back
-                    self.agents.get_mut(ai).unwrap().state = entangled_i;
-                    self.agents.get_mut(aj).unwrap().state = entangled_j;
-                }
-            }
+            // entangle every selected agent at once
+            let (members, mut states): (Vec<AgentId>, Vec<&mut QNum>) = self.agents
+                .iter_mut()
+                .filter(|(id, _)| ids.contains(id))
+                .map(|(id, agent)| (id.clone(), &mut agent.state))
+                .unzip();
+            let joint = entangle_all(&mut states)
+                .map_err(|e| CiCoreError::SyncError(format!("entanglement failed: {}", e)))?;
+            self.dissolve_groups(&members);
+            self.groups.push((members, joint));
             self.metrics.inc_counter("global_entanglements", 1);
         }
         Ok(())
     }
 
+    /// Forget the entangled groups of any of `ids`, whose states are about
+    /// to diverge from the recorded joint states.
+    fn dissolve_groups(&mut self, ids: &[AgentId]) {
+        self.groups.retain(|(members, _)| !members.iter().any(|id| ids.contains(id)));
+    }
+
     /// Retrieve a snapshot of all current agent states.
+    ///
+    /// Each group entangled by a global sync is measured from its joint state
+    /// with `measure_all`, so its members report the same outcome; other
+    /// agents are measured independently.
     pub fn snapshot(&self) -> HashMap<AgentId, Vec<u8>> {
-        self.agents.iter()
-            .map(|(id, agent)| (id.clone(), agent.state.clone().measure()))
-            .collect()
+        let mut snap = HashMap::new();
+        for (members, joint) in &self.groups {
+            let mut states: Vec<QNum> = members.iter()
+                .map(|id| self.agents[id].state.clone())
+                .collect();
+            let outcomes = measure_all(joint, &mut states)
+                .expect("group members match their joint state");
+            snap.extend(members.iter().cloned().zip(outcomes));
+        }
+        for (id, agent) in &self.agents {
+            snap.entry(id.clone()).or_insert_with(|| agent.state.clone().measure());
+        }
+        snap
     }
 
     /// Export internal metrics in Prometheus text format.
@@ -194,6 +253,41 @@ mod tests {
         assert!(matches!(snap.get(&b).unwrap().as_slice(), [5] | [9]));
     }
 
+    #[test]
+    fn test_snapshot_after_global_entangle_is_coherent() {
+        let cfg = CiCoreConfig::default();
+        let mut cs = CollectiveSync::new(&cfg);
+        for (id, digit) in [("A", 2), ("B", 5), ("C", 8)] {
+            cs.register_agent(id.into(), make_agent(id, digit).1).unwrap();
+        }
+        cs.synchronize().unwrap();
+        for _ in 0..20 {
+            let snap = cs.snapshot();
+            let first = &snap["A"];
+            assert!(matches!(first.as_slice(), [2] | [5] | [8]));
+            assert!(snap.values().all(|v| v == first), "incoherent snapshot {:?}", snap);
+        }
+    }
+
+    #[test]
+    fn test_snapshot_keeps_equal_unentangled_agents_independent() {
+        let cfg = CiCoreConfig::default();
+        let mut cs = CollectiveSync::new(&cfg);
+        for (id, digit) in [("A", 2), ("B", 5)] {
+            cs.register_agent(id.into(), make_agent(id, digit).1).unwrap();
+        }
+        cs.synchronize().unwrap();
+        // an agent registered with an equal state is not part of the group
+        let member = cs.agents["A"].clone();
+        cs.register_agent("C".into(), member).unwrap();
+        let disagreed = (0..100).any(|_| {
+            let snap = cs.snapshot();
+            assert_eq!(snap["A"], snap["B"]);
+            snap["C"] != snap["A"]
+        });
+        assert!(disagreed, "unentangled agent always matched the group");
+    }
+
     #[test]
     fn test_global_average() {
         let mut cfg = CiCoreConfig::default();
@@ -215,13 +309,39 @@ mod tests {
         cs.register_agent("X".into(), make_agent("X", 1).1).unwrap();
         cs.register_agent("Y".into(), make_agent("Y", 2).1).unwrap();
         cs.synchronize().unwrap();
-        let snap = cs.snapshot();
         // After entanglement, at least one state has increased entropy
         let entropies: Vec<f64> = cs.agents.values()
             .map(|ag| ag.state.entropy()).collect();
         assert!(entropies.iter().any(|&e| e > 0.0));
     }
 
+    #[test]
+    fn test_incremental_sync_touches_only_dirty_agents() {
+        let mut cfg = CiCoreConfig::default();
+        cfg.enable_global_average = false;
+        cfg.enable_global_entangle = false;
+        let mut cs = CollectiveSync::new(&cfg);
+        for (id, d) in [("A", 1), ("B", 2), ("C", 3)] {
+            cs.register_agent(id.into(), make_agent(id, d).1).unwrap();
+        }
+        cs.synchronize().unwrap();
+        assert!(cs.dirty_agents().is_empty());
+        let before = cs.agents.clone();
+
+        let (a, b) = ("A".to_string(), "B".to_string());
+        let msg = SyncMessage { from: a.clone(), state: QNum::from_digits(&[9]) };
+        cs.send_message(&a, &b, msg).unwrap();
+        let mut dirty = cs.dirty_agents();
+        dirty.sort();
+        assert_eq!(dirty, vec![a.clone(), b.clone()]);
+
+        cs.synchronize_incremental().unwrap();
+        assert!(cs.dirty_agents().is_empty());
+        assert_eq!(cs.agents["C"], before["C"]);
+        assert_ne!(cs.agents["A"], before["A"]);
+        assert_ne!(cs.agents["B"], before["B"]);
+    }
+
     #[test]
     fn test_synchronize_no_agents() {
         let cfg = CiCoreConfig::default();
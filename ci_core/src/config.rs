@@ -114,7 +114,7 @@ mod tests {
             enable_global_average = true
             enable_metrics = true
         "#;
-        let mut file = NamedTempFile::new().unwrap();
+        let file = NamedTempFile::new().unwrap();
         fs::write(file.path(), toml).unwrap();
         let cfg = CiCoreConfig::load(file.path()).unwrap();
         assert_eq!(cfg.num_neurons, 256);
@@ -132,7 +132,7 @@ mod tests {
 
     #[test]
     fn invalid_toml_errs_parse() {
-        let mut file = NamedTempFile::new().unwrap();
+        let file = NamedTempFile::new().unwrap();
         fs::write(file.path(), "not = valid = toml").unwrap();
         let err = CiCoreConfig::load(file.path()).unwrap_err();
         matches!(err, ConfigError::Parse(_));
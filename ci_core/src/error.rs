@@ -17,7 +17,9 @@ pub enum CiCoreError {
     /// Dimension mismatch when perceiving input.
     #[error("dimension mismatch: expected {expected}, got {got}")]
     DimensionMismatch {
+        /// Number of inputs the receiver expects.
         expected: usize,
+        /// Number of inputs actually provided.
         got: usize,
     },
 
@@ -47,6 +49,7 @@ mod tests {
 
     #[test]
     fn from_config_error() {
+        use serde::de::Error as _;
         let cfg_err = ConfigError::Parse(toml::de::Error::custom("oops"));
         let err: CiCoreError = cfg_err.into();
         assert!(err.to_string().starts_with("configuration error:"));
@@ -4,6 +4,7 @@
 //! - `MorphicAI`: an adaptive, generative neural substrate  
 //! - `MoralRegulator`: enforces ethical constraints on AI decisions  
 //! - `CollectiveSync`: synchronizes distributed AI agents into coherent collectives  
+//! - `NeuroFluxAgent`: learns consensus parameter adjustments from rewards  
 //!
 //! Additional modules provide configuration, shared types, error handling, and metrics.
 
@@ -16,6 +17,8 @@ pub mod morphic_ai;
 pub mod moral_regulator;
 /// Distributed multi-agent synchronization engine.
 pub mod collective_sync;
+/// Reinforcement-learning agent for consensus tuning.
+pub mod neuroflux;
 /// Configuration loader and defaults.
 pub mod config;
 /// Core shared types (agent state, policies, etc.).
@@ -34,6 +37,7 @@ pub use metrics::CiCoreMetrics;
 pub use morphic_ai::MorphicAI;
 pub use moral_regulator::MoralRegulator;
 pub use collective_sync::CollectiveSync;
+pub use neuroflux::{Action, NeuroFluxAgent, NeuroFluxConfig, RewardWeights};
 
 /// Conveniently import everything needed to get started.
 pub use prelude::*;
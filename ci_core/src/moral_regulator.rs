@@ -141,8 +141,8 @@ mod tests {
     fn metrics_recorded() {
         let cfg = CiCoreConfig::default();
         let mut mr = MoralRegulator::new(&cfg);
-        let _ = mr.add_principle("a".into(), classical_qnum(1));
-        let ok = mr.enforce(MotorOutput { signals: vec![0] }).unwrap_err();
+        let _ = mr.add_principle("a".into(), classical_qnum(0));
+        let _ = mr.enforce(MotorOutput { signals: vec![0] }).unwrap_err();
         let prom = mr.export_metrics();
         // One violation and one addition
         assert!(prom.contains("principles_added 1"));
@@ -119,7 +119,7 @@ mod tests {
         let cfg = default_cfg();
         let ai = MorphicAI::new(&cfg);
         assert_eq!(ai.state.0.len(), cfg.num_neurons);
-        for neuron in ai.state.0 {
+        for mut neuron in ai.state.0 {
             // Zero‐state QNum measures to all zeros
             assert_eq!(neuron.measure(), vec![0; neuron.len()]);
         }
@@ -163,15 +163,15 @@ mod tests {
         let mut ai = MorphicAI::new(&cfg);
         // Initialize one neuron to superposition [1/sqrt2,1/sqrt2]
         let q = QNum::from_superposed(vec![
-            (vec![0], 1.0 / 2f64.sqrt()),
-            (vec![1], 1.0 / 2f64.sqrt()),
+            (vec![0], Complex::new(1.0 / 2f64.sqrt(), 0.0)),
+            (vec![1], Complex::new(1.0 / 2f64.sqrt(), 0.0)),
         ]);
         ai.state = NeuralState(vec![q.clone(), q.clone(), q.clone()]);
         ai.train(1.0); // reward = 1
         // After training, normalization preserves entropy but amplitudes changed
         for neuron in &ai.state.0 {
             for qid in &neuron.0 {
-                let norm_sq: f64 = qid.amps.iter().map(|c| c.norm_sqr()).sum();
+                let norm_sq: f64 = qid.amps.iter().map(|c| c.norm_sqr().0).sum();
                 assert!((norm_sq - 1.0).abs() < 1e-12);
             }
         }
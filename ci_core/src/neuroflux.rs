@@ -0,0 +1,251 @@
+//! NeuroFlux — Reinforcement-Learning Agent for Consensus Tuning
+//!
+//! `NeuroFluxAgent` learns which parameter adjustment to make in a given
+//! observed consensus state:
+//! - States are `QNum` observations, keyed by their measured digits.
+//! - Actions are a signed step on one consensus parameter (entropy threshold,
+//!   tip count, block interval or branch count), or no change, with step
+//!   sizes from `NeuroFluxConfig`.
+//! - Each (state, action) pair keeps a running reward estimate; selection is
+//!   greedy on that estimate plus a count-based exploration bonus, so the
+//!   agent is deterministic and fully described by its learned table.
+//!
+//! `RewardWeights` carries the weights a host uses to score an epoch.
+
+use std::collections::BTreeMap;
+
+use qublis_qnum::QNum;
+use serde::{Deserialize, Serialize};
+
+/// Default learning rate for reward estimates.
+fn default_learning_rate() -> f64 {
+    0.1
+}
+
+/// Default exploration bonus for rarely-tried actions.
+fn default_exploration() -> f64 {
+    1.0
+}
+
+/// Default entropy-threshold step per action.
+fn default_entropy_step() -> f64 {
+    1.0
+}
+
+/// Default tip-count step per action.
+fn default_tip_step() -> i64 {
+    1
+}
+
+/// Default block-interval step (ms) per action.
+fn default_block_interval_step() -> i64 {
+    100
+}
+
+/// Default branch-count step per action.
+fn default_branch_step() -> i64 {
+    64
+}
+
+/// Configuration for a `NeuroFluxAgent`.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct NeuroFluxConfig {
+    /// Weight of each new reward in the running estimate, in `(0, 1]`.
+    #[serde(default = "default_learning_rate")]
+    pub learning_rate: f64,
+
+    /// Scale of the `1 / sqrt(1 + visits)` bonus added to each estimate.
+    #[serde(default = "default_exploration")]
+    pub exploration: f64,
+
+    /// Magnitude of one entropy-threshold step.
+    #[serde(default = "default_entropy_step")]
+    pub entropy_step: f64,
+
+    /// Magnitude of one tip-count step.
+    #[serde(default = "default_tip_step")]
+    pub tip_step: i64,
+
+    /// Magnitude of one block-interval step, in milliseconds.
+    #[serde(default = "default_block_interval_step")]
+    pub block_interval_step: i64,
+
+    /// Magnitude of one branch-count step.
+    #[serde(default = "default_branch_step")]
+    pub branch_step: i64,
+}
+
+impl Default for NeuroFluxConfig {
+    fn default() -> Self {
+        NeuroFluxConfig {
+            learning_rate: default_learning_rate(),
+            exploration: default_exploration(),
+            entropy_step: default_entropy_step(),
+            tip_step: default_tip_step(),
+            block_interval_step: default_block_interval_step(),
+            branch_step: default_branch_step(),
+        }
+    }
+}
+
+/// Weights used to combine epoch measurements into a scalar reward.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct RewardWeights {
+    /// Weight of the measured/target throughput ratio.
+    pub tps: f64,
+    /// Weight of the latency penalty.
+    pub latency: f64,
+    /// Weight of the fork-rate penalty.
+    pub forks: f64,
+}
+
+/// A parameter adjustment chosen by the agent.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Action {
+    /// Amount to add to the entropy finality threshold.
+    pub delta_entropy: f64,
+    /// Amount to add to the maximum tip count.
+    pub delta_tips: i64,
+    /// Amount (ms) to add to the block interval.
+    pub delta_block_interval_ms: i64,
+    /// Amount to add to the maximum branch count.
+    pub delta_branches: i64,
+}
+
+/// Learned statistics for one action in one state.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct ActionValue {
+    /// Running reward estimate.
+    pub value: f64,
+    /// Number of rewards folded into `value`.
+    pub visits: u64,
+}
+
+/// Reinforcement-learning agent choosing consensus parameter adjustments.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct NeuroFluxAgent {
+    config: NeuroFluxConfig,
+    /// Per-state statistics, indexed like `actions()`.
+    values: BTreeMap<String, Vec<ActionValue>>,
+}
+
+impl NeuroFluxAgent {
+    /// Create an agent with no learned values.
+    pub fn new(config: &NeuroFluxConfig) -> Self {
+        NeuroFluxAgent {
+            config: config.clone(),
+            values: BTreeMap::new(),
+        }
+    }
+
+    /// The agent's configuration.
+    pub fn config(&self) -> &NeuroFluxConfig {
+        &self.config
+    }
+
+    /// The action set: no change, then a down and an up step on each
+    /// parameter in turn.
+    pub fn actions(&self) -> Vec<Action> {
+        let c = &self.config;
+        let mut actions = vec![Action::default()];
+        for sign in [-1, 1] {
+            actions.push(Action { delta_entropy: sign as f64 * c.entropy_step, ..Action::default() });
+        }
+        for sign in [-1, 1] {
+            actions.push(Action { delta_tips: sign * c.tip_step, ..Action::default() });
+        }
+        for sign in [-1, 1] {
+            actions.push(Action { delta_block_interval_ms: sign * c.block_interval_step, ..Action::default() });
+        }
+        for sign in [-1, 1] {
+            actions.push(Action { delta_branches: sign * c.branch_step, ..Action::default() });
+        }
+        actions
+    }
+
+    /// Learned statistics for `state`, if it has been rewarded before.
+    pub fn values(&self, state: &QNum) -> Option<&[ActionValue]> {
+        self.values.get(&state_key(state)).map(Vec::as_slice)
+    }
+
+    /// Choose the action with the best estimate plus exploration bonus in
+    /// `state`; ties go to the earliest action in `actions()`.
+    pub fn select_action(&self, state: &QNum) -> Action {
+        let actions = self.actions();
+        let stats = self.values.get(&state_key(state));
+        let score = |i: usize| {
+            let av = stats.and_then(|s| s.get(i)).cloned().unwrap_or_default();
+            av.value + self.config.exploration / (1.0 + av.visits as f64).sqrt()
+        };
+        let best = (0..actions.len()).fold(0, |best, i| if score(i) > score(best) { i } else { best });
+        actions[best]
+    }
+
+    /// Fold `reward` into the estimate for taking `action` in `state`.
+    ///
+    /// Actions outside `actions()` are ignored.
+    pub fn learn(&mut self, state: QNum, action: Action, reward: f64) {
+        let actions = self.actions();
+        let Some(i) = actions.iter().position(|a| *a == action) else {
+            return;
+        };
+        let stats = self
+            .values
+            .entry(state_key(&state))
+            .or_insert_with(|| vec![ActionValue::default(); actions.len()]);
+        let av = &mut stats[i];
+        av.value += self.config.learning_rate * (reward - av.value);
+        av.visits += 1;
+    }
+}
+
+/// Table key for a state: its measured digits, comma separated.
+fn state_key(state: &QNum) -> String {
+    state
+        .clone()
+        .measure()
+        .iter()
+        .map(u8::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unexplored_state_picks_first_action() {
+        let agent = NeuroFluxAgent::new(&NeuroFluxConfig::default());
+        let state = QNum::from_digits(&[1, 2]);
+        assert_eq!(agent.actions().len(), 9);
+        assert_eq!(agent.select_action(&state), Action::default());
+        assert!(agent.values(&state).is_none());
+    }
+
+    #[test]
+    fn learning_steers_selection_toward_rewarded_action() {
+        let cfg = NeuroFluxConfig { learning_rate: 1.0, exploration: 0.0, ..Default::default() };
+        let mut agent = NeuroFluxAgent::new(&cfg);
+        let state = QNum::from_digits(&[3]);
+        let good = agent.actions()[4];
+        agent.learn(state.clone(), agent.actions()[0], -1.0);
+        agent.learn(state.clone(), good, 2.0);
+        assert_eq!(agent.select_action(&state), good);
+
+        let stats = agent.values(&state).unwrap();
+        assert_eq!(stats[4], ActionValue { value: 2.0, visits: 1 });
+        // other states are untouched
+        assert!(agent.values(&QNum::from_digits(&[4])).is_none());
+    }
+
+    #[test]
+    fn agent_round_trips_through_json() {
+        let mut agent = NeuroFluxAgent::new(&NeuroFluxConfig::default());
+        let action = agent.actions()[2];
+        agent.learn(QNum::from_digits(&[7, 1]), action, 0.5);
+        let json = serde_json::to_string(&agent).unwrap();
+        let back: NeuroFluxAgent = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, agent);
+    }
+}
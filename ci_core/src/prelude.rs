@@ -11,6 +11,7 @@ pub use crate::metrics::CiCoreMetrics;
 pub use crate::morphic_ai::MorphicAI;
 pub use crate::moral_regulator::MoralRegulator;
 pub use crate::collective_sync::CollectiveSync;
+pub use crate::neuroflux::{Action, NeuroFluxAgent, NeuroFluxConfig, RewardWeights};
 
 pub use crate::types::{
     NeuralState,
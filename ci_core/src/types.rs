@@ -45,6 +45,7 @@ impl SensoryInput {
 /// Motor output from `MorphicAI`: a vector of classical u8 signals.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct MotorOutput {
+    /// Classical output signals, one per measured digit.
     pub signals: Vec<u8>,
 }
 
@@ -54,13 +55,16 @@ pub type AgentId = String;
 /// Holds the quantum state of an agent for `CollectiveSync`.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct AgentState {
+    /// The agent's current quantum state.
     pub state: QNum,
 }
 
 /// Message sent between agents, carrying a `QNum` state payload.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct SyncMessage {
+    /// Sending agent.
     pub from: AgentId,
+    /// State payload sent by `from`.
     pub state: QNum,
 }
 
@@ -73,7 +77,7 @@ mod tests {
     fn neural_state_zero_and_len() {
         let ns = NeuralState::zero(4);
         assert_eq!(ns.len(), 4);
-        for q in ns.0 {
+        for mut q in ns.0 {
             // each neuron measures to [0]
             assert_eq!(q.measure(), vec![0]);
         }
@@ -85,7 +89,7 @@ mod tests {
         let si = SensoryInput::from_digits(digits.clone());
         assert_eq!(si.len(), 3);
         // each channel is a single‐digit QNum
-        let measures: Vec<_> = si.0.iter().map(|q| q.measure()[0]).collect();
+        let measures: Vec<_> = si.0.into_iter().map(|mut q| q.measure()[0]).collect();
         assert_eq!(measures, digits);
     }
 
@@ -98,11 +102,11 @@ mod tests {
     #[test]
     fn agent_state_and_sync_message() {
         let q = QNum::from_digits(&[4, 2]);
-        let agent = AgentState { state: q.clone() };
-        assert_eq!(agent.state.measure(), q.measure());
+        let mut agent = AgentState { state: q.clone() };
+        assert_eq!(agent.state.measure(), q.clone().measure());
 
-        let msg = SyncMessage { from: "agent1".into(), state: q.clone() };
+        let mut msg = SyncMessage { from: "agent1".into(), state: q.clone() };
         assert_eq!(msg.from, "agent1");
-        assert_eq!(msg.state.measure(), q.measure());
+        assert_eq!(msg.state.measure(), q.clone().measure());
     }
 }
@@ -123,7 +123,6 @@ pub async fn run(config_path: &str) -> Result<(), CiForkError> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use tempfile::NamedTempFile;
     use tokio::runtime::Runtime;
 
     #[test]
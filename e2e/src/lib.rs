@@ -0,0 +1,17 @@
+//! Cross-crate end-to-end smoke tests for Qublis v2.0 (2-74136).
+//!
+//! This crate has no library code; see `tests/e2e.rs`. Each crate's own
+//! tests run in isolation, so the smoke test drives one small pipeline
+//! through all of them to catch regressions at the seams:
+//!
+//! 1. **qnum** — seed node states as `QNum`s.
+//! 2. **qmesh** — link them into an `EntropicDag`, propagate, and analyze
+//!    cognitive entropy.
+//! 3. **runtime** — record each DAG node as a `Block` and run one NeuroFlux
+//!    consensus tick and one entanglement-loop tick.
+//! 4. **qnetx / qnet** — open an entangled channel between two dimensions and
+//!    relay the newest block's payload along the mesh.
+//!
+//! Run with `cargo test -p qublis-e2e`.
+
+#![forbid(unsafe_code)]
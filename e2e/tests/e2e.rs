@@ -0,0 +1,119 @@
+//! End-to-end smoke test: qnum → qmesh → runtime → qnetx/qnet.
+//!
+//! Minimal flow:
+//! 1. Seed three node states (`QNum`) and chain them in an `EntropicDag`
+//!    (`genesis → left → right`), then propagate entanglement.
+//! 2. Analyze the DAG with `CognitiveEntropy`.
+//! 3. Record every node as a runtime `Block`, feed the global entropy to a
+//!    `ConsensusEngine`, and run one NeuroFlux tick and one entanglement tick.
+//! 4. Open a QNetX channel between two dimensions and relay the newest block's
+//!    payload across the mesh with a QNet `Relay`.
+//!
+//! Each step asserts that its output agrees with the previous one.
+
+use qublis_qmesh::{CognitiveEntropy, EntropicDag, QMeshConfig};
+use qublis_qnet::{Packet, QNetConfig, Relay};
+use qublis_qnetx::{Dimension, QNetXConfig, QuantumMesh};
+use qublis_qnum::QNum;
+use qublis_runtime::config::{ConsensusConfig, EntanglementConfig};
+use qublis_runtime::{Block, ConsensusEngine, ConsensusNeuroFlux, EntanglementLoop};
+
+const NODE_IDS: [&str; 3] = ["genesis", "left", "right"];
+
+#[tokio::test]
+async fn pipeline_produces_consistent_state() {
+    // 1. qnum + qmesh: build and propagate a small mesh
+    let mesh_cfg = QMeshConfig::default();
+    let mut dag = EntropicDag::new(&mesh_cfg);
+    let indices: Vec<_> = NODE_IDS
+        .iter()
+        .zip([[1u8, 2], [3, 4], [5, 6]])
+        .map(|(id, digits)| dag.add_node(id.to_string(), QNum::from_digits(&digits)))
+        .collect();
+    for pair in indices.windows(2) {
+        dag.add_edge(pair[0], pair[1], 1.0).expect("chain is acyclic");
+    }
+    dag.propagate();
+    assert_eq!(dag.node_count(), NODE_IDS.len());
+    assert_eq!(dag.edge_count(), NODE_IDS.len() - 1);
+
+    // 2. qmesh: cognitive entropy agrees with the DAG
+    let mut cognition = CognitiveEntropy::new(&mesh_cfg);
+    let report = cognition.analyze(&dag);
+    assert_eq!(report.node_entropies.len(), NODE_IDS.len());
+    assert!((report.global_entropy - dag.total_entropy()).abs() < 1e-9);
+    assert_eq!(cognition.history(), vec![report.global_entropy]);
+
+    // 3a. runtime: record entropic blocks
+    let blocks: Vec<Block> = indices
+        .iter()
+        .map(|&idx| Block::from_dag_node(&dag, idx))
+        .collect();
+    for (block, &idx) in blocks.iter().zip(&indices) {
+        let node = dag.node_data(idx);
+        assert_eq!(block.id, node.state);
+        assert_eq!(block.payload, node.id.as_bytes());
+        assert!((block.entropy - dag.node_entropy(idx)).abs() < 1e-9);
+    }
+    let block_entropy: f64 = blocks.iter().map(|b| b.entropy).sum();
+    assert!((block_entropy - report.global_entropy).abs() < 1e-9);
+
+    // 3b. runtime: one consensus tick and one entanglement tick
+    let mut engine = ConsensusEngine::mock();
+    for block in &blocks {
+        engine.accept_block(block);
+    }
+    assert_eq!(engine.height, (NODE_IDS.len() - 1) as u64);
+    assert_eq!(engine.confirmations(&blocks[0]), engine.height);
+    engine.tip_count = blocks.len();
+    engine.cognitive_entropy = report.global_entropy;
+    engine.set_entanglement_capacity(2);
+
+    let mut neuroflux = ConsensusNeuroFlux::new(&ConsensusConfig {
+        qmesh_config_path: "qmesh.toml".into(),
+        neuroflux_enabled: true,
+        neuroflux_config_path: None,
+        neuroflux: Default::default(),
+    });
+    neuroflux.tick(&mut engine);
+    let tuned = &engine.config;
+    assert!((tuned.min_entropy..=tuned.max_entropy).contains(&tuned.entropy_finality));
+    assert!(tuned.max_tips >= tuned.min_tips);
+
+    let mut entanglement = EntanglementLoop::new(&EntanglementConfig {
+        interval_ms: 0,
+        max_branches: 8,
+    });
+    entanglement.tick(&mut engine).expect("entanglement tick");
+    assert!(entanglement.export_metrics().contains("entanglement_ticks"));
+
+    // 4a. qnetx: entangle a channel between two dimensions
+    let mut overlay = QuantumMesh::new(&QNetXConfig::default());
+    let (dim_a, dim_b) = (Dimension::from("alpha"), Dimension::from("beta"));
+    let channel = overlay.entangle_channel(&dim_a, &dim_b).expect("entangle channel");
+    assert!(overlay.get_channel(&channel).is_some());
+    assert_eq!(overlay.channel_endpoints(&channel), Some((&dim_a, &dim_b)));
+
+    // 4b. qnet: relay the newest block's payload along the DAG's chain
+    let net_cfg = QNetConfig {
+        k_paths: 1,
+        enable_teleport: false,
+        ..QNetConfig::default()
+    };
+    let mut relay = Relay::new(&net_cfg).expect("default config is valid");
+    for pair in NODE_IDS.windows(2) {
+        relay.add_edge(pair[0].to_string(), pair[1].to_string());
+    }
+    let newest = blocks.last().expect("at least one block");
+    let receipt = relay
+        .relay(
+            &NODE_IDS[0].to_string(),
+            &NODE_IDS[2].to_string(),
+            Packet::new(newest.payload.clone()),
+        )
+        .await
+        .expect("relay along the chain");
+    assert_eq!(receipt.path, NODE_IDS.map(String::from).to_vec());
+    assert_eq!(receipt.hops, NODE_IDS.len() - 1);
+    assert!(!receipt.teleported);
+}
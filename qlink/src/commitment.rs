@@ -0,0 +1,211 @@
+//! Pedersen commitments and private equality proofs for QIDs.
+//!
+//! A [`Commitment`] is a Pedersen commitment `m·G + r·H` over ristretto255,
+//! where `m` is a hash of the QID's [`QNum::canonical_key`] and `r` is derived
+//! from a blinding nonce. It hides the QID and binds the committer to it.
+//! Building on that, [`prove_equal`] / [`verify_equal`] let the committer show
+//! a verifier that it commits to the verifier's own QID without sending the
+//! QID or the nonce. The proof is a Schnorr proof of knowledge of `r` with
+//! `C − m·G = r·H`, whose challenge is derived from a fresh random challenge
+//! the verifier issues, so a proof only verifies for the challenge it
+//! answered and only the holder of the nonce can produce one.
+//!
+//! A proof still shows which QID it verifies against: anyone holding it can
+//! test candidate QIDs, and a QID has few enough values to make that
+//! practical. Treat a proof as disclosing the QID to whoever sees it.
+
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::Scalar;
+use qublis_qnum::QNum;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+
+/// Domain separation tag for the commitment's value scalar `m`.
+const COMMIT_DOMAIN: &[u8] = b"qublis/qlink/qid-commitment/v2";
+/// Domain separation tag for the blinding scalar `r`.
+const BLINDING_DOMAIN: &[u8] = b"qublis/qlink/qid-commitment/v2/blinding";
+/// Domain separation tag for the second generator `H`.
+const GENERATOR_DOMAIN: &[u8] = b"qublis/qlink/qid-commitment/v2/generator";
+/// Domain separation tag for the Schnorr proof nonce `k`.
+const PROOF_NONCE_DOMAIN: &[u8] = b"qublis/qlink/qid-equality-proof/v2/nonce";
+/// Domain separation tag for the Schnorr challenge scalar.
+const PROOF_DOMAIN: &[u8] = b"qublis/qlink/qid-equality-proof/v2";
+
+/// Size in bytes of nonces and challenges.
+pub const NONCE_LEN: usize = 32;
+
+/// A Pedersen commitment to a QID: a compressed ristretto255 point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Commitment(pub [u8; 32]);
+
+/// Commit to `qid` under the blinding `nonce`.
+pub fn commit(qid: &QNum, nonce: &[u8; NONCE_LEN]) -> Commitment {
+    Commitment(commitment_point(qid, nonce).compress().to_bytes())
+}
+
+/// Check that `commitment` opens to `qid` under `nonce`.
+pub fn open(commitment: &Commitment, qid: &QNum, nonce: &[u8; NONCE_LEN]) -> bool {
+    ct_eq(&commit(qid, nonce).0, &commitment.0)
+}
+
+/// Proof that a published commitment is to the same QID as the verifier's.
+///
+/// Reveals neither the QID nor the commitment's nonce.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EqualityProof {
+    /// Announcement `k·H` for the prover's one-time proof nonce `k`.
+    pub announcement: [u8; 32],
+    /// Response `k + c·r` to the challenge scalar `c`.
+    pub response: [u8; 32],
+}
+
+/// Answer the verifier's `challenge` for the commitment `commit(qid, nonce)`.
+pub fn prove_equal(
+    qid: &QNum,
+    nonce: &[u8; NONCE_LEN],
+    challenge: &[u8; NONCE_LEN],
+) -> EqualityProof {
+    let commitment = commitment_point(qid, nonce).compress();
+    // derived rather than drawn, so a proof is reproducible; it differs for
+    // every challenge, which is what keeps `r` secret
+    let k = hash_to_scalar(&[PROOF_NONCE_DOMAIN, nonce, commitment.as_bytes(), challenge]);
+    let announcement = (k * generator_h()).compress();
+    let c = challenge_scalar(&commitment, &announcement, challenge);
+    EqualityProof {
+        announcement: announcement.to_bytes(),
+        response: (k + c * blinding(nonce)).to_bytes(),
+    }
+}
+
+/// Verify that `proof`, issued for `challenge`, shows `commitment` opens to
+/// the verifier's own `qid`.
+pub fn verify_equal(
+    proof: &EqualityProof,
+    commitment: &Commitment,
+    qid: &QNum,
+    challenge: &[u8; NONCE_LEN],
+) -> bool {
+    let commitment = CompressedRistretto(commitment.0);
+    let announcement = CompressedRistretto(proof.announcement);
+    let (Some(c_point), Some(t_point), Some(s)) = (
+        commitment.decompress(),
+        announcement.decompress(),
+        Option::<Scalar>::from(Scalar::from_canonical_bytes(proof.response)),
+    ) else {
+        return false;
+    };
+    let c = challenge_scalar(&commitment, &announcement, challenge);
+    // s·H = k·H + c·r·H, and r·H = C − m·G exactly when C commits to `qid`
+    s * generator_h() == t_point + c * (c_point - value(qid) * RISTRETTO_BASEPOINT_POINT)
+}
+
+/// The point `m·G + r·H` committing to `qid` under `nonce`.
+fn commitment_point(qid: &QNum, nonce: &[u8; NONCE_LEN]) -> RistrettoPoint {
+    value(qid) * RISTRETTO_BASEPOINT_POINT + blinding(nonce) * generator_h()
+}
+
+/// Scalar `m` a QID is committed as, from its rounding-stable canonical key.
+fn value(qid: &QNum) -> Scalar {
+    let mut h = Sha512::new();
+    h.update(COMMIT_DOMAIN);
+    let key = qid.canonical_key();
+    h.update((key.len() as u64).to_le_bytes());
+    for (basis, re, im) in key {
+        h.update([basis]);
+        h.update(re.to_le_bytes());
+        h.update(im.to_le_bytes());
+    }
+    Scalar::from_bytes_mod_order_wide(&h.finalize().into())
+}
+
+/// Blinding scalar `r` derived from a commitment nonce.
+fn blinding(nonce: &[u8; NONCE_LEN]) -> Scalar {
+    hash_to_scalar(&[BLINDING_DOMAIN, nonce])
+}
+
+/// Second generator `H`, with no known discrete log relative to `G`.
+fn generator_h() -> RistrettoPoint {
+    RistrettoPoint::from_uniform_bytes(&Sha512::digest(GENERATOR_DOMAIN).into())
+}
+
+/// Fiat–Shamir challenge scalar binding the commitment, the announcement and
+/// the verifier's challenge.
+fn challenge_scalar(
+    commitment: &CompressedRistretto,
+    announcement: &CompressedRistretto,
+    challenge: &[u8; NONCE_LEN],
+) -> Scalar {
+    hash_to_scalar(&[PROOF_DOMAIN, commitment.as_bytes(), announcement.as_bytes(), challenge])
+}
+
+/// Reduce the SHA-512 hash of `parts` to a scalar.
+fn hash_to_scalar(parts: &[&[u8]]) -> Scalar {
+    let mut h = Sha512::new();
+    for part in parts {
+        h.update(part);
+    }
+    Scalar::from_bytes_mod_order_wide(&h.finalize().into())
+}
+
+/// Constant-time byte comparison.
+fn ct_eq(a: &[u8; 32], b: &[u8; 32]) -> bool {
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commitment_opens_only_to_its_qid() {
+        let qid = QNum::from_digits(&[4, 0, 4]);
+        let nonce = [7u8; NONCE_LEN];
+        let c = commit(&qid, &nonce);
+        assert!(open(&c, &qid, &nonce));
+        assert!(!open(&c, &qid, &[8u8; NONCE_LEN]));
+        assert!(!open(&c, &QNum::from_digits(&[4, 0, 5]), &nonce));
+    }
+
+    #[test]
+    fn equal_qids_verify_and_unequal_fail() {
+        let alice = QNum::from_digits(&[1, 2, 3, 4, 5, 6]);
+        let bob_same = QNum::from_digits(&[1, 2, 3, 4, 5, 6]);
+        let bob_other = QNum::from_digits(&[1, 2, 3, 4, 5, 7]);
+        let nonce = [0x11u8; NONCE_LEN];
+        let published = commit(&alice, &nonce);
+        let challenge = [0x5au8; NONCE_LEN];
+
+        let proof = prove_equal(&alice, &nonce, &challenge);
+        assert!(verify_equal(&proof, &published, &bob_same, &challenge));
+        assert!(!verify_equal(&proof, &published, &bob_other, &challenge));
+
+        // a proof is bound to the challenge it answered
+        assert!(!verify_equal(&proof, &published, &bob_same, &[0xa5u8; NONCE_LEN]));
+    }
+
+    #[test]
+    fn commitment_ignores_rounding_noise() {
+        let qid = QNum::from_digits(&[3, 1, 4]);
+        let mut noisy = qid.clone();
+        let c = &mut noisy.0[1].amps[1];
+        c.re = (c.re.into_inner() - 1e-15).into();
+        let nonce = [9u8; NONCE_LEN];
+        assert_eq!(commit(&qid, &nonce), commit(&noisy, &nonce));
+    }
+
+    #[test]
+    fn proof_is_bound_to_the_commitment_nonce() {
+        let qid = QNum::from_digits(&[9, 8, 7]);
+        let published = commit(&qid, &[1u8; NONCE_LEN]);
+        let challenge = [2u8; NONCE_LEN];
+
+        // knowing the QID is not enough: a proof under another nonce fails
+        let forged = prove_equal(&qid, &[3u8; NONCE_LEN], &challenge);
+        assert!(!verify_equal(&forged, &published, &qid, &challenge));
+
+        // nor is a disclosed nonce with a response for a different QID
+        let spliced = prove_equal(&QNum::from_digits(&[9, 8, 6]), &[1u8; NONCE_LEN], &challenge);
+        assert!(!verify_equal(&spliced, &published, &qid, &challenge));
+    }
+}
@@ -1,7 +1,8 @@
 //! QLink Configuration
 //!
 //! Defines the `QLinkConfig` struct for the QLink crate, including
-//! quantum identity length, consent probability, and metrics toggles.
+//! quantum identity length, consent probability or graded consent levels,
+//! and metrics toggles.
 //! Supports loading from a TOML file.
 
 use serde::{Deserialize, Serialize};
@@ -34,6 +35,12 @@ pub struct QLinkConfig {
     #[serde(default = "default_consent_probability")]
     pub consent_probability: f64,
 
+    /// Relative weights of graded consent levels `0..n` (level 0 = none,
+    /// the highest level = full consent). When empty, consent is binary
+    /// with `consent_probability`.
+    #[serde(default)]
+    pub consent_levels: Vec<f64>,
+
     /// Enable collection and export of Prometheus metrics.
     #[serde(default = "default_enable_metrics")]
     pub enable_metrics: bool,
@@ -44,6 +51,7 @@ impl Default for QLinkConfig {
         QLinkConfig {
             qid_length: default_qid_length(),
             consent_probability: default_consent_probability(),
+            consent_levels: Vec::new(),
             enable_metrics: default_enable_metrics(),
         }
     }
@@ -70,6 +78,17 @@ impl QLinkConfig {
         let cfg = toml::from_str(&s)?;
         Ok(cfg)
     }
+
+    /// Weight of each consent level: `consent_levels` if configured,
+    /// otherwise `[1 − p, p]` for `p = consent_probability`.
+    pub fn consent_distribution(&self) -> Vec<f64> {
+        if self.consent_levels.is_empty() {
+            let p = self.consent_probability;
+            vec![1.0 - p, p]
+        } else {
+            self.consent_levels.clone()
+        }
+    }
 }
 
 #[cfg(test)]
@@ -83,6 +102,8 @@ mod tests {
         let cfg = QLinkConfig::default();
         assert_eq!(cfg.qid_length, 6);
         assert!((cfg.consent_probability - 0.5).abs() < 1e-12);
+        assert!(cfg.consent_levels.is_empty());
+        assert_eq!(cfg.consent_distribution(), vec![0.5, 0.5]);
         assert!(!cfg.enable_metrics);
     }
 
@@ -91,6 +112,7 @@ mod tests {
         let toml = r#"
             qid_length = 8
             consent_probability = 0.75
+            consent_levels = [0.2, 0.3, 0.5]
             enable_metrics = true
         "#;
         let mut file = NamedTempFile::new().expect("temp file");
@@ -98,6 +120,7 @@ mod tests {
         let cfg = QLinkConfig::load(file.path()).expect("load config");
         assert_eq!(cfg.qid_length, 8);
         assert!((cfg.consent_probability - 0.75).abs() < 1e-12);
+        assert_eq!(cfg.consent_distribution(), vec![0.2, 0.3, 0.5]);
         assert!(cfg.enable_metrics);
     }
 
@@ -1,14 +1,17 @@
 //! Conscious Consent module for QLink — Qublis v2.0
 //!
 //! Manages user consent as quantum‐number states (`QNum`), with probabilistic
-//! superposition based on configured consent probability.  Records consent
-//! grants and revocations as `ConsentRecord`s, and exposes metrics.
+//! superposition based on configured consent probability, or over graded
+//! consent levels (e.g. none/partial/full).  Records consent grants and
+//! revocations as `ConsentRecord`s, and exposes metrics.
 
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
 
 use std::collections::HashMap;
+use num_complex::Complex;
 use qublis_qnum::{QNum, Qid};
+use rand::{thread_rng, Rng};
 use crate::{
     config::QLinkConfig,
     error::QLinkError,
@@ -37,49 +40,67 @@ impl ConsciousConsent {
 
     /// Request consent for the given `qid` under `terms` at `timestamp`.
     ///
-    /// Generates a single‐digit superposed QNum with amplitudes
-    /// √p for consent (digit 1) and √(1−p) for denial (digit 0),
-    /// where `p = config.consent_probability`.
-    /// Measures (collapses) to a classical boolean, stores the record,
-    /// and returns it.
+    /// Generates a single‐digit superposed Qid with one basis value per
+    /// consent level, amplitude √pᵢ for level `i`, where the `pᵢ` are
+    /// `config.consent_distribution()` normalized to sum to 1. For binary
+    /// consent that is √(1−p) for denial (digit 0) and √p for consent
+    /// (digit 1), `p = config.consent_probability`.
+    /// Measures (collapses) to a level, stores the record, and returns it.
+    ///
+    /// Fails if the configured levels are fewer than two, more than a digit
+    /// can hold, negative, non-finite, or all zero.
     pub fn request_consent(
         &mut self,
         qid: &QNum,
         terms: &str,
         timestamp: u64,
+    ) -> Result<ConsentRecord, QLinkError> {
+        self.request_consent_with(qid, terms, timestamp, &mut thread_rng())
+    }
+
+    /// Like [`request_consent`](Self::request_consent), but collapses the
+    /// consent digit with the supplied RNG.
+    pub fn request_consent_with<R: Rng + ?Sized>(
+        &mut self,
+        qid: &QNum,
+        terms: &str,
+        timestamp: u64,
+        rng: &mut R,
     ) -> Result<ConsentRecord, QLinkError> {
         // Prevent duplicate requests
         if self.consents.contains_key(qid) {
             return Err(QLinkError::ConsentError("already requested".into()));
         }
 
-        // Build superposed Qid: |0⟩ vs |1⟩
-        let p = self.config.consent_probability;
-        let amp_yes = (p).sqrt();
-        let amp_no = (1.0 - p).sqrt();
-        let mut super_qid = Qid::from_f64([
-            num_complex::Complex::new(amp_no, 0.0), // |0⟩: denial
-            num_complex::Complex::new(amp_yes, 0.0), // |1⟩: consent
-            // rest of basis unused
-            num_complex::Complex::new(0.0, 0.0),
-            num_complex::Complex::new(0.0, 0.0),
-            num_complex::Complex::new(0.0, 0.0),
-            num_complex::Complex::new(0.0, 0.0),
-            num_complex::Complex::new(0.0, 0.0),
-            num_complex::Complex::new(0.0, 0.0),
-            num_complex::Complex::new(0.0, 0.0),
-            num_complex::Complex::new(0.0, 0.0),
-        ]);
-
-        // Collapse to classical digit
-        let result = super_qid.measure();
-        let granted = result == 1;
+        // Build superposed Qid: |0⟩ (none) … |n−1⟩ (full)
+        let weights = self.config.consent_distribution();
+        if weights.len() < 2 || weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+            return Err(QLinkError::ConsentError(format!(
+                "invalid consent levels {:?}",
+                weights
+            )));
+        }
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 {
+            return Err(QLinkError::ConsentError("consent levels sum to zero".into()));
+        }
+        let amps = weights
+            .iter()
+            .map(|w| Complex::new((w / total).sqrt(), 0.0))
+            .collect();
+        let super_qid = Qid::try_from_f64_vec(amps)
+            .map_err(|e| QLinkError::ConsentError(e.to_string()))?;
+
+        // Collapse to a classical level
+        let level = super_qid.measure_with(rng) as u8;
+        let granted = level > 0;
 
         // Build and store the record
         let record = ConsentRecord {
             qid: qid.clone(),
             terms: terms.to_string(),
             granted,
+            level,
             timestamp,
         };
         self.consents.insert(qid.clone(), record.clone());
@@ -97,7 +118,8 @@ impl ConsciousConsent {
         self.consents.get(qid)
     }
 
-    /// Revoke consent for `qid`, updating the record’s `granted` flag to `false`.
+    /// Revoke consent for `qid`, updating the record’s `granted` flag to `false`
+    /// and its level to 0.
     /// Returns the updated record or an error if not found.
     pub fn revoke_consent(&mut self, qid: &QNum) -> Result<ConsentRecord, QLinkError> {
         let rec = self
@@ -105,6 +127,7 @@ impl ConsciousConsent {
             .get_mut(qid)
             .ok_or_else(|| QLinkError::ConsentError("no existing consent".into()))?;
         rec.granted = false;
+        rec.level = 0;
         self.metrics.inc_counter("consents_revoked", 1);
         Ok(rec.clone())
     }
@@ -134,6 +157,7 @@ mod tests {
         let qid = dummy_qnum();
         let rec = cc.request_consent(&qid, "terms", 12345).unwrap();
         assert!(rec.granted);
+        assert_eq!(rec.level, 1);
         assert_eq!(rec.terms, "terms");
         assert_eq!(rec.timestamp, 12345);
 
@@ -163,6 +187,7 @@ mod tests {
         let _ = cc.request_consent(&qid, "t", 100).unwrap();
         let rec = cc.revoke_consent(&qid).unwrap();
         assert!(!rec.granted);
+        assert_eq!(rec.level, 0);
 
         // Nonexistent revoke fails
         let missing = QNum::from_digits(&[0]);
@@ -183,4 +208,40 @@ mod tests {
         assert!(prom.contains("qlink_consents_requested 1"));
         assert!(prom.contains("qlink_consents_revoked 1"));
     }
+
+    #[test]
+    fn graded_consent_follows_configured_distribution() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut cfg = QLinkConfig::default();
+        cfg.consent_levels = vec![0.2, 0.3, 0.5]; // none / partial / full
+        let mut cc = ConsciousConsent::new(&cfg);
+        let mut rng = StdRng::seed_from_u64(1782);
+
+        let trials = 3000u64;
+        let mut counts = [0u32; 3];
+        for i in 0..trials {
+            let rec = cc
+                .request_consent_with(&QNum::from_u64(i, 4), "graded", i, &mut rng)
+                .unwrap();
+            assert!(rec.level <= 2, "level {} out of range", rec.level);
+            assert_eq!(rec.granted, rec.level > 0);
+            counts[rec.level as usize] += 1;
+        }
+        for (count, p) in counts.iter().zip(&cfg.consent_levels) {
+            let freq = *count as f64 / trials as f64;
+            assert!((freq - p).abs() < 0.05, "frequency {} vs configured {}", freq, p);
+        }
+    }
+
+    #[test]
+    fn invalid_consent_levels_rejected() {
+        let mut cfg = QLinkConfig::default();
+        cfg.consent_levels = vec![1.0];
+        let mut cc = ConsciousConsent::new(&cfg);
+        let err = cc.request_consent(&dummy_qnum(), "t", 0).unwrap_err();
+        assert!(matches!(err, QLinkError::ConsentError(_)));
+        assert!(cc.get_consent(&dummy_qnum()).is_none());
+    }
 }
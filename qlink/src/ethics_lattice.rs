@@ -9,7 +9,8 @@
 
 use std::collections::HashMap;
 
-use qublis_qnum::{QNum, entangle};
+use qublis_qnum::{QNum, Qid, entangle};
+use rand::Rng;
 use crate::{
     config::QLinkConfig,
     error::QLinkError,
@@ -89,6 +90,35 @@ impl EthicsLattice {
         results
     }
 
+    /// Deterministic expected weight of each principle, without collapsing.
+    ///
+    /// The weight is the mean of the MSB digit's distribution, `Σ i·|αᵢ|²`,
+    /// the same digit `evaluate` samples. Empty states weigh 0.0.
+    pub fn evaluate_expected(&self) -> HashMap<Principle, f64> {
+        self.nodes
+            .iter()
+            .map(|(name, qnum)| (name.clone(), qnum.0.first().map_or(0.0, expected_digit)))
+            .collect()
+    }
+
+    /// Evaluate every principle in one pass, returning `(expected, sampled)`
+    /// where `expected` matches [`EthicsLattice::evaluate_expected`] and
+    /// `sampled` is a collapse of the MSB digit drawn from `rng`.
+    ///
+    /// Records one evaluation event.
+    pub fn evaluate_full<R: Rng + ?Sized>(&mut self, rng: &mut R) -> HashMap<Principle, (f64, u8)> {
+        let mut results = HashMap::new();
+        for (name, qnum) in &self.nodes {
+            let entry = match qnum.0.first() {
+                Some(qid) => (expected_digit(qid), qid.measure_with(rng) as u8),
+                None => (0.0, 0),
+            };
+            results.insert(name.clone(), entry);
+        }
+        self.metrics.inc_counter("lattice_evaluations", 1);
+        results
+    }
+
     /// Retrieve the current `QNum` state for a principle, if present.
     pub fn get_state(&self, name: &Principle) -> Option<&QNum> {
         self.nodes.get(name)
@@ -100,6 +130,15 @@ impl EthicsLattice {
     }
 }
 
+/// Mean digit value of `qid`'s probability distribution.
+fn expected_digit(qid: &Qid) -> f64 {
+    qid.amps
+        .iter()
+        .enumerate()
+        .map(|(i, c)| i as f64 * c.norm_sqr().into_inner())
+        .sum()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -116,7 +155,7 @@ mod tests {
         let mut lat = EthicsLattice::new(&cfg);
         assert!(lat.add_principle("fairness".into(), classical_qnum(3)).is_ok());
         let state = lat.get_state(&"fairness".into()).unwrap();
-        assert_eq!(state.measure(), vec![3]);
+        assert_eq!(state.clone().measure(), vec![3]);
     }
 
     #[test]
@@ -146,6 +185,27 @@ mod tests {
         assert!((w1 == 2 && w2 == 7) || (w1 == 7 && w2 == 2));
     }
 
+    #[test]
+    fn evaluate_full_matches_expected_and_samples_valid_digit() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let cfg = QLinkConfig::default();
+        let mut lat = EthicsLattice::new(&cfg);
+        lat.add_principle("p1".into(), classical_qnum(2)).unwrap();
+        lat.add_principle("p2".into(), classical_qnum(7)).unwrap();
+        lat.entangle_principles(&"p1".into(), &"p2".into()).unwrap();
+
+        let expected = lat.evaluate_expected();
+        let full = lat.evaluate_full(&mut StdRng::seed_from_u64(1));
+        assert_eq!(full.len(), 2);
+        for (name, (exp, sampled)) in &full {
+            assert!((exp - expected[name]).abs() < 1e-12);
+            assert!(*sampled == 2 || *sampled == 7);
+        }
+        assert!((expected["p1"] - 4.5).abs() < 1e-9);
+    }
+
     #[test]
     fn entangle_missing_principle_error() {
         let cfg = QLinkConfig::default();
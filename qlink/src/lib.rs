@@ -24,15 +24,21 @@ pub mod types;
 pub mod error;
 /// Metrics collector for QLink events
 pub mod metrics;
+/// Pedersen commitments and private QID equality proofs
+pub mod commitment;
+/// Log redaction: print QIDs as stable hashes instead of amplitudes
+pub mod redact;
 /// Prelude: convenient re-exports of primary QLink types
 pub mod prelude;
 
 pub use config::QLinkConfig;
-pub use qid_layer::QidLayer;
+pub use qid_layer::{IdentityStore, InMemoryIdentityStore, QidLayer};
 pub use ethics_lattice::EthicsLattice;
 pub use conscious_consent::ConsciousConsent;
 pub use mutation_engine::MutationEngine;
 pub use types::{IdentityState, ConsentRecord};
 pub use metrics::QLinkMetrics;
 pub use error::QLinkError;
+pub use redact::Redact;
+pub use commitment::{prove_equal, verify_equal, Commitment, EqualityProof};
 pub use prelude::*;
@@ -117,7 +117,7 @@ mod tests {
 
         let updated = engine.apply_updates(&identity).unwrap();
         // After entanglement, measuring yields either original or swapped digits
-        let meas = updated.qid.measure();
+        let meas = updated.qid.clone().measure();
         assert!(meas == vec![4,2] || meas == vec![2,4]);
 
         // Metrics recorded
@@ -131,7 +131,7 @@ mod tests {
         let mut engine = MutationEngine::new(&default_cfg());
         let identity = IdentityState::new(QNum::from_digits(&[5,5]), 0);
         let result = engine.apply_updates(&identity).unwrap();
-        assert_eq!(result.qid.measure(), identity.qid.measure());
+        assert_eq!(result.qid.clone().measure(), identity.qid.clone().measure());
     }
 
     #[test]
@@ -7,13 +7,14 @@
 #![forbid(unsafe_code)]
 
 pub use crate::config::QLinkConfig;
-pub use crate::qid_layer::QidLayer;
+pub use crate::qid_layer::{IdentityStore, InMemoryIdentityStore, QidLayer};
 pub use crate::ethics_lattice::EthicsLattice;
 pub use crate::conscious_consent::ConsciousConsent;
 pub use crate::mutation_engine::MutationEngine;
 pub use crate::types::{IdentityState, ConsentRecord, PolicyUpdate};
 pub use crate::metrics::QLinkMetrics;
 pub use crate::error::QLinkError;
+pub use crate::redact::Redact;
 
 #[cfg(test)]
 mod tests {
@@ -2,33 +2,80 @@
 //!
 //! This module provides generation, registration, lookup, and revocation of
 //! quantum identities (QIDs) on‐chain.  A QID is represented as a `QNum`,
-//! derived deterministically from a seed; identities are stored in an
-//! in‐memory registry (simulating a blockchain state), with lifecycle events
-//! recorded in metrics.
+//! derived deterministically from a seed; identities are stored behind the
+//! [`IdentityStore`] trait (in memory by default, simulating a blockchain
+//! state), with lifecycle events recorded in metrics.
 
 use std::collections::HashMap;
+use std::fmt;
+use log::debug;
 use qublis_qnum::QNum;
 use crate::config::QLinkConfig;
 use crate::error::QLinkError;
 use crate::types::IdentityState;
 use crate::metrics::QLinkMetrics;
+use crate::redact::Redact;
+
+/// Storage backend for registered identities.
+///
+/// Implement this to persist identities (e.g. to sled or a file) instead of
+/// keeping them in RAM; inject it with [`QidLayer::with_store`]. Stores must
+/// be `Send + Sync` so a `QidLayer` can be shared across threads.
+pub trait IdentityStore: fmt::Debug + Send + Sync {
+    /// Fetch the state registered under `qid`, if any.
+    fn get(&self, qid: &QNum) -> Option<IdentityState>;
+
+    /// Insert or replace the state registered under `state.qid`.
+    fn put(&mut self, state: IdentityState);
+
+    /// Whether an identity is registered under `qid`.
+    fn contains(&self, qid: &QNum) -> bool {
+        self.get(qid).is_some()
+    }
+}
+
+/// Default in-memory identity store.
+#[derive(Debug, Default)]
+pub struct InMemoryIdentityStore {
+    /// Registry mapping QNum → IdentityState
+    registry: HashMap<QNum, IdentityState>,
+}
+
+impl IdentityStore for InMemoryIdentityStore {
+    fn get(&self, qid: &QNum) -> Option<IdentityState> {
+        self.registry.get(qid).cloned()
+    }
+
+    fn put(&mut self, state: IdentityState) {
+        self.registry.insert(state.qid.clone(), state);
+    }
+
+    fn contains(&self, qid: &QNum) -> bool {
+        self.registry.contains_key(qid)
+    }
+}
 
 /// QidLayer manages the lifecycle of quantum identities.
 #[derive(Debug)]
 pub struct QidLayer {
     config: QLinkConfig,
     metrics: QLinkMetrics,
-    /// Registry mapping QNum → IdentityState
-    registry: HashMap<QNum, IdentityState>,
+    store: Box<dyn IdentityStore>,
 }
 
 impl QidLayer {
-    /// Create a new `QidLayer` using the given configuration.
+    /// Create a new `QidLayer` using the given configuration and an
+    /// in-memory store.
     pub fn new(config: &QLinkConfig) -> Self {
+        Self::with_store(config, Box::new(InMemoryIdentityStore::default()))
+    }
+
+    /// Create a new `QidLayer` backed by the given identity store.
+    pub fn with_store(config: &QLinkConfig, store: Box<dyn IdentityStore>) -> Self {
         QidLayer {
             config: config.clone(),
             metrics: QLinkMetrics::new(),
-            registry: HashMap::new(),
+            store,
         }
     }
 
@@ -52,29 +99,32 @@ impl QidLayer {
         qid: QNum,
         created: u64
     ) -> Result<IdentityState, QLinkError> {
-        if self.registry.contains_key(&qid) {
+        if self.store.contains(&qid) {
             return Err(QLinkError::IdentityAlreadyExists(qid));
         }
         let state = IdentityState::new(qid.clone(), created);
-        self.registry.insert(qid.clone(), state.clone());
+        self.store.put(state.clone());
         self.metrics.inc_counter("identities_registered", 1);
+        debug!("registered identity {}", qid.redacted());
         Ok(state)
     }
 
     /// Look up an identity’s state by its `qid`.
-    pub fn get_identity(&self, qid: &QNum) -> Option<&IdentityState> {
-        self.registry.get(qid)
+    pub fn get_identity(&self, qid: &QNum) -> Option<IdentityState> {
+        self.store.get(qid)
     }
 
     /// Revoke an existing identity, marking it as inactive.
     ///
     /// Returns the updated `IdentityState` or an error if not found.
     pub fn revoke_identity(&mut self, qid: &QNum) -> Result<IdentityState, QLinkError> {
-        let state = self.registry.get_mut(qid)
+        let mut state = self.store.get(qid)
             .ok_or_else(|| QLinkError::IdentityNotFound(qid.clone()))?;
         state.revoked = true;
+        self.store.put(state.clone());
         self.metrics.inc_counter("identities_revoked", 1);
-        Ok(state.clone())
+        debug!("revoked identity {}", qid.redacted());
+        Ok(state)
     }
 
     /// Export current metrics (e.g., for Prometheus).
@@ -86,6 +136,7 @@ impl QidLayer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use qublis_qnum::testing::CallLog;
     use qublis_qnum::QNum;
 
     fn default_cfg() -> QLinkConfig {
@@ -149,6 +200,55 @@ mod tests {
         matches!(err, QLinkError::IdentityNotFound(_));
     }
 
+    /// Store that records every call while delegating to an in-memory map.
+    #[derive(Debug, Default)]
+    struct MockStore {
+        inner: InMemoryIdentityStore,
+        calls: CallLog,
+    }
+
+    impl IdentityStore for MockStore {
+        fn get(&self, qid: &QNum) -> Option<IdentityState> {
+            self.calls.record("get");
+            self.inner.get(qid)
+        }
+
+        fn put(&mut self, state: IdentityState) {
+            self.calls.record("put");
+            self.inner.put(state)
+        }
+
+        fn contains(&self, qid: &QNum) -> bool {
+            self.calls.record("contains");
+            self.inner.contains(qid)
+        }
+    }
+
+    #[test]
+    fn operations_route_through_store() {
+        let store = MockStore::default();
+        let calls = store.calls.clone();
+        let mut layer = QidLayer::with_store(&default_cfg(), Box::new(store));
+        let qid = layer.generate_qid(b"erin");
+
+        layer.register_identity(qid.clone(), 7).unwrap();
+        assert_eq!(calls.calls(), ["contains", "put"]);
+
+        assert_eq!(layer.get_identity(&qid).unwrap().created, 7);
+        assert_eq!(calls.last(), Some("get"));
+
+        calls.clear();
+        assert!(layer.revoke_identity(&qid).unwrap().revoked);
+        assert_eq!(calls.calls(), ["get", "put"]);
+        assert!(layer.get_identity(&qid).unwrap().revoked);
+    }
+
+    #[test]
+    fn layer_is_thread_safe() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<QidLayer>();
+    }
+
     #[test]
     fn metrics_increment_on_operations() {
         let mut layer = QidLayer::new(&default_cfg());
@@ -0,0 +1,90 @@
+//! Log redaction for quantum identities.
+//!
+//! A QID's `Debug` output exposes its full amplitude vector, which is identity
+//! material. [`Redact::redacted`] wraps a `QNum` so that formatting it yields
+//! only a stable short hash, safe to write to logs.
+
+use std::fmt;
+use qublis_qnum::QNum;
+
+/// Formats a QID as `qid#<hash>` without revealing its digits or amplitudes.
+#[derive(Clone, Copy)]
+pub struct RedactedQid<'a>(&'a QNum);
+
+impl RedactedQid<'_> {
+    /// Stable 64-bit FNV-1a hash over the QID's canonical key (see
+    /// [`QNum::canonical_key`]), so states equal up to float noise share a
+    /// fingerprint just as they compare equal.
+    ///
+    /// Unlike the derived `Hash`, this does not depend on the std hasher and is
+    /// identical across processes and releases.
+    pub fn fingerprint(&self) -> u64 {
+        const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0000_0100_0000_01b3;
+        let mut h = OFFSET;
+        for (basis, re, im) in self.0.canonical_key() {
+            let bytes = [basis].into_iter().chain(re.to_le_bytes()).chain(im.to_le_bytes());
+            for b in bytes {
+                h ^= b as u64;
+                h = h.wrapping_mul(PRIME);
+            }
+        }
+        h
+    }
+}
+
+impl fmt::Display for RedactedQid<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "qid#{:012x}", self.fingerprint() >> 16)
+    }
+}
+
+impl fmt::Debug for RedactedQid<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Extension trait for logging QIDs without exposing their contents.
+pub trait Redact {
+    /// Wrap `self` in a formatter that prints only a stable short hash.
+    fn redacted(&self) -> RedactedQid<'_>;
+}
+
+impl Redact for QNum {
+    fn redacted(&self) -> RedactedQid<'_> {
+        RedactedQid(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacted_is_stable_and_hides_digits() {
+        let qid = QNum::from_digits(&[7, 3, 1, 9, 5, 2]);
+        let a = qid.redacted().to_string();
+        let b = qid.clone().redacted().to_string();
+        assert_eq!(a, b);
+        assert_eq!(format!("{:?}", qid.redacted()), a);
+        assert!(a.starts_with("qid#"));
+        assert!(!a.contains("731952"));
+        assert!(!a.contains("amps"));
+
+        let other = QNum::from_digits(&[7, 3, 1, 9, 5, 3]);
+        assert_ne!(other.redacted().to_string(), a);
+    }
+
+    #[test]
+    fn nearly_equal_qids_share_a_fingerprint() {
+        let qid = QNum::from_digits(&[7, 3, 1]);
+        let mut noisy = qid.clone();
+        let amp = &mut noisy.0[1].amps[3];
+        amp.re = (amp.re.into_inner() - 1e-15).into();
+        assert_ne!(noisy.0[1].amps[3], qid.0[1].amps[3]);
+        assert_eq!(noisy, qid);
+        assert_eq!(noisy.redacted().fingerprint(), qid.redacted().fingerprint());
+        assert_eq!(noisy.redacted().to_string(), qid.redacted().to_string());
+    }
+}
@@ -33,6 +33,10 @@ pub struct ConsentRecord {
     pub terms: String,
     /// Whether consent was granted (`true`) or denied (`false`).
     pub granted: bool,
+    /// Collapsed consent level: 0 = none, up to the highest configured
+    /// level (1 for binary consent). `granted` is `level > 0`.
+    #[serde(default)]
+    pub level: u8,
     /// UNIX timestamp when the consent was recorded.
     pub timestamp: u64,
 }
@@ -57,7 +61,7 @@ mod tests {
     fn identity_state_new_defaults_revoked_false() {
         let q = QNum::from_digits(&[1,2,3]);
         let state = IdentityState::new(q.clone(), 1_600_000_000);
-        assert_eq!(state.qid.measure(), q.measure());
+        assert_eq!(state.qid.clone().measure(), q.clone().measure());
         assert_eq!(state.created, 1_600_000_000);
         assert!(!state.revoked);
     }
@@ -69,11 +73,13 @@ mod tests {
             qid: q.clone(),
             terms: "T&C".into(),
             granted: true,
+            level: 1,
             timestamp: 12345,
         };
-        assert_eq!(rec.qid.measure(), q.measure());
+        assert_eq!(rec.qid.clone().measure(), q.clone().measure());
         assert_eq!(rec.terms, "T&C");
         assert!(rec.granted);
+        assert_eq!(rec.level, 1);
         assert_eq!(rec.timestamp, 12345);
     }
 
@@ -86,7 +92,7 @@ mod tests {
             timestamp: 999,
         };
         assert_eq!(upd.policy_id, "P1");
-        assert_eq!(upd.parameters.measure(), params.measure());
+        assert_eq!(upd.parameters.clone().measure(), params.clone().measure());
         assert_eq!(upd.timestamp, 999);
     }
 }
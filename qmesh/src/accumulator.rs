@@ -0,0 +1,217 @@
+//! QNum Merkle Accumulator for QMesh — Qublis v2.0
+//!
+//! Commits to a sequence of block states (`QNum`s) with a binary SHA-256
+//! Merkle tree, so a light client holding only the 32-byte root can check
+//! that a block's state belongs to the retrochain from a logarithmic-size
+//! [`MembershipProof`].
+//!
+//! Leaves hash a state's canonical form (see `QNum::canonical_key`), so a
+//! state proves membership for any copy equal to it up to float noise. A
+//! level with an odd number of nodes promotes its last node unchanged.
+
+use qublis_qnum::QNum;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+
+/// A SHA-256 digest: a leaf, interior node or root of the accumulator.
+pub type Digest = [u8; 32];
+
+/// Domain separation tag for leaf digests.
+const LEAF_DOMAIN: &[u8] = b"qublis/qmesh/accumulator-leaf/v1";
+/// Domain separation tag for interior node digests.
+const NODE_DOMAIN: &[u8] = b"qublis/qmesh/accumulator-node/v1";
+
+/// Evidence that a state is the leaf at `index` of an accumulator with
+/// `leaf_count` leaves.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MembershipProof {
+    /// Position of the proven leaf, in insertion order.
+    pub index: usize,
+    /// Number of leaves the accumulator held when the proof was made.
+    pub leaf_count: usize,
+    /// Sibling digests from the leaf level up to just below the root.
+    pub siblings: Vec<Digest>,
+}
+
+/// `QnumAccumulator` is an append-only Merkle accumulator over block states.
+#[derive(Clone, Debug, Default)]
+pub struct QnumAccumulator {
+    leaves: Vec<Digest>,
+}
+
+impl QnumAccumulator {
+    /// Create an empty accumulator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append `block`'s state and return its leaf index.
+    pub fn add(&mut self, block: &QNum) -> usize {
+        self.leaves.push(leaf_digest(block));
+        self.leaves.len() - 1
+    }
+
+    /// Number of states added.
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Whether no state has been added.
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Root digest committing to every state added so far; all zeros while
+    /// the accumulator is empty.
+    pub fn root(&self) -> Digest {
+        let mut level = self.leaves.clone();
+        if level.is_empty() {
+            return [0; 32];
+        }
+        while level.len() > 1 {
+            level = parent_level(&level);
+        }
+        level[0]
+    }
+
+    /// Proof that `block` was added, or `None` if it never was. If the same
+    /// state was added more than once, the first occurrence is proven.
+    pub fn prove_membership(&self, block: &QNum) -> Option<MembershipProof> {
+        let leaf = leaf_digest(block);
+        let index = self.leaves.iter().position(|l| *l == leaf)?;
+
+        let mut siblings = Vec::new();
+        let mut level = self.leaves.clone();
+        let mut idx = index;
+        while level.len() > 1 {
+            let sibling = idx ^ 1;
+            if sibling < level.len() {
+                siblings.push(level[sibling]);
+            }
+            level = parent_level(&level);
+            idx /= 2;
+        }
+        Some(MembershipProof {
+            index,
+            leaf_count: self.leaves.len(),
+            siblings,
+        })
+    }
+
+    /// Check that `proof` shows `block` to be a member of the accumulator
+    /// whose root is `root`. Needs no access to the accumulator itself.
+    pub fn verify(root: &Digest, block: &QNum, proof: &MembershipProof) -> bool {
+        if proof.index >= proof.leaf_count {
+            return false;
+        }
+        let mut siblings = proof.siblings.iter();
+        let mut digest = leaf_digest(block);
+        let (mut idx, mut width) = (proof.index, proof.leaf_count);
+        while width > 1 {
+            if idx % 2 == 1 {
+                let Some(left) = siblings.next() else { return false };
+                digest = node_digest(left, &digest);
+            } else if idx + 1 < width {
+                let Some(right) = siblings.next() else { return false };
+                digest = node_digest(&digest, right);
+            }
+            idx /= 2;
+            width = width.div_ceil(2);
+        }
+        siblings.next().is_none() && digest == *root
+    }
+}
+
+/// The level above `level`, promoting an unpaired last node unchanged.
+fn parent_level(level: &[Digest]) -> Vec<Digest> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => node_digest(left, right),
+            [only] => *only,
+            _ => unreachable!("chunks(2) yields one or two nodes"),
+        })
+        .collect()
+}
+
+/// Digest of `state`'s radices and canonical key.
+fn leaf_digest(state: &QNum) -> Digest {
+    let mut h = Sha256::new();
+    h.update(LEAF_DOMAIN);
+    h.update((state.len() as u64).to_le_bytes());
+    for qid in &state.0 {
+        h.update((qid.radix() as u64).to_le_bytes());
+    }
+    for (basis, re, im) in state.canonical_key() {
+        h.update([basis]);
+        h.update(re.to_le_bytes());
+        h.update(im.to_le_bytes());
+    }
+    h.finalize().into()
+}
+
+/// Digest of an interior node from its two children.
+fn node_digest(left: &Digest, right: &Digest) -> Digest {
+    Sha256::new()
+        .chain_update(NODE_DOMAIN)
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blocks(n: u8) -> Vec<QNum> {
+        (0..n).map(|i| QNum::from_digits(&[i / 10, i % 10])).collect()
+    }
+
+    #[test]
+    fn member_proves_and_non_member_fails() {
+        let mut acc = QnumAccumulator::new();
+        for block in blocks(7) {
+            acc.add(&block);
+        }
+        let root = acc.root();
+
+        // every leaf proves, including the promoted last one
+        for (i, block) in blocks(7).iter().enumerate() {
+            let proof = acc.prove_membership(block).expect("member");
+            assert_eq!(proof.index, i);
+            assert!(QnumAccumulator::verify(&root, block, &proof));
+        }
+
+        let outsider = QNum::from_digits(&[9, 9]);
+        assert!(acc.prove_membership(&outsider).is_none());
+        let proof = acc.prove_membership(&blocks(7)[3]).unwrap();
+        assert!(!QnumAccumulator::verify(&root, &outsider, &proof));
+    }
+
+    #[test]
+    fn proofs_do_not_survive_tampering_or_growth() {
+        let mut acc = QnumAccumulator::new();
+        assert_eq!(acc.root(), [0; 32]);
+        let all = blocks(5);
+        for block in &all {
+            acc.add(block);
+        }
+        let root = acc.root();
+        let proof = acc.prove_membership(&all[2]).unwrap();
+
+        let mut moved = proof.clone();
+        moved.index = 3;
+        assert!(!QnumAccumulator::verify(&root, &all[2], &moved));
+        let mut forged = proof.clone();
+        forged.siblings[0][0] ^= 1;
+        assert!(!QnumAccumulator::verify(&root, &all[2], &forged));
+
+        // the root changes as states are added; old proofs bind the old root
+        acc.add(&QNum::from_digits(&[4, 2]));
+        assert_ne!(acc.root(), root);
+        assert!(!QnumAccumulator::verify(&acc.root(), &all[2], &proof));
+        let fresh = acc.prove_membership(&all[2]).unwrap();
+        assert!(QnumAccumulator::verify(&acc.root(), &all[2], &fresh));
+    }
+}
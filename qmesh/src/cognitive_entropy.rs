@@ -60,9 +60,9 @@ impl CognitiveEntropy {
     pub fn analyze(&mut self, dag: &EntropicDag) -> CognitiveReport {
         // Compute per-node entropies
         let mut node_entropies = HashMap::new();
-        for idx in dag.graph.node_indices() {
-            let data = &dag.graph[idx];
-            let ent = dag.graph[idx].state.entropy();
+        for idx in dag.node_indices() {
+            let data = dag.node_data(idx);
+            let ent = dag.node_entropy(idx);
             node_entropies.insert(data.id.clone(), ent);
             self.metrics.inc_counter("node_entropy_computed", 1);
         }
@@ -130,6 +130,32 @@ impl CognitiveEntropy {
             .sum::<f64>() / n;
         Some(var)
     }
+
+    /// Export the history window as a Prometheus time series, one
+    /// `qmesh_global_entropy_history{index="i"}` sample per entry, oldest first.
+    pub fn export_history_prometheus(&self) -> String {
+        let mut out = String::from("# TYPE qmesh_global_entropy_history gauge\n");
+        for (i, e) in self.history.iter().enumerate() {
+            out.push_str(&format!(
+                "qmesh_global_entropy_history{{index=\"{}\"}} {}\n",
+                i, e
+            ));
+        }
+        out
+    }
+
+    /// Export the history window as a JSON array of
+    /// `{"index": i, "global_entropy": e}` samples, oldest first.
+    pub fn export_history_json(&self) -> Result<String, String> {
+        let samples: Vec<_> = self
+            .history
+            .iter()
+            .enumerate()
+            .map(|(i, e)| serde_json::json!({ "index": i, "global_entropy": e }))
+            .collect();
+        serde_json::to_string_pretty(&samples)
+            .map_err(|e| format!("Entropy history JSON export failed: {}", e))
+    }
 }
 
 #[cfg(test)]
@@ -172,7 +198,7 @@ mod tests {
         };
         let mut ce = CognitiveEntropy::new(&cfg);
         // Build DAG with three nodes all |5⟩ -> entropy 0 each
-        let mut dag = build_simple_dag(&["A","B","C"], 5);
+        let dag = build_simple_dag(&["A","B","C"], 5);
         let report = ce.analyze(&dag);
 
         assert_eq!(report.global_entropy, 0.0);
@@ -190,11 +216,14 @@ mod tests {
         };
         let mut ce = CognitiveEntropy::new(&cfg);
         // Push two values via analyze
-        let mut dag1 = build_simple_dag(&["X"], 1);
+        let dag1 = build_simple_dag(&["X"], 1);
         ce.analyze(&dag1); // global=0
         let mut dag2 = build_simple_dag(&["Y"], 2);
-        // Make Y superposed to yield entropy >0
-        dag2.add_edge(dag2.add_node("Y".into(), QNum::from_digits(&[1])), dag2.add_node("Z".into(), QNum::from_digits(&[2])), 1.0).unwrap();
+        // Propagating Y into Z superposes Z, yielding entropy >0
+        let y = dag2.add_node("Y".into(), QNum::from_digits(&[1]));
+        let z = dag2.add_node("Z".into(), QNum::from_digits(&[2]));
+        dag2.add_edge(y, z, 1.0).unwrap();
+        dag2.propagate();
         let report2 = ce.analyze(&dag2);
         assert!(report2.global_entropy > 0.0);
 
@@ -204,4 +233,27 @@ mod tests {
         assert_eq!(ce.history_mean(), Some((0.0 + report2.global_entropy) / 2.0));
         assert!(ce.history_variance().unwrap() >= 0.0);
     }
+
+    #[test]
+    fn test_export_history() {
+        let cfg = QMeshConfig {
+            history_window: Some(3),
+            ..Default::default()
+        };
+        let mut ce = CognitiveEntropy::new(&cfg);
+        let dag = build_simple_dag(&["A"], 4);
+        for _ in 0..5 {
+            ce.analyze(&dag);
+        }
+
+        let prom = ce.export_history_prometheus();
+        let samples: Vec<&str> = prom.lines().filter(|l| !l.starts_with('#')).collect();
+        assert_eq!(samples.len(), 3);
+        assert_eq!(samples[2], "qmesh_global_entropy_history{index=\"2\"} 0");
+
+        let json: serde_json::Value =
+            serde_json::from_str(&ce.export_history_json().unwrap()).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), 3);
+        assert_eq!(json[1]["index"], 1);
+    }
 }
@@ -78,7 +78,7 @@ mod tests {
 
     #[test]
     fn load_from_toml() {
-        let mut file = NamedTempFile::new().expect("create temp file");
+        let file = NamedTempFile::new().expect("create temp file");
         let toml = r#"
             history_window = 7
             enable_metrics = true
@@ -97,7 +97,7 @@ mod tests {
 
     #[test]
     fn invalid_toml_errs_parse() {
-        let mut file = NamedTempFile::new().unwrap();
+        let file = NamedTempFile::new().unwrap();
         fs::write(file.path(), "not = valid = toml").unwrap();
         let err = QMeshConfig::load(file.path()).unwrap_err();
         matches!(err, ConfigError::Parse(_));
@@ -5,19 +5,25 @@
 //! weight.  Propagation entangles connected nodes’ QNum states, and we can
 //! compute per-node and total entropy as a measure of uncertainty.
 
-use petgraph::graph::{DiGraph, NodeIndex};
-use petgraph::visit::Topo;
+use std::collections::HashMap;
+
+use petgraph::graph::DiGraph;
+pub use petgraph::graph::NodeIndex;
+use petgraph::visit::{EdgeRef, Topo};
+use petgraph::Direction;
 use petgraph::algo::is_cyclic_directed;
 use crate::config::QMeshConfig;
 use crate::error::QMeshError;
 use crate::metrics::QMeshMetrics;
 use crate::types::NodeId;
-use qublis_qnum::{QNum, entangle};
+use qublis_qnum::{QNum, entangle_weighted};
 
 /// Data stored at each node: an identifier and a QNum state.
 #[derive(Clone, Debug)]
 pub struct NodeData {
+    /// Node identifier.
     pub id: NodeId,
+    /// Current QNum state of the node.
     pub state: QNum,
 }
 
@@ -61,17 +67,23 @@ impl EntropicDag {
     }
 
     /// Propagate entropic influence across the DAG:
-    /// for each edge (u→v), entangle u.state with v.state scaled by `weight`.
+    /// for each edge (u→v), entangle u.state with v.state with strength
+    /// `weight`, clamped to `[0, 1]` (see [`entangle_weighted`]).
     /// Process nodes in topological order to respect causality.
     pub fn propagate(&mut self) {
         let mut topo = Topo::new(&self.graph);
         while let Some(idx) = topo.next(&self.graph) {
-            for edge in self.graph.edges(idx) {
-                let target = edge.target();
-                let weight = *edge.weight();
-                // scale v.state toward u.state by entanglement
-                // here we call entangle on the two QNums directly
-                entangle(&mut self.graph[idx].state, &mut self.graph[target].state);
+            let out: Vec<(NodeIndex, f64)> = self
+                .graph
+                .edges(idx)
+                .map(|edge| (edge.target(), *edge.weight()))
+                .collect();
+            for (target, weight) in out {
+                // weight sets how strongly v.state is mixed with u.state;
+                // NaN weights carry no influence
+                let strength = if weight.is_nan() { 0.0 } else { weight.clamp(0.0, 1.0) };
+                let (parent, child) = self.graph.index_twice_mut(idx, target);
+                entangle_weighted(&mut parent.state, &mut child.state, strength);
                 // record metric
                 self.metrics.inc_counter("entanglements", 1);
                 self.metrics.set_gauge("last_influence_weight", weight);
@@ -91,6 +103,35 @@ impl EntropicDag {
             .sum()
     }
 
+    /// Iterate over the indices of all nodes.
+    pub fn node_indices(&self) -> impl Iterator<Item = NodeIndex> + '_ {
+        self.graph.node_indices()
+    }
+
+    /// Iterate over the direct parents of `idx`.
+    pub fn parents(&self, idx: NodeIndex) -> impl Iterator<Item = NodeIndex> + '_ {
+        self.graph.neighbors_directed(idx, Direction::Incoming)
+    }
+
+    /// Height of `idx`: the number of edges on the longest path back to a
+    /// root. Roots have height 0.
+    pub fn height(&self, idx: NodeIndex) -> u64 {
+        let mut heights: HashMap<NodeIndex, u64> = HashMap::new();
+        let mut topo = Topo::new(&self.graph);
+        while let Some(n) = topo.next(&self.graph) {
+            let h = self
+                .parents(n)
+                .map(|p| heights[&p] + 1)
+                .max()
+                .unwrap_or(0);
+            if n == idx {
+                return h;
+            }
+            heights.insert(n, h);
+        }
+        0
+    }
+
     /// Get a reference to the node data by index.
     pub fn node_data(&self, idx: NodeIndex) -> &NodeData {
         &self.graph[idx]
@@ -134,14 +175,33 @@ mod tests {
         matches!(err, QMeshError::CycleDetected);
     }
 
+    #[test]
+    fn test_height_follows_longest_parent_path() {
+        let cfg = QMeshConfig::default();
+        let mut dag = EntropicDag::new(&cfg);
+        // genesis → a → b, plus a shortcut genesis → b
+        let g = dag.add_node("G".into(), QNum::zero(1));
+        let a = dag.add_node("A".into(), QNum::zero(1));
+        let b = dag.add_node("B".into(), QNum::zero(1));
+        dag.add_edge(g, a, 1.0).unwrap();
+        dag.add_edge(a, b, 1.0).unwrap();
+        dag.add_edge(g, b, 1.0).unwrap();
+        assert_eq!(dag.height(g), 0);
+        assert_eq!(dag.height(a), 1);
+        assert_eq!(dag.height(b), 2);
+        let mut parents: Vec<_> = dag.parents(b).collect();
+        parents.sort();
+        assert_eq!(parents, vec![g, a]);
+    }
+
     #[test]
     fn test_propagation_and_entropy() {
         let cfg = QMeshConfig::default();
         let mut dag = EntropicDag::new(&cfg);
 
         // Two nodes with distinct classical states
-        let mut s1 = QNum::from_digits(&[1]);
-        let mut s2 = QNum::from_digits(&[2]);
+        let s1 = QNum::from_digits(&[1]);
+        let s2 = QNum::from_digits(&[2]);
         let n1 = dag.add_node("N1".into(), s1.clone());
         let n2 = dag.add_node("N2".into(), s2.clone());
 
@@ -160,4 +220,27 @@ mod tests {
         // Total entropy > 0
         assert!(dag.total_entropy() > 0.0);
     }
+
+    #[test]
+    fn test_propagation_uses_edge_weight() {
+        let cfg = QMeshConfig::default();
+        let mut dag = EntropicDag::new(&cfg);
+        let n1 = dag.add_node("N1".into(), QNum::from_digits(&[1]));
+        let n2 = dag.add_node("N2".into(), QNum::from_digits(&[2]));
+        let n3 = dag.add_node("N3".into(), QNum::from_digits(&[3]));
+        dag.add_edge(n1, n2, 0.0).unwrap();
+        dag.propagate();
+        assert_eq!(dag.total_entropy(), 0.0, "zero-weight edge must not mix states");
+
+        dag.add_edge(n2, n3, 0.3).unwrap();
+        dag.propagate();
+        let weak = dag.node_entropy(n3);
+
+        let mut strong = EntropicDag::new(&cfg);
+        let m2 = strong.add_node("N2".into(), QNum::from_digits(&[2]));
+        let m3 = strong.add_node("N3".into(), QNum::from_digits(&[3]));
+        strong.add_edge(m2, m3, 1.0).unwrap();
+        strong.propagate();
+        assert!(weak > 0.0 && weak < strong.node_entropy(m3));
+    }
 }
@@ -41,6 +41,7 @@ mod tests {
 
     #[test]
     fn from_config_error() {
+        use serde::de::Error as _;
         let cfg_err = ConfigError::Parse(toml::de::Error::custom("oops"));
         let err: QMeshError = cfg_err.into();
         assert!(err.to_string().starts_with("configuration error:"));
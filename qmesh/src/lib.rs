@@ -4,6 +4,7 @@
 //! - `EntropicDag`: a directed acyclic graph of `QNum` states with entropic propagation.
 //! - `CognitiveEntropy`: utilities to compute and monitor mesh‐wide entropy patterns.
 //! - `RetrochainTracker`: track entropic “retrochain” dependencies across blocks.
+//! - `QnumAccumulator`: Merkle accumulator for light-client membership proofs.
 //! - `QMeshConfig`: configuration loader for QMesh settings.
 //! - `QMeshError`: error definitions.
 //! - `QMeshMetrics`: domain‐specific metrics.
@@ -18,6 +19,8 @@ pub mod entropic_dag;
 pub mod cognitive_entropy;
 /// Retrochain dependency tracking
 pub mod retrochain_tracker;
+/// Merkle accumulator over block states
+pub mod accumulator;
 /// Configuration loader and defaults
 pub mod config;
 /// Core data types (NodeId, NodeData, etc.)
@@ -33,6 +36,7 @@ pub mod prelude;
 pub use config::QMeshConfig;
 pub use entropic_dag::EntropicDag;
 pub use cognitive_entropy::CognitiveEntropy;
-pub use retrochain_tracker::RetrochainTracker;
+pub use retrochain_tracker::{ChainStore, InMemoryChainStore, RetrochainTracker};
+pub use accumulator::{MembershipProof, QnumAccumulator};
 pub use error::QMeshError;
 pub use metrics::QMeshMetrics;
@@ -9,7 +9,8 @@
 pub use crate::config::QMeshConfig;
 pub use crate::entropic_dag::EntropicDag;
 pub use crate::cognitive_entropy::CognitiveEntropy;
-pub use crate::retrochain_tracker::RetrochainTracker;
+pub use crate::retrochain_tracker::{ChainStore, InMemoryChainStore, RetrochainTracker};
+pub use crate::accumulator::{MembershipProof, QnumAccumulator};
 pub use crate::error::QMeshError;
 pub use crate::metrics::QMeshMetrics;
 pub use crate::types::NodeId;
@@ -24,7 +25,7 @@ mod tests {
         // Config
         let cfg: QMeshConfig = QMeshConfig::default();
         // Create an EntropicDag
-        let mut dag = EntropicDag::new(&cfg);
+        let dag = EntropicDag::new(&cfg);
         assert_eq!(dag.node_count(), 0);
         // CognitiveEntropy requires an EntropicDag
         let mut analyzer = CognitiveEntropy::new(&cfg);
@@ -2,16 +2,85 @@
 //!
 //! Tracks the sequence of entropic states (QNums) associated with successive
 //! blocks, and lets you retrieve the “retrochain” history or compute entropic
-//! differences along that chain.
+//! differences along that chain. The chain itself lives behind the
+//! [`ChainStore`] trait so long histories can be backed by disk.
 
 use crate::config::QMeshConfig;
 use crate::metrics::QMeshMetrics;
 use qublis_qnum::QNum;
 use std::collections::HashMap;
+use std::fmt;
 
 /// Identifier for a block in the retrochain.
 pub type BlockId = String;
 
+/// Append-only storage backend for the retrochain.
+///
+/// Implement this to keep large histories on disk; inject it with
+/// [`RetrochainTracker::with_store`]. Stores must be `Clone` (so the tracker
+/// can be cloned) and `Send + Sync` (so it can be shared across threads).
+pub trait ChainStore: fmt::Debug + Send + Sync + ChainStoreClone {
+    /// Append `(block, state)` at the end of the chain.
+    fn append(&mut self, block: BlockId, state: QNum);
+
+    /// Position of `block` in the chain, if recorded.
+    fn position(&self, block: &BlockId) -> Option<usize>;
+
+    /// Entry at position `idx`, if any.
+    fn get(&self, idx: usize) -> Option<&(BlockId, QNum)>;
+
+    /// Entries `0..=end`, in chain order.
+    fn prefix(&self, end: usize) -> Vec<(BlockId, QNum)> {
+        (0..=end).filter_map(|i| self.get(i).cloned()).collect()
+    }
+}
+
+/// Object-safe cloning for boxed [`ChainStore`]s, implemented for every
+/// `Clone` store.
+pub trait ChainStoreClone {
+    /// Clone `self` into a new boxed store.
+    fn clone_box(&self) -> Box<dyn ChainStore>;
+}
+
+impl<S: ChainStore + Clone + 'static> ChainStoreClone for S {
+    fn clone_box(&self) -> Box<dyn ChainStore> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn ChainStore> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// Default in-memory chain store.
+#[derive(Clone, Debug, Default)]
+pub struct InMemoryChainStore {
+    chain: Vec<(BlockId, QNum)>,
+    index: HashMap<BlockId, usize>,
+}
+
+impl ChainStore for InMemoryChainStore {
+    fn append(&mut self, block: BlockId, state: QNum) {
+        let idx = self.chain.len();
+        self.chain.push((block.clone(), state));
+        self.index.insert(block, idx);
+    }
+
+    fn position(&self, block: &BlockId) -> Option<usize> {
+        self.index.get(block).copied()
+    }
+
+    fn get(&self, idx: usize) -> Option<&(BlockId, QNum)> {
+        self.chain.get(idx)
+    }
+
+    fn prefix(&self, end: usize) -> Vec<(BlockId, QNum)> {
+        self.chain[0..=end].to_vec()
+    }
+}
+
 /// `RetrochainTracker` records a linear sequence of `(BlockId, QNum)` states
 /// and provides methods to retrieve the chain up to any recorded block, as
 /// well as to compute entropy diffs along that chain.
@@ -19,18 +88,21 @@ pub type BlockId = String;
 pub struct RetrochainTracker {
     config: QMeshConfig,
     metrics: QMeshMetrics,
-    chain: Vec<(BlockId, QNum)>,
-    index: HashMap<BlockId, usize>,
+    store: Box<dyn ChainStore>,
 }
 
 impl RetrochainTracker {
-    /// Create a new, empty `RetrochainTracker`.
+    /// Create a new, empty `RetrochainTracker` backed by an in-memory store.
     pub fn new(config: &QMeshConfig) -> Self {
+        Self::with_store(config, Box::new(InMemoryChainStore::default()))
+    }
+
+    /// Create a `RetrochainTracker` backed by the given chain store.
+    pub fn with_store(config: &QMeshConfig, store: Box<dyn ChainStore>) -> Self {
         RetrochainTracker {
             config: config.clone(),
             metrics: QMeshMetrics::new(),
-            chain: Vec::new(),
-            index: HashMap::new(),
+            store,
         }
     }
 
@@ -38,20 +110,18 @@ impl RetrochainTracker {
     ///
     /// If `block` was recorded before, this is a no-op.
     pub fn record_block(&mut self, block: BlockId, state: QNum) {
-        if self.index.contains_key(&block) {
+        if self.store.position(&block).is_some() {
             return;
         }
-        let idx = self.chain.len();
-        self.chain.push((block.clone(), state));
-        self.index.insert(block, idx);
+        self.store.append(block, state);
         self.metrics.inc_counter("blocks_recorded", 1);
     }
 
     /// Retrieve the recorded `QNum` state for `block`, if any.
     pub fn get_state(&self, block: &BlockId) -> Option<&QNum> {
-        self.index
-            .get(block)
-            .and_then(|&i| self.chain.get(i).map(|(_, q)| q))
+        self.store
+            .position(block)
+            .and_then(|i| self.store.get(i).map(|(_, q)| q))
     }
 
     /// Return the retrochain for `block`: a vector of `(BlockId, QNum)` from
@@ -59,11 +129,8 @@ impl RetrochainTracker {
     ///
     /// If `block` is not recorded, returns `None`.
     pub fn retrochain(&mut self, block: &BlockId) -> Option<Vec<(BlockId, QNum)>> {
-        let &i = self.index.get(block)?;
-        let subchain = self.chain[0..=i]
-            .iter()
-            .cloned()
-            .collect();
+        let i = self.store.position(block)?;
+        let subchain = self.store.prefix(i);
         self.metrics.inc_counter("retrochain_retrieved", 1);
         Some(subchain)
     }
@@ -88,6 +155,7 @@ impl RetrochainTracker {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use qublis_qnum::testing::CallLog;
     use qublis_qnum::{QNum, Qid};
     use num_complex::Complex;
 
@@ -103,8 +171,8 @@ mod tests {
         rt.record_block("genesis".into(), make_qnum(1));
         rt.record_block("block2".into(), make_qnum(2));
 
-        assert_eq!(rt.get_state(&"genesis".into()).unwrap().measure(), vec![1]);
-        assert_eq!(rt.get_state(&"block2".into()).unwrap().measure(), vec![2]);
+        assert_eq!(rt.get_state(&"genesis".into()).unwrap().clone().measure(), vec![1]);
+        assert_eq!(rt.get_state(&"block2".into()).unwrap().clone().measure(), vec![2]);
         assert!(rt.get_state(&"unknown".into()).is_none());
     }
 
@@ -125,6 +193,66 @@ mod tests {
         assert!(rt.retrochain(&"X".into()).is_none());
     }
 
+    /// Store that records every call while delegating to an in-memory chain.
+    #[derive(Clone, Debug, Default)]
+    struct MockStore {
+        inner: InMemoryChainStore,
+        calls: CallLog,
+    }
+
+    impl ChainStore for MockStore {
+        fn append(&mut self, block: BlockId, state: QNum) {
+            self.calls.record("append");
+            self.inner.append(block, state)
+        }
+
+        fn position(&self, block: &BlockId) -> Option<usize> {
+            self.calls.record("position");
+            self.inner.position(block)
+        }
+
+        fn get(&self, idx: usize) -> Option<&(BlockId, QNum)> {
+            self.calls.record("get");
+            self.inner.get(idx)
+        }
+    }
+
+    #[test]
+    fn test_operations_route_through_store() {
+        let store = MockStore::default();
+        let calls = store.calls.clone();
+        let mut rt = RetrochainTracker::with_store(&QMeshConfig::default(), Box::new(store));
+
+        rt.record_block("A".into(), make_qnum(3));
+        rt.record_block("B".into(), make_qnum(4));
+        assert_eq!(calls.calls(), ["position", "append", "position", "append"]);
+
+        calls.clear();
+        assert_eq!(rt.get_state(&"B".into()), Some(&make_qnum(4)));
+        assert_eq!(calls.calls(), ["position", "get"]);
+
+        // default `prefix` walks the store entry by entry
+        calls.clear();
+        let diffs = rt.diffs(&"B".into()).unwrap();
+        assert_eq!(diffs.len(), 2);
+        assert_eq!(calls.calls(), ["position", "get", "get"]);
+    }
+
+    #[test]
+    fn test_clone_is_independent() {
+        let mut rt = RetrochainTracker::new(&QMeshConfig::default());
+        rt.record_block("A".into(), make_qnum(1));
+        let snapshot = rt.clone();
+        rt.record_block("B".into(), make_qnum(2));
+
+        assert_eq!(snapshot.get_state(&"A".into()), Some(&make_qnum(1)));
+        assert!(snapshot.get_state(&"B".into()).is_none());
+        assert!(rt.get_state(&"B".into()).is_some());
+
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<RetrochainTracker>();
+    }
+
     #[test]
     fn test_diffs_computation() {
         let cfg = QMeshConfig::default();
@@ -134,7 +262,7 @@ mod tests {
         let mut amps = [Complex::new(0.0,0.0); 10];
         amps[0] = Complex::new(1.0/2f64.sqrt(), 0.0);
         amps[1] = Complex::new(1.0/2f64.sqrt(), 0.0);
-        let super_q = QNum::new(amps);
+        let super_q = QNum::new(vec![Qid::from_f64(amps)]);
 
         rt.record_block("P".into(), make_qnum(0));
         rt.record_block("Q".into(), super_q.clone());
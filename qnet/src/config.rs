@@ -3,15 +3,24 @@
 //! Defines the `QNetConfig` struct for routing, teleportation, and metrics settings,
 //! with support for loading from a TOML file.
 
+use crate::error::QNetError;
 use serde::{Deserialize, Serialize};
 use std::{fs, path::Path};
 use thiserror::Error;
 
+/// Largest supported `index_radix`: every digit value must fit in a `u8`.
+const MAX_INDEX_RADIX: usize = 256;
+
 /// Default number of candidate paths to superpose.
 fn default_k_paths() -> usize {
     4
 }
 
+/// Default radix of the `QNum` digits that encode path indices.
+fn default_index_radix() -> usize {
+    10
+}
+
 /// QNet configuration parameters.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct QNetConfig {
@@ -19,6 +28,11 @@ pub struct QNetConfig {
     #[serde(default = "default_k_paths")]
     pub k_paths: usize,
 
+    /// Radix of the routing `QNum`: path indices are encoded in this base
+    /// (e.g. 2 for binary, 16 for hex).
+    #[serde(default = "default_index_radix")]
+    pub index_radix: usize,
+
     /// Enable the teleportation overlay.
     #[serde(default)]
     pub enable_teleport: bool,
@@ -32,6 +46,7 @@ impl Default for QNetConfig {
     fn default() -> Self {
         QNetConfig {
             k_paths: default_k_paths(),
+            index_radix: default_index_radix(),
             enable_teleport: false,
             enable_metrics: false,
         }
@@ -59,6 +74,25 @@ impl QNetConfig {
         let cfg = toml::from_str(&content)?;
         Ok(cfg)
     }
+
+    /// Check that the settings are usable, returning `QNetError::ConfigError`
+    /// otherwise.
+    ///
+    /// Rejects `k_paths == 0` (every route would fail) and an `index_radix`
+    /// outside `2..=256`. Teleportation has no settings of its own beyond
+    /// `enable_teleport`, so it cannot currently contradict anything else.
+    pub fn validate(&self) -> Result<(), QNetError> {
+        if self.k_paths == 0 {
+            return Err(QNetError::ConfigError("k_paths must be at least 1".into()));
+        }
+        if !(2..=MAX_INDEX_RADIX).contains(&self.index_radix) {
+            return Err(QNetError::ConfigError(format!(
+                "index_radix must be in 2..={}, got {}",
+                MAX_INDEX_RADIX, self.index_radix
+            )));
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -71,13 +105,25 @@ mod tests {
     fn default_config_values() {
         let cfg = QNetConfig::default();
         assert_eq!(cfg.k_paths, 4);
+        assert_eq!(cfg.index_radix, 10);
         assert!(!cfg.enable_teleport);
         assert!(!cfg.enable_metrics);
     }
 
+    #[test]
+    fn validate_accepts_defaults_and_rejects_bad_values() {
+        assert!(QNetConfig::default().validate().is_ok());
+
+        let zero_paths = QNetConfig { k_paths: 0, ..Default::default() };
+        assert!(matches!(zero_paths.validate(), Err(QNetError::ConfigError(_))));
+
+        let unary = QNetConfig { index_radix: 1, ..Default::default() };
+        assert!(matches!(unary.validate(), Err(QNetError::ConfigError(_))));
+    }
+
     #[test]
     fn load_config_from_toml() {
-        let mut file = NamedTempFile::new().expect("create temp file");
+        let file = NamedTempFile::new().expect("create temp file");
         let toml = r#"
             k_paths = 7
             enable_teleport = true
@@ -92,7 +138,7 @@ mod tests {
 
     #[test]
     fn error_on_invalid_toml() {
-        let mut file = NamedTempFile::new().expect("create temp file");
+        let file = NamedTempFile::new().expect("create temp file");
         fs::write(file.path(), "not = valid = toml").unwrap();
         let err = QNetConfig::load(file.path()).unwrap_err();
         matches!(err, ConfigError::Parse(_));
@@ -4,7 +4,7 @@
 
 pub use crate::config::QNetConfig;
 pub use crate::router::Router;
-pub use crate::relay::Relay;
+pub use crate::relay::{Relay, RelayReceipt};
 pub use crate::teleport_core::TeleportCore;
 pub use crate::types::{NodeId, Path, Packet};
 pub use crate::metrics::QNetMetrics;
@@ -19,18 +19,18 @@ mod tests {
         // Config
         let cfg: QNetConfig = QNetConfig::default();
         // Router
-        let mut router = Router::new(&cfg);
+        let mut router = Router::new(&cfg).unwrap();
         router.add_edge("A".into(), "B".into());
         // Relay
-        let mut relay = Relay::new(&cfg);
+        let _relay = Relay::new(&cfg).unwrap();
         // TeleportCore
-        let tc = TeleportCore::new(&cfg);
+        let _tc = TeleportCore::new(&cfg);
         // Types
         let src: NodeId = "A".into();
         let dst: NodeId = "B".into();
         let path: Path = vec![src.clone(), dst.clone()];
         let pkt: Packet = Packet::from(vec![0x01, 0x02]);
-        assert_eq!(path, vec!["A".into(), "B".into()]);
+        assert_eq!(path, vec!["A".to_string(), "B".to_string()]);
         assert_eq!(pkt.as_slice(), &[0x01, 0x02]);
         // Metrics
         let mut metrics = QNetMetrics::new();
@@ -9,12 +9,13 @@ use crate::{
     error::QNetError,
     metrics::QNetMetrics,
     prelude::{Router, TeleportCore},
-    types::{NodeId, Packet},
+    types::{NodeId, Packet, Path},
 };
 use futures::future::try_join_all;
+use std::time::{Duration, Instant};
 
 #[cfg(test)]
-use super::tests::dummy_transport as transport;
+use self::tests::dummy_transport as transport;
 
 #[cfg(not(test))]
 mod transport {
@@ -31,6 +32,19 @@ mod transport {
 }
 // No `use self::transport;` needed here!
 
+/// Per-call record of a successful [`Relay::relay`], for logging or billing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RelayReceipt {
+    /// Path the packet took, from source to destination.
+    pub path: Path,
+    /// Number of hops (edges) along `path`.
+    pub hops: usize,
+    /// Whether the packet was teleported rather than forwarded hop-by-hop.
+    pub teleported: bool,
+    /// Wall-clock time spent relaying, including path selection.
+    pub duration: Duration,
+}
+
 /// Packet relay engine.
 #[derive(Clone)]
 pub struct Relay {
@@ -42,18 +56,35 @@ pub struct Relay {
 
 impl Relay {
     /// Create a new `Relay` with the given configuration.
-    pub fn new(config: &QNetConfig) -> Self {
+    ///
+    /// Fails if the configuration does not pass [`QNetConfig::validate`].
+    pub fn new(config: &QNetConfig) -> Result<Self, QNetError> {
         let teleport = if config.enable_teleport {
             Some(TeleportCore::new(config))
         } else {
             None
         };
-        Relay {
+        Ok(Relay {
             config: config.clone(),
-            router: Router::new(config),
+            router: Router::new(config)?,
             teleport,
             metrics: QNetMetrics::new(),
-        }
+        })
+    }
+
+    /// Access the internal metrics collector.
+    pub fn metrics(&self) -> &QNetMetrics {
+        &self.metrics
+    }
+
+    /// Mutable access to the internal metrics collector, e.g. to record a snapshot.
+    pub fn metrics_mut(&mut self) -> &mut QNetMetrics {
+        &mut self.metrics
+    }
+
+    /// Add an undirected link between two nodes to the relay's routing graph.
+    pub fn add_edge(&mut self, a: NodeId, b: NodeId) {
+        self.router.add_edge(a, b);
     }
 
     /// Relay a packet from `src` to `dst`.
@@ -63,12 +94,15 @@ impl Relay {
     /// 3. If teleportation is enabled, invokes `TeleportCore::teleport` to send  
     ///    the packet atomically along the entire path.  
     /// 4. Otherwise, forwards hop-by-hop.  
+    ///
+    /// Returns a [`RelayReceipt`] describing the delivery.
     pub async fn relay(
         &mut self,
         src: &NodeId,
         dst: &NodeId,
         packet: Packet,
-    ) -> Result<(), QNetError> {
+    ) -> Result<RelayReceipt, QNetError> {
+        let started = Instant::now();
         // Record attempt
         self.metrics.record_relay_attempt();
 
@@ -77,7 +111,8 @@ impl Relay {
         self.metrics.record_path_length(path.len());
 
         // Step 2: teleport or hop-by-hop
-        if let Some(tc) = &self.teleport {
+        let teleported = self.teleport.is_some();
+        if let Some(tc) = &mut self.teleport {
             // Teleport the packet in one shot
             tc.teleport(src, dst, &path, packet)
                 .await
@@ -109,7 +144,12 @@ impl Relay {
 
         // Record success
         self.metrics.record_relay_success();
-        Ok(())
+        Ok(RelayReceipt {
+            hops: path.len().saturating_sub(1),
+            path,
+            teleported,
+            duration: started.elapsed(),
+        })
     }
 }
 
@@ -124,7 +164,7 @@ mod tests {
         use super::{NodeId, Packet};
         use std::sync::Mutex;
         lazy_static::lazy_static! {
-            pub static ref LOG: Mutex<Vec<(NodeId, NodeId, Vec<u8>)>> = Mutex::new(vec![]);
+            pub static ref LOG: Mutex<Vec<(NodeId, NodeId, Packet)>> = Mutex::new(vec![]);
         }
         pub async fn send_direct(
             from: &NodeId,
@@ -141,13 +181,16 @@ mod tests {
         let mut cfg = QNetConfig::default();
         cfg.enable_teleport = false;
         cfg.k_paths = 1;
-        let mut relay = Relay::new(&cfg);
+        let mut relay = Relay::new(&cfg).unwrap();
         // Build graph: A-B-C
         relay.router.add_edge("A".into(), "B".into());
         relay.router.add_edge("B".into(), "C".into());
 
         let packet = Packet::from(vec![1, 2, 3]);
-        relay.relay(&"A".into(), &"C".into(), packet.clone()).await.unwrap();
+        let receipt = relay.relay(&"A".into(), &"C".into(), packet.clone()).await.unwrap();
+        assert_eq!(receipt.path, vec!["A".to_string(), "B".to_string(), "C".to_string()]);
+        assert_eq!(receipt.hops, 2);
+        assert!(!receipt.teleported);
 
         let log = dummy_transport::LOG.lock().unwrap();
         assert_eq!(log.len(), 2);
@@ -162,21 +205,25 @@ mod tests {
         let mut cfg = QNetConfig::default();
         cfg.enable_teleport = true;
         cfg.k_paths = 1;
-        let mut relay = Relay::new(&cfg);
+        let mut relay = Relay::new(&cfg).unwrap();
         relay.router.add_edge("X".into(), "Y".into());
 
         // Stub TeleportCore to record calls
         let packet = Packet::from(vec![9, 9]);
+        let expected = packet.clone();
         relay
             .teleport
             .as_mut()
             .unwrap()
-            .override_teleport_fn(|_src, _dst, path, pkt| {
-                assert_eq!(path, &vec!["X".into(), "Y".into()]);
-                assert_eq!(pkt, packet);
+            .override_teleport_fn(move |_src, _dst, path, pkt| {
+                assert_eq!(path, ["X".to_string(), "Y".to_string()]);
+                assert_eq!(pkt, expected);
                 Ok(())
             });
 
-        relay.relay(&"X".into(), &"Y".into(), packet).await.unwrap();
+        let receipt = relay.relay(&"X".into(), &"Y".into(), packet).await.unwrap();
+        assert_eq!(receipt.path, vec!["X".to_string(), "Y".to_string()]);
+        assert_eq!(receipt.hops, 1);
+        assert!(receipt.teleported);
     }
 }
@@ -5,8 +5,8 @@
 //! measures (collapses) that superposition to select a single path at relay time.
 
 use std::collections::{HashMap, VecDeque};
-use num_complex::Complex;
-use qublis_qnum::{QNum};
+use qublis_qnum::{seeded_measure, QNum, Qid};
+use rand::Rng;
 use crate::{
     config::QNetConfig,
     error::QNetError,
@@ -24,11 +24,14 @@ pub struct Router {
 
 impl Router {
     /// Create a new Router with the given QNetConfig.
-    pub fn new(config: &QNetConfig) -> Self {
-        Router {
+    ///
+    /// Fails if the configuration does not pass [`QNetConfig::validate`].
+    pub fn new(config: &QNetConfig) -> Result<Self, QNetError> {
+        config.validate()?;
+        Ok(Router {
             config: config.clone(),
             graph: HashMap::new(),
-        }
+        })
     }
 
     /// Add an undirected edge between two nodes in the graph.
@@ -67,63 +70,147 @@ impl Router {
         results
     }
 
+    /// Candidate paths from `src` to `dst` and the `QNum` whose measurement
+    /// selects among them, or [`QNetError::NoPath`] if there are none.
+    fn candidates(&self, src: &NodeId, dst: &NodeId) -> Result<(Vec<Path>, QNum), QNetError> {
+        let paths = self.k_shortest_paths(src, dst, self.config.k_paths);
+        if paths.is_empty() {
+            return Err(QNetError::NoPath(src.clone(), dst.clone()));
+        }
+        Ok((paths, self.route_qnum(src, dst)))
+    }
+
     /// Return a `QNum` superposition over up to `k_paths` candidate routes.
     ///
-    /// Each path is assigned equal amplitude; the basis states encode
-    /// the path index in fixed-width decimal digits.
+    /// Each path is assigned equal weight; the basis states encode
+    /// the path index in fixed-width digits of the configured `index_radix`.
+    ///
+    /// A `QNum`'s digits are measured independently, so when the indices need
+    /// more than one digit no register is uniform over exactly `0..k`.
+    /// Instead every digit is uniform, covering all `radix^width` indices
+    /// equally; selection rejects indices past the last path and measures
+    /// again, which keeps the choice unbiased.
     pub fn route_qnum(&self, src: &NodeId, dst: &NodeId) -> QNum {
         let paths = self.k_shortest_paths(src, dst, self.config.k_paths);
         let k = paths.len().max(1);
-        // Determine width in decimal digits to encode indices [0..k)
-        let width = ((k as f64).log10().ceil() as usize).max(1);
+        let radix = self.config.index_radix;
+        // Determine width in digits to encode indices [0..k)
+        let width = index_width(k, radix);
+        if width > 1 {
+            return QNum::new(vec![Qid::uniform_in(radix); width]);
+        }
 
-        // Build superposed states: (digits_of_index, amplitude)
-        let amplitude = Complex::new(1.0, 0.0) / (k as f64).sqrt();
-        let states: Vec<(Vec<u8>, Complex<f64>)> = (0..k)
+        // Build equally weighted states: (digits_of_index, weight)
+        let states: Vec<(Vec<u8>, f64)> = (0..k)
             .map(|i| {
                 let mut digits = vec![0u8; width];
                 let mut idx = i;
                 for d in (0..width).rev() {
-                    digits[d] = (idx % 10) as u8;
-                    idx /= 10;
+                    digits[d] = (idx % radix) as u8;
+                    idx /= radix;
                 }
-                (digits, amplitude)
+                (digits, 1.0)
             })
             .collect();
 
-        QNum::from_superposed(states)
+        QNum::from_weighted_in(radix, &states)
     }
 
     /// Collapse the `QNum` to select one path, returning it or an error if none.
     pub fn route(&self, src: &NodeId, dst: &NodeId) -> Result<Path, QNetError> {
-        let paths = self.k_shortest_paths(src, dst, self.config.k_paths);
-        if paths.is_empty() {
-            return Err(QNetError::NoPath(src.clone(), dst.clone()));
+        let (paths, qnum) = self.candidates(src, dst)?;
+        let index = select_index(paths.len(), || qnum.clone().measure_u64() as usize);
+        Ok(paths[index].clone())
+    }
+
+    /// Like [`Router::route`], but collapses the `QNum` with
+    /// [`seeded_measure`] keyed by `seed`, so every node with the same graph
+    /// and seed selects the same path. Use this for consensus-critical
+    /// routing; the draw uses ChaCha20, whose output is fixed across
+    /// platforms and `rand` releases, unlike `StdRng`.
+    pub fn route_deterministic(&self, src: &NodeId, dst: &NodeId, seed: u64) -> Result<Path, QNetError> {
+        let (paths, qnum) = self.candidates(src, dst)?;
+        let mut attempt = 0u64;
+        let index = select_index(paths.len(), || {
+            // each retry after a rejected index draws under a fresh key
+            let key = [seed.to_le_bytes(), attempt.to_le_bytes()].concat();
+            attempt += 1;
+            let (digits, _) = seeded_measure(&key, &qnum);
+            index_value(&digits, self.config.index_radix)
+        });
+        Ok(paths[index].clone())
+    }
+
+    /// Empirical selection frequency of each candidate path from `src` to
+    /// `dst` over `samples` collapses drawn from `rng`, including paths that
+    /// were never chosen (frequency `0.0`).
+    ///
+    /// Selection goes through the same code as [`Router::route`], so the
+    /// frequencies are those `route` realizes. Use it to check realized
+    /// traffic against the intended equal weights: each frequency should
+    /// approach `1 / paths`.
+    pub fn path_distribution<R: Rng + ?Sized>(
+        &self,
+        src: &NodeId,
+        dst: &NodeId,
+        samples: usize,
+        rng: &mut R,
+    ) -> Result<HashMap<Path, f64>, QNetError> {
+        let (paths, qnum) = self.candidates(src, dst)?;
+        let mut counts = vec![0usize; paths.len()];
+        for _ in 0..samples {
+            counts[select_index(paths.len(), || qnum.clone().measure_u64_with(rng) as usize)] += 1;
         }
+        let total = samples.max(1) as f64;
+        Ok(paths
+            .into_iter()
+            .zip(counts)
+            .map(|(path, count)| (path, count as f64 / total))
+            .collect())
+    }
+}
 
-        // Measure superposed path indices
-        let mut qnum = self.route_qnum(src, dst);
-        let digits = qnum.measure();
-        let index = digits.iter().fold(0usize, |acc, &d| acc * 10 + d as usize);
+/// Draw path indices from `draw` until one falls in `0..paths`.
+///
+/// Rejecting out-of-range indices, rather than wrapping them with `%`, keeps
+/// every path equally likely when `draw` is uniform over a wider range.
+fn select_index(paths: usize, mut draw: impl FnMut() -> usize) -> usize {
+    loop {
+        let index = draw();
+        if index < paths {
+            return index;
+        }
+    }
+}
 
-        // If the measured index is out of bounds, wrap around
-        let chosen = paths.get(index % paths.len())
-            .cloned()
-            .ok_or_else(|| QNetError::NoPath(src.clone(), dst.clone()))?;
+/// Path index encoded by base-`radix` `digits`, most significant first.
+fn index_value(digits: &[u8], radix: usize) -> usize {
+    digits.iter().fold(0, |acc, &d| acc * radix + d as usize)
+}
 
-        Ok(chosen)
+/// Smallest number of base-`radix` digits (at least one) that can encode
+/// every index in `0..k`.
+fn index_width(k: usize, radix: usize) -> usize {
+    let mut width = 1;
+    let mut capacity = radix;
+    while capacity < k {
+        capacity = capacity.saturating_mul(radix);
+        width += 1;
     }
+    width
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::config::QNetConfig;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
 
     fn build_simple_graph() -> Router {
         let mut cfg = QNetConfig::default();
         cfg.k_paths = 4;
-        let mut r = Router::new(&cfg);
+        let mut r = Router::new(&cfg).unwrap();
         // Graph: A—B—C, A—D—C
         r.add_edge("A".into(), "B".into());
         r.add_edge("B".into(), "C".into());
@@ -157,18 +244,123 @@ mod tests {
         assert!(seen.contains(&0) && seen.contains(&1));
     }
 
+    #[test]
+    fn test_route_qnum_binary_index_encoding() {
+        let cfg = QNetConfig { k_paths: 4, index_radix: 2, ..Default::default() };
+        let mut r = Router::new(&cfg).unwrap();
+        // Graph: A—B—C, A—D—C, A—E—C
+        for mid in ["B", "D", "E"] {
+            r.add_edge("A".into(), mid.into());
+            r.add_edge(mid.into(), "C".into());
+        }
+
+        let qnum = r.route_qnum(&"A".into(), &"C".into());
+        // three indices need two binary digits: 00, 01, 10
+        assert_eq!(qnum.radix(), 2);
+        assert_eq!(qnum.len(), 2);
+        let mut seen = std::collections::HashSet::new();
+        for _ in 0..200 {
+            // every digit is uniform, so 0b11 appears too; selection rejects it
+            let index = qnum.clone().measure_u64();
+            assert!(index < 4);
+            seen.insert(index);
+        }
+        assert_eq!(seen.len(), 4);
+
+        let path = r.route(&"A".into(), &"C".into()).unwrap();
+        assert_eq!(path.len(), 3);
+        let mut rng = ChaCha20Rng::seed_from_u64(1769);
+        let dist = r.path_distribution(&"A".into(), &"C".into(), 6_000, &mut rng).unwrap();
+        for (path, freq) in &dist {
+            assert!((freq - 1.0 / 3.0).abs() < 0.04, "{:?} chosen with frequency {}", path, freq);
+        }
+        let chosen: std::collections::HashSet<Path> = (0..64)
+            .map(|seed| r.route_deterministic(&"A".into(), &"C".into(), seed).unwrap())
+            .collect();
+        assert_eq!(chosen.len(), 3);
+        assert_eq!(index_width(5, 2), 3);
+        assert_eq!(index_width(16, 16), 1);
+        assert_eq!(index_width(17, 16), 2);
+    }
+
     #[test]
     fn test_route_selects_valid_path() {
         let r = build_simple_graph();
         let path = r.route(&"A".into(), &"C".into()).unwrap();
-        assert!(path == vec!["A".into(), "B".into(), "C".into()] ||
-                path == vec!["A".into(), "D".into(), "C".into()]);
+        let via = |mid: &str| -> Path { vec!["A".into(), mid.into(), "C".into()] };
+        assert!(path == via("B") || path == via("D"));
+    }
+
+    #[test]
+    fn test_route_deterministic_is_seed_stable() {
+        let r = build_simple_graph();
+        let (a, c): (NodeId, NodeId) = ("A".into(), "C".into());
+
+        let first = r.route_deterministic(&a, &c, 42).unwrap();
+        for _ in 0..10 {
+            assert_eq!(r.route_deterministic(&a, &c, 42).unwrap(), first);
+        }
+
+        let chosen: std::collections::HashSet<Path> = (0..64)
+            .map(|seed| r.route_deterministic(&a, &c, seed).unwrap())
+            .collect();
+        assert_eq!(chosen.len(), 2, "different seeds should reach both paths");
+
+        assert!(matches!(
+            r.route_deterministic(&a, &"Z".into(), 42),
+            Err(QNetError::NoPath(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_path_distribution_is_fair_for_equal_weights() {
+        let r = build_simple_graph();
+        let (a, c): (NodeId, NodeId) = ("A".into(), "C".into());
+        let mut rng = ChaCha20Rng::seed_from_u64(7);
+
+        let dist = r.path_distribution(&a, &c, 4_000, &mut rng).unwrap();
+        assert_eq!(dist.len(), 2);
+        for (path, freq) in &dist {
+            assert!((freq - 0.5).abs() < 0.05, "{:?} chosen with frequency {}", path, freq);
+        }
+        assert!((dist.values().sum::<f64>() - 1.0).abs() < 1e-9);
+
+        let empty = r.path_distribution(&a, &c, 0, &mut rng).unwrap();
+        assert!(empty.values().all(|&f| f == 0.0));
+        assert!(matches!(
+            r.path_distribution(&a, &"Z".into(), 10, &mut rng),
+            Err(QNetError::NoPath(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_path_distribution_splits_three_paths_evenly() {
+        let cfg = QNetConfig { k_paths: 4, ..Default::default() };
+        let mut r = Router::new(&cfg).unwrap();
+        // Graph: A—B—C, A—D—C, A—E—C
+        for mid in ["B", "D", "E"] {
+            r.add_edge("A".into(), mid.into());
+            r.add_edge(mid.into(), "C".into());
+        }
+        let mut rng = ChaCha20Rng::seed_from_u64(1792);
+
+        let dist = r.path_distribution(&"A".into(), &"C".into(), 6_000, &mut rng).unwrap();
+        assert_eq!(dist.len(), 3);
+        for (path, freq) in &dist {
+            assert!((freq - 1.0 / 3.0).abs() < 0.04, "{:?} chosen with frequency {}", path, freq);
+        }
+    }
+
+    #[test]
+    fn test_new_rejects_zero_k_paths() {
+        let cfg = QNetConfig { k_paths: 0, ..Default::default() };
+        assert!(matches!(Router::new(&cfg), Err(QNetError::ConfigError(_))));
     }
 
     #[test]
     fn test_route_no_path_error() {
         let cfg = QNetConfig { k_paths: 2, ..Default::default() };
-        let r = Router::new(&cfg);
+        let r = Router::new(&cfg).unwrap();
         let err = r.route(&"X".into(), &"Y".into()).unwrap_err();
         matches!(err, QNetError::NoPath(_, _));
     }
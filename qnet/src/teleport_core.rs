@@ -32,7 +32,7 @@ mod transport {
 // Don't add `use self::transport;` -- it's accessible as `transport::` already.
 
 #[cfg(test)]
-use super::tests::transport;
+use self::tests::transport;
 
 /// Result type for teleport operations.
 pub type TeleportResult = Result<(), QNetError>;
@@ -59,6 +59,16 @@ impl TeleportCore {
         }
     }
 
+    /// Access the internal metrics collector.
+    pub fn metrics(&self) -> &QNetMetrics {
+        &self.metrics
+    }
+
+    /// Mutable access to the internal metrics collector, e.g. to record a snapshot.
+    pub fn metrics_mut(&mut self) -> &mut QNetMetrics {
+        &mut self.metrics
+    }
+
     /// Override the teleport function (used in tests to inject a stub).
     pub fn override_teleport_fn<F>(&mut self, func: F)
     where
@@ -133,9 +143,9 @@ mod tests {
     use std::sync::Mutex;
     use lazy_static::lazy_static;
 
-    /// Dummy transport logger for hop-by-hop fallback.
     lazy_static! {
-        static ref LOG: Mutex<Vec<(NodeId, NodeId, Vec<u8>)>> = Mutex::new(Vec::new());
+        /// Dummy transport logger for hop-by-hop fallback.
+        static ref LOG: Mutex<Vec<(NodeId, NodeId, Packet)>> = Mutex::new(Vec::new());
     }
 
     // Stub the real transport to use our LOG
@@ -154,7 +164,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_teleport_default_hop_by_hop() {
-        let mut cfg = QNetConfig::default();
+        let cfg = QNetConfig::default();
         let mut tc = TeleportCore::new(&cfg);
         let path = vec!["A".into(), "B".into(), "C".into()];
         let packet = Packet::from(vec![1, 2, 3]);
@@ -171,17 +181,18 @@ mod tests {
 
     #[tokio::test]
     async fn test_teleport_with_override() {
-        let mut cfg = QNetConfig::default();
+        let cfg = QNetConfig::default();
         let mut tc = TeleportCore::new(&cfg);
         let packet = Packet::from(vec![9, 9]);
         let path = vec!["X".into(), "Y".into()];
 
         // Install a stub that verifies its inputs
+        let (expected_path, expected_packet) = (path.clone(), packet.clone());
         tc.override_teleport_fn(move |src, dst, pth, pkt| {
-            assert_eq!(src, &"X");
-            assert_eq!(dst, &"Y");
-            assert_eq!(pth, &path[..]);
-            assert_eq!(pkt, packet);
+            assert_eq!(src, "X");
+            assert_eq!(dst, "Y");
+            assert_eq!(pth, &expected_path[..]);
+            assert_eq!(pkt, expected_packet);
             Ok(())
         });
 
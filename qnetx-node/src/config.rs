@@ -67,6 +67,15 @@ pub struct MetricsConfig {
 
     /// Globally enable or disable the metrics endpoint.
     pub enabled: bool,
+
+    /// How often (ms) subsystem metrics are snapshotted into their history.
+    #[serde(default = "default_snapshot_interval_ms")]
+    pub snapshot_interval_ms: u64,
+}
+
+/// Default metrics snapshot interval: every 10 seconds.
+fn default_snapshot_interval_ms() -> u64 {
+    10_000
 }
 
 /// Telemetry section of the node config.
@@ -187,6 +196,8 @@ mod tests {
         assert!(cfg.jsonrpc_addr.is_none());
         // consensus.neuroflux_config_path should default to None
         assert!(cfg.consensus.neuroflux_config_path.is_none());
+        // metrics.snapshot_interval_ms should default to 10s
+        assert_eq!(cfg.metrics.snapshot_interval_ms, 10_000);
     }
 
     #[test]
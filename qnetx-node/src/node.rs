@@ -8,15 +8,18 @@ use crate::config::NodeConfig;
 use crate::error::NodeError;
 use crate::telemetry;
 use crate::metrics;
-use std::{path::Path, time::Duration};
+use std::{path::Path, sync::{Arc, Mutex}, time::Duration};
 use tokio::signal;
 use tokio::time;
 use log::{error, info};
 
 // QNet primitives
-use qublis_qnet::{Router, Relay, TeleportCore};
+use qublis_qnet::{Router, Relay, TeleportCore, QNetConfig};
 // QNetX overlay
-use qublis_qnetx::{QuantumMeshOverlay, ZeroPropagation, StateCondenser, AnomalyFilter};
+use qublis_qnetx::{
+    QuantumMeshOverlay, QuantumMesh, QNetXConfig, ZeroPropagation, StateCondenser, AnomalyFilter,
+    SharedCollector, spawn_metrics_flush,
+};
 // Runtime modules
 use qublis_runtime::{
     ConsensusNeuroFlux, EntanglementLoop, CausalReflector,
@@ -32,7 +35,7 @@ use qublis_runtime::{
 /// - Entanglement propagation loop
 /// - Causal reflection loop
 /// - JSON-RPC server (if enabled)
-/// - Metrics & telemetry
+/// - Metrics & telemetry, including a periodic snapshot flush
 pub async fn run(cfg: &NodeConfig, base_path: &Path) -> Result<(), NodeError> {
     // 1. Initialize telemetry & metrics endpoints
     telemetry::start(&cfg.telemetry)?;
@@ -43,14 +46,19 @@ pub async fn run(cfg: &NodeConfig, base_path: &Path) -> Result<(), NodeError> {
         .listen_addr(&cfg.listen_addr)
         .bootstrap_peers(cfg.bootstrap_peers.clone())
         .build()?;
-    let mut relay = Relay::new();
-    let mut teleport = TeleportCore::new();
+    // Shared with the metrics flush task below, which snapshots their collectors
+    let qnet_cfg = QNetConfig::default();
+    let relay = Relay::new(&qnet_cfg).map_err(|e| NodeError::Other(e.to_string()))?;
+    let relay = Arc::new(Mutex::new(relay));
+    let teleport = Arc::new(Mutex::new(TeleportCore::new(&qnet_cfg)));
+    let mesh = Arc::new(Mutex::new(QuantumMesh::new(&QNetXConfig::default())));
 
     // 3. Build QNetX overlay (QuantumMeshOverlay) atop QNet primitives
     let mut qnetx = QuantumMeshOverlay::builder()
         .router(router.clone())
-        .relay(relay.clone())
-        .teleport(teleport.clone())
+        .relay(Arc::clone(&relay))
+        .teleport(Arc::clone(&teleport))
+        .mesh(Arc::clone(&mesh))
         .build()?;
 
     info!("QNetX overlay initialized; listening on {}", &cfg.listen_addr);
@@ -74,14 +82,22 @@ pub async fn run(cfg: &NodeConfig, base_path: &Path) -> Result<(), NodeError> {
     // 8. Instantiate WASM executor (for future QBLang contract hooks)
     let wasm_exec = WasmExecutor::new(&rt_cfg.wasm);
 
-    // 9. Spawn P2P networking task
+    // 9. Spawn metrics flush task (configured interval) over the collectors
+    //    the relay, teleport core and mesh record into
+    let collectors: Vec<SharedCollector> = vec![relay, teleport, mesh];
+    spawn_metrics_flush(
+        collectors,
+        Duration::from_millis(cfg.metrics.snapshot_interval_ms.max(1)),
+    );
+
+    // 10. Spawn P2P networking task
     tokio::spawn(async move {
         if let Err(e) = qnetx.run().await {
             error!("QNetX networking error: {}", e);
         }
     });
 
-    // 10. Spawn consensus + NeuroFlux loop (every 1s)
+    // 11. Spawn consensus + NeuroFlux loop (every 1s)
     tokio::spawn(async move {
         let mut tick = time::interval(Duration::from_secs(1));
         loop {
@@ -90,7 +106,7 @@ pub async fn run(cfg: &NodeConfig, base_path: &Path) -> Result<(), NodeError> {
         }
     });
 
-    // 11. Spawn entanglement propagation loop (configured interval)
+    // 12. Spawn entanglement propagation loop (configured interval)
     tokio::spawn(async move {
         let mut tick = time::interval(Duration::from_millis(rt_cfg.entanglement.interval_ms));
         loop {
@@ -101,7 +117,7 @@ pub async fn run(cfg: &NodeConfig, base_path: &Path) -> Result<(), NodeError> {
         }
     });
 
-    // 12. Spawn causal reflection loop (every 2s)
+    // 13. Spawn causal reflection loop (every 2s)
     tokio::spawn(async move {
         let mut tick = time::interval(Duration::from_secs(2));
         loop {
@@ -114,7 +130,7 @@ pub async fn run(cfg: &NodeConfig, base_path: &Path) -> Result<(), NodeError> {
 
     info!("Node running. Press CTRL-C to shut down.");
 
-    // 13. Wait for shutdown signal (CTRL-C)
+    // 14. Wait for shutdown signal (CTRL-C)
     signal::ctrl_c().await.map_err(|e| NodeError::Other(format!("Signal error: {}", e)))?;
     info!("Shutdown signal received. Terminating.");
 
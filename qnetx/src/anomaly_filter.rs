@@ -11,7 +11,6 @@ use crate::config::QNetXConfig;
 use crate::metrics::QNetXMetrics;
 use crate::quantum_mesh::QuantumMesh;
 use crate::types::ChannelId;
-use qublis_qnum::QNum;
 
 /// Default entropy threshold: channels with entropy above this are anomalous.
 const DEFAULT_THRESHOLD: f64 = 1.0;
@@ -41,7 +40,7 @@ impl AnomalyFilter {
     /// Increments the `"anomalies_detected"` counter for each anomaly.
     pub fn detect(&mut self, mesh: &QuantumMesh) -> Vec<ChannelId> {
         let mut anomalies = Vec::new();
-        for (id, state) in &mesh.channels {
+        for (id, state, _) in mesh.channel_states() {
             let ent = state.entropy();
             if ent > self.threshold {
                 anomalies.push(id.clone());
@@ -71,7 +70,7 @@ mod tests {
         };
         let mut mesh = QuantumMesh::new(&cfg);
         for (id, q) in states {
-            mesh.channels.insert(id, q);
+            mesh.insert_channel(id, q, ("A".into(), "B".into()));
         }
         mesh
     }
@@ -95,12 +94,12 @@ mod tests {
     fn detects_anomalies_when_entropy_high() {
         // Build a QNum in equal superposition of all digits => high entropy
         let mut amps = [num_complex::Complex::new(1.0, 0.0); 10];
-        let total = 10.0;
+        let total: f64 = 10.0;
         for a in amps.iter_mut() {
             *a /= total.sqrt();
         }
-        let q_high = Qid::new(amps);
-        let qnum = QNum(vec![q_high.clone(), q_high]);
+        let q_high = Qid::from_f64(amps);
+        let qnum = QNum::new(vec![q_high.clone(), q_high]);
         let mesh = build_mesh(vec![(vec![9,9], qnum)]);
         let mut cfg = QNetXConfig::default();
         // Low threshold to catch it
@@ -1,11 +1,12 @@
 //! QNetX Configuration
 //!
 //! Defines the `QNetXConfig` struct for the entangled‐overlay mesh, including
-//! bootstrap dimensions, metrics, and anomaly‐filter thresholds.  
+//! bootstrap dimensions, metrics, anomaly‐filter thresholds, the default
+//! latency of new channels, and per‐dimension condensation weights.  
 //! Supports loading from a TOML file.
 
 use serde::{Deserialize, Serialize};
-use std::{fs, path::Path};
+use std::{collections::HashMap, fs, path::Path};
 use thiserror::Error;
 
 /// Default is no bootstrap dimensions.
@@ -28,6 +29,16 @@ fn default_enable_anomaly() -> bool {
     false
 }
 
+/// Default latency of a newly entangled channel, in milliseconds.
+fn default_channel_latency_ms() -> f64 {
+    10.0
+}
+
+/// Default is every dimension weighing the same in condensation.
+fn default_dimension_weights() -> HashMap<String, f64> {
+    HashMap::new()
+}
+
 /// QNetX mesh configuration parameters.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct QNetXConfig {
@@ -46,6 +57,16 @@ pub struct QNetXConfig {
     /// Whether to run the anomaly filter.
     #[serde(default = "default_enable_anomaly")]
     pub enable_anomaly: bool,
+
+    /// Latency (ms) assigned to each newly entangled channel; override per
+    /// channel with `QuantumMesh::set_channel_latency`.
+    #[serde(default = "default_channel_latency_ms")]
+    pub channel_latency_ms: f64,
+
+    /// Weight of each dimension (by name) in `StateCondenser::condense`;
+    /// unlisted dimensions weigh 1.0.
+    #[serde(default = "default_dimension_weights")]
+    pub dimension_weights: HashMap<String, f64>,
 }
 
 impl Default for QNetXConfig {
@@ -55,6 +76,8 @@ impl Default for QNetXConfig {
             enable_metrics: default_enable_metrics(),
             anomaly_threshold: default_anomaly_threshold(),
             enable_anomaly: default_enable_anomaly(),
+            channel_latency_ms: default_channel_latency_ms(),
+            dimension_weights: default_dimension_weights(),
         }
     }
 }
@@ -93,6 +116,8 @@ mod tests {
         assert!(!cfg.enable_metrics);
         assert!(cfg.anomaly_threshold.is_none());
         assert!(!cfg.enable_anomaly);
+        assert_eq!(cfg.channel_latency_ms, 10.0);
+        assert!(cfg.dimension_weights.is_empty());
     }
 
     #[test]
@@ -102,6 +127,10 @@ mod tests {
             enable_metrics = true
             anomaly_threshold = 0.42
             enable_anomaly = true
+            channel_latency_ms = 2.5
+
+            [dimension_weights]
+            dimA = 3.0
         "#;
         let mut file = NamedTempFile::new().unwrap();
         fs::write(file.path(), toml).unwrap();
@@ -111,6 +140,8 @@ mod tests {
         assert!(cfg.enable_metrics);
         assert_eq!(cfg.anomaly_threshold, Some(0.42));
         assert!(cfg.enable_anomaly);
+        assert_eq!(cfg.channel_latency_ms, 2.5);
+        assert_eq!(cfg.dimension_weights.get("dimA"), Some(&3.0));
     }
 
     #[test]
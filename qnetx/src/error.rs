@@ -13,10 +13,19 @@ pub enum QNetXError {
     #[error("Handshake error: {0}")]
     HandshakeError(String),
 
+    /// Failure during channel gossip with a peer.
+    #[error("Gossip error: {0}")]
+    GossipError(String),
+
     /// Requested channel ID was not found.
     #[error("Channel not found: {0:?}")]
     ChannelNotFound(ChannelId),
 
+    /// A new channel's ID is already taken by a channel between other
+    /// dimensions.
+    #[error("Channel ID collision: {0:?} already names another channel")]
+    ChannelCollision(ChannelId),
+
     /// Error during state condensation (e.g., no channels to condense).
     #[error("Condensation error: {0}")]
     CondensationError(String),
@@ -0,0 +1,120 @@
+//! Periodic Metrics Flush
+//!
+//! Snapshots the counters and gauges of QNet/QNetX metrics collectors into
+//! their history on a fixed schedule, so a long-running node keeps a time
+//! series rather than only its latest values.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use log::error;
+use tokio::task::JoinHandle;
+use tokio::time;
+
+use crate::metrics::QNetXMetrics;
+use crate::quantum_mesh::QuantumMesh;
+
+/// A subsystem metrics collector whose counters and gauges can be
+/// snapshotted into its history on a schedule.
+pub trait SnapshotCollector: Send {
+    /// Append a snapshot of the current counters and gauges.
+    fn record_snapshot(&mut self);
+}
+
+impl SnapshotCollector for QNetXMetrics {
+    fn record_snapshot(&mut self) {
+        QNetXMetrics::record_snapshot(self);
+    }
+}
+
+impl SnapshotCollector for QuantumMesh {
+    fn record_snapshot(&mut self) {
+        self.metrics_mut().record_snapshot();
+    }
+}
+
+#[cfg(feature = "qnet")]
+impl SnapshotCollector for qublis_qnet::QNetMetrics {
+    fn record_snapshot(&mut self) {
+        qublis_qnet::QNetMetrics::record_snapshot(self);
+    }
+}
+
+#[cfg(feature = "qnet")]
+impl SnapshotCollector for qublis_qnet::Relay {
+    fn record_snapshot(&mut self) {
+        self.metrics_mut().record_snapshot();
+    }
+}
+
+#[cfg(feature = "qnet")]
+impl SnapshotCollector for qublis_qnet::TeleportCore {
+    fn record_snapshot(&mut self) {
+        self.metrics_mut().record_snapshot();
+    }
+}
+
+/// A collector shared between the subsystem recording into it and the
+/// metrics flush task.
+pub type SharedCollector = Arc<Mutex<dyn SnapshotCollector>>;
+
+/// Spawn a background task that calls `record_snapshot` on every collector
+/// once per `interval`, starting one interval from now.
+///
+/// Must be called from within a Tokio runtime.
+pub fn spawn_metrics_flush(collectors: Vec<SharedCollector>, interval: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut tick = time::interval_at(time::Instant::now() + interval, interval);
+        loop {
+            tick.tick().await;
+            for collector in &collectors {
+                match collector.lock() {
+                    Ok(mut c) => c.record_snapshot(),
+                    Err(e) => error!("Metrics collector lock poisoned: {}", e),
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::QNetXConfig;
+
+    #[tokio::test]
+    async fn metrics_flush_records_snapshots() {
+        let metrics = Arc::new(Mutex::new(QNetXMetrics::new()));
+        let shared: SharedCollector = metrics.clone();
+        let handle = spawn_metrics_flush(vec![shared], Duration::from_millis(20));
+
+        time::sleep(Duration::from_millis(70)).await;
+        handle.abort();
+
+        let json = metrics.lock().unwrap().export_json().unwrap();
+        assert!(json.contains("\"timestamp\""), "no snapshot recorded: {}", json);
+    }
+
+    #[cfg(feature = "qnet")]
+    #[tokio::test]
+    async fn metrics_flush_snapshots_subsystem_collectors() {
+        let mesh = Arc::new(Mutex::new(QuantumMesh::new(&QNetXConfig::default())));
+        mesh.lock().unwrap().entangle_channel(&"a".into(), &"b".into()).unwrap();
+        let qnet_cfg = qublis_qnet::QNetConfig::default();
+        let relay = Arc::new(Mutex::new(qublis_qnet::Relay::new(&qnet_cfg).unwrap()));
+        let teleport = Arc::new(Mutex::new(qublis_qnet::TeleportCore::new(&qnet_cfg)));
+        let collectors: Vec<SharedCollector> = vec![mesh.clone(), relay.clone(), teleport.clone()];
+        let handle = spawn_metrics_flush(collectors, Duration::from_millis(20));
+
+        time::sleep(Duration::from_millis(70)).await;
+        handle.abort();
+
+        for json in [
+            mesh.lock().unwrap().metrics().export_json().unwrap(),
+            relay.lock().unwrap().metrics().export_json().unwrap(),
+            teleport.lock().unwrap().metrics().export_json().unwrap(),
+        ] {
+            assert!(json.contains("\"timestamp\""), "no snapshot recorded: {}", json);
+        }
+    }
+}
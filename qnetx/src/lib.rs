@@ -2,12 +2,14 @@
 //!
 //! `qublis-qnetx` provides a multi-dimensional, entangled overlay network
 //! on top of QNet.  Core components include:
-//! - `QuantumMesh`: build and manage entangled channels between dimensions.
+//! - `QuantumMesh`: build and manage entangled channels between dimensions,
+//!   and discover peers' channels by gossip.
 //! - `ZeroPropagator`: propagate quantum‐zero modes through the mesh.
 //! - `StateCondenser`: condense global mesh state into lower‐dimensional summaries.
 //! - `AnomalyFilter`: detect and filter entanglement anomalies.
 //! - `QNetXConfig`: configurable parameters for mesh behavior.
 //! - `QNetXMetrics`: telemetry for mesh operations.
+//! - `spawn_metrics_flush`: periodic snapshots of QNet/QNetX metrics collectors.
 //! - `QNetXError`: unified error handling.
 //!
 //! All routing identifiers and channel IDs are represented as `QNum` superpositions
@@ -24,16 +26,18 @@ pub mod config;
 pub mod types;
 pub mod error;
 pub mod metrics;
+pub mod flush;
 pub mod prelude;
 
 pub use config::QNetXConfig;
-pub use quantum_mesh::QuantumMesh;
+pub use quantum_mesh::{QuantumMesh, answer_gossip, exchange_adverts, spawn_gossip};
 pub use zero_prop::ZeroPropagator;
 pub use state_condenser::StateCondenser;
 pub use anomaly_filter::AnomalyFilter;
 pub use error::QNetXError;
 pub use metrics::QNetXMetrics;
-pub use types::{Dimension, ChannelId};
+pub use flush::{spawn_metrics_flush, SharedCollector, SnapshotCollector};
+pub use types::{Dimension, ChannelId, ChannelAdvert};
 
 /// Convenient import of all primary QNetX types.
 pub use prelude::*;
@@ -92,6 +92,11 @@ impl QNetXMetrics {
         self.inc_counter("channels_created", 1);
     }
 
+    /// Record `count` channels newly learned from a peer's gossip.
+    pub fn record_channels_discovered(&mut self, count: u64) {
+        self.inc_counter("channels_discovered", count);
+    }
+
     /// Record an entanglement operation on a channel.
     pub fn record_entanglement(&mut self) {
         self.inc_counter("entanglements", 1);
@@ -9,7 +9,7 @@ pub use crate::state_condenser::StateCondenser;
 pub use crate::anomaly_filter::AnomalyFilter;
 pub use crate::metrics::QNetXMetrics;
 pub use crate::error::QNetXError;
-pub use crate::types::{Dimension, ChannelId};
+pub use crate::types::{Dimension, ChannelId, ChannelAdvert};
 
 #[cfg(test)]
 mod tests {
@@ -3,25 +3,55 @@
 //! Manages a set of entangled “channels” (QNum states) between logical
 //! dimensions.  Supports peer‐to‐peer handshake to establish new channels,
 //! stores them by `ChannelId`, and provides access for routing and teleport.
+//! Each channel carries a latency, so routing can prefer fast channels among
+//! equally entropic ones.
+//!
+//! Channels established by peers are discovered by gossip: each round a node
+//! sends its known channel endpoints (never states) to a peer as JSON, the
+//! peer merges them and replies with its own list, and the node merges that.
+//! Each side reads at most `MAX_GOSSIP_BYTES` from its peer and gives up after
+//! `GOSSIP_READ_TIMEOUT`, so a misbehaving peer cannot exhaust memory or stall
+//! a round.
 
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
+use log::warn;
 use tokio::net::TcpStream;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::task::JoinHandle;
+use tokio::time;
 use qublis_qnum::{QNum, entangle};
 use crate::config::QNetXConfig;
 use crate::metrics::QNetXMetrics;
 use crate::error::QNetXError;
-use crate::types::{Dimension, ChannelId};
+use crate::types::{Dimension, ChannelId, ChannelAdvert};
+
+/// Largest gossip message (bytes) accepted from a peer.
+pub const MAX_GOSSIP_BYTES: u64 = 1 << 20;
+
+/// Longest a gossip round waits for a peer's message.
+pub const GOSSIP_READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A stored channel: its entangled state, the dimensions it connects, and
+/// its latency.
+#[derive(Clone, Debug)]
+struct Channel {
+    state: QNum,
+    endpoints: (Dimension, Dimension),
+    latency_ms: f64,
+}
 
 /// QuantumMesh holds entangled channels between dimensions.
 #[derive(Clone, Debug)]
 pub struct QuantumMesh {
     config: Arc<QNetXConfig>,
     metrics: QNetXMetrics,
-    /// Map from channel identifier to its QNum state.
-    channels: HashMap<ChannelId, QNum>,
+    /// Map from channel identifier to its QNum state and endpoints.
+    channels: HashMap<ChannelId, Channel>,
+    /// Endpoints of peers' channels learned by gossip.
+    discovered: HashMap<ChannelId, (Dimension, Dimension)>,
 }
 
 impl QuantumMesh {
@@ -31,6 +61,7 @@ impl QuantumMesh {
             config: Arc::new(config.clone()),
             metrics: QNetXMetrics::new(),
             channels: HashMap::new(),
+            discovered: HashMap::new(),
         }
     }
 
@@ -39,8 +70,18 @@ impl QuantumMesh {
         &self.config
     }
 
+    /// Access the internal metrics collector.
+    pub fn metrics(&self) -> &QNetXMetrics {
+        &self.metrics
+    }
+
+    /// Mutable access to the internal metrics collector, e.g. to record a snapshot.
+    pub fn metrics_mut(&mut self) -> &mut QNetXMetrics {
+        &mut self.metrics
+    }
+
     /// Connect to a peer at `addr` and perform overlay handshake.
-    pub async fn connect(&self, addr: String) -> Result<(), QNetXError> {
+    pub async fn connect(&mut self, addr: String) -> Result<(), QNetXError> {
         let mut stream = TcpStream::connect(&addr).await?;
         // Send our local dimension IDs as "dimA,dimB\n"
         let dims = &self.config.bootstrap_nodes; // reuse bootstrap_nodes as dimension names
@@ -49,19 +90,21 @@ impl QuantumMesh {
         }
         let payload = format!("{},{}\n", dims[0], dims[1]);
         stream.write_all(payload.as_bytes()).await?;
+        // Half-close so the peer sees the end of the handshake
+        stream.shutdown().await?;
         // Wait for peer to respond with ChannelId JSON
         let mut buf = Vec::new();
         stream.read_to_end(&mut buf).await?;
-        let channel_id: ChannelId = serde_json::from_slice(&buf)?;
+        let _channel_id: ChannelId = serde_json::from_slice(&buf)?;
         self.metrics.record_channel_created();
         Ok(())
     }
 
     /// Handle an incoming overlay connection: read two dimension names,
     /// entangle a new channel, and write back the `ChannelId` as JSON.
-    pub async fn handle_connection(&self, mut stream: TcpStream) -> Result<(), QNetXError> {
+    pub async fn handle_connection(&mut self, mut stream: TcpStream) -> Result<(), QNetXError> {
         let mut buf = Vec::new();
-        // Read until EOF or newline
+        // Read until the initiator half-closes
         let _ = stream.read_to_end(&mut buf).await?;
         let msg = String::from_utf8_lossy(&buf);
         let parts: Vec<&str> = msg.trim_end().split(',').collect();
@@ -75,7 +118,7 @@ impl QuantumMesh {
         let dim_b = Dimension(parts[1].to_string());
 
         // Entangle a new channel between dim_a and dim_b
-        let channel_id = self.entangle_channel(&dim_a, &dim_b);
+        let channel_id = self.entangle_channel(&dim_a, &dim_b)?;
 
         // Send back the ChannelId as JSON
         let resp = serde_json::to_vec(&channel_id)?;
@@ -86,85 +129,418 @@ impl QuantumMesh {
     /// Create and store a new entangled channel between dimensions `a` and `b`.
     ///
     /// Returns a `ChannelId` (vector of digits) that can be used to retrieve the QNum.
-    pub fn entangle_channel(&self, a: &Dimension, b: &Dimension) -> ChannelId {
-        // Derive initial digit vector from dimension names (take ASCII mods)
-        let mut seed: Vec<u8> = a.0.bytes().chain(b.0.bytes())
-            .map(|b| (b % 10) as u8)
-            .take(4)
-            .collect();
-        while seed.len() < 4 {
-            seed.push(0);
-        }
-
-        // Build a QNum from that seed
-        let mut channel_q = QNum::from_digits(&seed);
+    /// Re-entangling the same `(a, b)` pair replaces its channel; fails with
+    /// [`QNetXError::ChannelCollision`] if the ID already names a local or
+    /// discovered channel between other dimensions.
+    pub fn entangle_channel(&mut self, a: &Dimension, b: &Dimension) -> Result<ChannelId, QNetXError> {
+        // Build one 2-digit register per dimension and join them
+        let mut channel_q = dimension_register(a).tensor(&dimension_register(b));
 
         // Entangle each digit with itself to spread amplitude
-        entangle(&mut channel_q, &mut channel_q.clone());
+        let mut partner = channel_q.clone();
+        entangle(&mut channel_q, &mut partner);
 
         // Collapse to obtain the ChannelId
         let channel_id = channel_q.clone().measure();
+        let endpoints = (a.clone(), b.clone());
+        let taken = self
+            .channel_endpoints(&channel_id)
+            .is_some_and(|(x, y)| (x, y) != (&endpoints.0, &endpoints.1));
+        if taken {
+            return Err(QNetXError::ChannelCollision(channel_id));
+        }
 
-        // Store the entangled state
-        self.channels
-            .insert(channel_id.clone(), channel_q);
+        // Store the entangled state with its endpoints
+        self.channels.insert(
+            channel_id.clone(),
+            Channel {
+                state: channel_q,
+                endpoints,
+                latency_ms: self.config.channel_latency_ms,
+            },
+        );
 
         // Record metric
         self.metrics.record_entanglement();
 
-        channel_id
+        Ok(channel_id)
     }
 
     /// Retrieve the `QNum` state for the given `ChannelId`, if it exists.
     pub fn get_channel(&self, id: &ChannelId) -> Option<&QNum> {
-        self.channels.get(id)
+        self.channels.get(id).map(|c| &c.state)
+    }
+
+    /// Latency (ms) of a local channel, if it exists.
+    pub fn channel_latency(&self, id: &ChannelId) -> Option<f64> {
+        self.channels.get(id).map(|c| c.latency_ms)
+    }
+
+    /// Set the latency (ms) of a local channel, e.g. from a measured round trip.
+    pub fn set_channel_latency(&mut self, id: &ChannelId, latency_ms: f64) -> Result<(), QNetXError> {
+        let channel = self
+            .channels
+            .get_mut(id)
+            .ok_or_else(|| QNetXError::ChannelNotFound(id.clone()))?;
+        channel.latency_ms = latency_ms;
+        Ok(())
+    }
+
+    /// Pick the local channel with an endpoint at `from` to route over: the
+    /// one whose state has the lowest entropy, then the lowest latency, then
+    /// the smallest `ChannelId`. Returns `None` if no channel touches `from`.
+    pub fn select_channel(&self, from: &Dimension) -> Option<ChannelId> {
+        self.channels
+            .iter()
+            .filter(|(_, c)| c.endpoints.0 == *from || c.endpoints.1 == *from)
+            .map(|(id, c)| (c.state.entropy(), c.latency_ms, id))
+            .min_by(|x, y| {
+                x.0.total_cmp(&y.0)
+                    .then(x.1.total_cmp(&y.1))
+                    .then_with(|| x.2.cmp(y.2))
+            })
+            .map(|(_, _, id)| id.clone())
+    }
+
+    /// The `(a, b)` dimensions the given channel was created between, if it
+    /// exists locally or was learned by gossip.
+    pub fn channel_endpoints(&self, id: &ChannelId) -> Option<(&Dimension, &Dimension)> {
+        self.channels
+            .get(id)
+            .map(|c| &c.endpoints)
+            .or_else(|| self.discovered.get(id))
+            .map(|(a, b)| (a, b))
+    }
+
+    /// Every local channel's state with the dimensions it connects.
+    pub(crate) fn channel_states(
+        &self,
+    ) -> impl Iterator<Item = (&ChannelId, &QNum, &(Dimension, Dimension))> {
+        self.channels.iter().map(|(id, c)| (id, &c.state, &c.endpoints))
+    }
+
+    /// Store `state` as the channel `id` between `endpoints`, for tests that
+    /// need exact channel states.
+    #[cfg(test)]
+    pub(crate) fn insert_channel(&mut self, id: ChannelId, state: QNum, endpoints: (Dimension, Dimension)) {
+        let latency_ms = self.config.channel_latency_ms;
+        self.channels.insert(id, Channel { state, endpoints, latency_ms });
+    }
+
+    /// Adverts for every channel this mesh knows, local or discovered,
+    /// sorted by `ChannelId`.
+    pub fn channel_adverts(&self) -> Vec<ChannelAdvert> {
+        let local = self.channels.iter().map(|(id, c)| (id, &c.endpoints));
+        let mut adverts: Vec<ChannelAdvert> = local
+            .chain(self.discovered.iter())
+            .map(|(id, endpoints)| ChannelAdvert {
+                id: id.clone(),
+                endpoints: endpoints.clone(),
+            })
+            .collect();
+        adverts.sort_by(|x, y| x.id.cmp(&y.id));
+        adverts
+    }
+
+    /// Record the endpoints of every advertised channel not already known,
+    /// returning how many were new. Known channels are left untouched.
+    pub fn merge_adverts(&mut self, adverts: Vec<ChannelAdvert>) -> usize {
+        let mut added = 0;
+        for advert in adverts {
+            if self.channels.contains_key(&advert.id) || self.discovered.contains_key(&advert.id) {
+                continue;
+            }
+            self.discovered.insert(advert.id, advert.endpoints);
+            added += 1;
+        }
+        self.metrics.record_channels_discovered(added as u64);
+        added
+    }
+}
+
+/// One gossip round as the initiator: send `adverts` to the peer at `addr`
+/// and return the peer's adverts.
+pub async fn exchange_adverts(addr: &str, adverts: &[ChannelAdvert]) -> Result<Vec<ChannelAdvert>, QNetXError> {
+    let mut stream = TcpStream::connect(addr).await?;
+    stream.write_all(&serde_json::to_vec(adverts)?).await?;
+    // Half-close so the peer sees the end of our list
+    stream.shutdown().await?;
+    let buf = read_capped(&mut stream, MAX_GOSSIP_BYTES, GOSSIP_READ_TIMEOUT).await?;
+    Ok(serde_json::from_slice(&buf)?)
+}
+
+/// Answer an incoming gossip round: read the peer's adverts, merge them into
+/// `mesh`, and reply with everything `mesh` now knows. Returns how many
+/// channels were new.
+pub async fn answer_gossip(mesh: &Mutex<QuantumMesh>, mut stream: TcpStream) -> Result<usize, QNetXError> {
+    let buf = read_capped(&mut stream, MAX_GOSSIP_BYTES, GOSSIP_READ_TIMEOUT).await?;
+    let theirs: Vec<ChannelAdvert> = serde_json::from_slice(&buf)?;
+    let (added, ours) = {
+        let mut mesh = mesh.lock().map_err(|e| QNetXError::GossipError(e.to_string()))?;
+        let added = mesh.merge_adverts(theirs);
+        (added, mesh.channel_adverts())
+    };
+    stream.write_all(&serde_json::to_vec(&ours)?).await?;
+    Ok(added)
+}
+
+/// Read `reader` to its end, failing if that takes longer than `timeout` or
+/// yields more than `limit` bytes.
+async fn read_capped<R: AsyncRead + Unpin>(
+    reader: R,
+    limit: u64,
+    timeout: Duration,
+) -> Result<Vec<u8>, QNetXError> {
+    let mut buf = Vec::new();
+    // one byte past the limit tells an oversized message from one that fits
+    let mut capped = reader.take(limit + 1);
+    time::timeout(timeout, capped.read_to_end(&mut buf))
+        .await
+        .map_err(|_| QNetXError::GossipError(format!("no message within {:?}", timeout)))??;
+    if buf.len() as u64 > limit {
+        return Err(QNetXError::GossipError(format!("message exceeds {} bytes", limit)));
     }
+    Ok(buf)
+}
+
+/// Spawn a background task that gossips with every peer in `peers` once
+/// per `interval`, starting one interval from now, merging what each peer
+/// knows into `mesh`. Unreachable peers are logged and retried next round.
+pub fn spawn_gossip(mesh: Arc<Mutex<QuantumMesh>>, peers: Vec<String>, interval: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut tick = time::interval_at(time::Instant::now() + interval, interval);
+        loop {
+            tick.tick().await;
+            for peer in &peers {
+                let ours = match mesh.lock() {
+                    Ok(m) => m.channel_adverts(),
+                    Err(e) => {
+                        warn!("QuantumMesh lock poisoned: {}", e);
+                        return;
+                    }
+                };
+                match exchange_adverts(peer, &ours).await {
+                    Ok(theirs) => {
+                        if let Ok(mut m) = mesh.lock() {
+                            m.merge_adverts(theirs);
+                        }
+                    }
+                    Err(e) => warn!("Gossip with {} failed: {}", peer, e),
+                }
+            }
+        }
+    })
+}
+
+/// Derive a 2-digit register from a dimension name: the last two decimal
+/// digits of a 64-bit FNV-1a hash over the whole name, so names sharing a
+/// prefix still spread across registers.
+fn dimension_register(d: &Dimension) -> QNum {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    let h = d.0.bytes().fold(OFFSET, |h, b| (h ^ b as u64).wrapping_mul(PRIME));
+    QNum::from_digits(&[(h / 10 % 10) as u8, (h % 10) as u8])
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use tokio::runtime::Runtime;
     use crate::config::QNetXConfig;
-    use crate::types::{Dimension, ChannelId};
-    use std::net::SocketAddr;
-    use serde_json;
+    use crate::types::Dimension;
 
     #[test]
     fn test_entangle_channel_generates_id() {
         let cfg = QNetXConfig::default();
-        let mesh = QuantumMesh::new(&cfg);
+        let mut mesh = QuantumMesh::new(&cfg);
         let dim_a = Dimension("A".into());
         let dim_b = Dimension("B".into());
-        let id = mesh.entangle_channel(&dim_a, &dim_b);
+        let id = mesh.entangle_channel(&dim_a, &dim_b).unwrap();
         assert_eq!(id.len(), 4);
         // Channel state stored
         assert!(mesh.get_channel(&id).is_some());
     }
 
+    #[test]
+    fn test_channel_endpoints_reported() {
+        let cfg = QNetXConfig::default();
+        let mut mesh = QuantumMesh::new(&cfg);
+        let dim_a = Dimension("A".into());
+        let dim_b = Dimension("B".into());
+        let id = mesh.entangle_channel(&dim_a, &dim_b).unwrap();
+        assert_eq!(mesh.channel_endpoints(&id), Some((&dim_a, &dim_b)));
+        assert_eq!(mesh.channel_endpoints(&vec![9, 9, 9, 9, 9]), None);
+    }
+
+    #[test]
+    fn test_entangle_channel_rejects_id_collisions() {
+        let cfg = QNetXConfig::default();
+        // names sharing a prefix no longer share a register
+        assert_ne!(dimension_register(&"node-1".into()), dimension_register(&"node-2".into()));
+
+        // a distinct name whose hash agrees mod 100 still collides
+        let first = Dimension::from("peer-0");
+        let twin = (1..)
+            .map(|i| Dimension(format!("peer-{}", i)))
+            .find(|d| dimension_register(d) == dimension_register(&first))
+            .unwrap();
+        let hub = Dimension::from("hub");
+        let mut mesh = QuantumMesh::new(&cfg);
+        let id = mesh.entangle_channel(&first, &hub).unwrap();
+        assert!(matches!(
+            mesh.entangle_channel(&twin, &hub),
+            Err(QNetXError::ChannelCollision(ref c)) if *c == id
+        ));
+        assert_eq!(mesh.channel_endpoints(&id), Some((&first, &hub)));
+        // re-entangling the same pair is not a collision
+        assert_eq!(mesh.entangle_channel(&first, &hub).unwrap(), id);
+
+        // nor may a local channel shadow one learned by gossip
+        let mut other = QuantumMesh::new(&cfg);
+        other.merge_adverts(vec![ChannelAdvert { id, endpoints: (twin, hub.clone()) }]);
+        assert!(matches!(
+            other.entangle_channel(&first, &hub),
+            Err(QNetXError::ChannelCollision(_))
+        ));
+    }
+
+    #[test]
+    fn test_select_channel_prefers_lower_latency() {
+        let cfg = QNetXConfig::default();
+        let mut mesh = QuantumMesh::new(&cfg);
+        let (dim_a, dim_b, dim_c) = (Dimension("A".into()), Dimension("B".into()), Dimension("C".into()));
+        let ab = mesh.entangle_channel(&dim_a, &dim_b).unwrap();
+        let ac = mesh.entangle_channel(&dim_a, &dim_c).unwrap();
+        assert_eq!(mesh.channel_latency(&ab), Some(cfg.channel_latency_ms));
+        assert_eq!(
+            mesh.get_channel(&ab).unwrap().entropy(),
+            mesh.get_channel(&ac).unwrap().entropy()
+        );
+
+        mesh.set_channel_latency(&ab, 40.0).unwrap();
+        mesh.set_channel_latency(&ac, 5.0).unwrap();
+        assert_eq!(mesh.select_channel(&dim_a), Some(ac.clone()));
+        mesh.set_channel_latency(&ac, 80.0).unwrap();
+        assert_eq!(mesh.select_channel(&dim_a), Some(ab.clone()));
+
+        // only channels touching the dimension are candidates
+        assert_eq!(mesh.select_channel(&dim_c), Some(ac));
+        assert_eq!(mesh.select_channel(&Dimension("Z".into())), None);
+        assert!(matches!(
+            mesh.set_channel_latency(&vec![9, 9, 9, 9, 9], 1.0),
+            Err(QNetXError::ChannelNotFound(_))
+        ));
+    }
+
     #[test]
     fn test_handle_connection_and_connect() {
         // Run a mini server and client on localhost
-        let rt = Runtime::new().unwrap();
+        let rt = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
         let cfg = QNetXConfig {
             bootstrap_nodes: vec!["A".into(), "B".into()],
             ..Default::default()
         };
-        let mesh = QuantumMesh::new(&cfg);
-        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
-        let addr = listener.local_addr().unwrap();
-
-        // Spawn server task
-        rt.spawn(async move {
-            let (sock, _) = listener.accept().unwrap();
-            let stream = TcpStream::from_std(sock).unwrap();
-            mesh.handle_connection(stream).await.unwrap();
+        let mut mesh = QuantumMesh::new(&cfg);
+        let mut client_mesh = QuantumMesh::new(&cfg);
+
+        rt.block_on(async {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let server = async {
+                let (stream, _) = listener.accept().await.unwrap();
+                mesh.handle_connection(stream).await.unwrap();
+            };
+            // Client connects and sends handshake
+            let client = client_mesh.connect(addr.to_string());
+            let ((), connected) = tokio::join!(server, client);
+            connected.unwrap();
         });
+        // the server entangled a channel between the client's dimensions
+        let adverts = mesh.channel_adverts();
+        assert_eq!(adverts.len(), 1);
+        assert_eq!(adverts[0].endpoints, (Dimension("A".into()), Dimension("B".into())));
+    }
 
-        // Client connects and sends handshake
+    #[test]
+    fn test_merge_adverts_skips_known_channels() {
+        let cfg = QNetXConfig::default();
+        let mut mesh = QuantumMesh::new(&cfg);
+        let own = mesh.entangle_channel(&Dimension("A".into()), &Dimension("B".into())).unwrap();
+        let foreign = ChannelAdvert {
+            id: vec![9, 9, 9, 9, 9],
+            endpoints: (Dimension("C".into()), Dimension("D".into())),
+        };
+        let adverts = vec![
+            ChannelAdvert {
+                id: own.clone(),
+                endpoints: (Dimension("X".into()), Dimension("Y".into())),
+            },
+            foreign.clone(),
+        ];
+        assert_eq!(mesh.merge_adverts(adverts.clone()), 1);
+        assert_eq!(mesh.merge_adverts(adverts), 0);
+        // a local channel's endpoints are never overwritten by gossip
+        assert_eq!(mesh.channel_endpoints(&own).unwrap().0, &Dimension("A".into()));
+        assert!(mesh.get_channel(&foreign.id).is_none());
+        assert_eq!(mesh.channel_adverts().len(), 2);
+        assert!(mesh.channel_adverts().contains(&foreign));
+    }
+
+    #[test]
+    fn test_gossip_round_discovers_peer_channel() {
+        let rt = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        let cfg = QNetXConfig::default();
+        let (dim_a, dim_b) = (Dimension("alpha".into()), Dimension("beta".into()));
+        let (dim_c, dim_d) = (Dimension("gamma".into()), Dimension("delta".into()));
+
+        let mut server_mesh = QuantumMesh::new(&cfg);
+        let peer_channel = server_mesh.entangle_channel(&dim_a, &dim_b).unwrap();
+        let server_mesh = Arc::new(Mutex::new(server_mesh));
+        let mut client = QuantumMesh::new(&cfg);
+        let own_channel = client.entangle_channel(&dim_c, &dim_d).unwrap();
+        assert!(client.channel_endpoints(&peer_channel).is_none());
+
+        rt.block_on(async {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap().to_string();
+            let server = {
+                let mesh = server_mesh.clone();
+                tokio::spawn(async move {
+                    let (sock, _) = listener.accept().await.unwrap();
+                    answer_gossip(&mesh, sock).await.unwrap()
+                })
+            };
+
+            let theirs = exchange_adverts(&addr, &client.channel_adverts()).await.unwrap();
+            assert_eq!(client.merge_adverts(theirs), 1);
+            assert_eq!(server.await.unwrap(), 1);
+        });
+
+        // the client learned the peer's channel endpoints, but not its state
+        assert_eq!(client.channel_endpoints(&peer_channel), Some((&dim_a, &dim_b)));
+        assert!(client.get_channel(&peer_channel).is_none());
+        // and the exchange went both ways
+        let server_mesh = server_mesh.lock().unwrap();
+        assert_eq!(server_mesh.channel_endpoints(&own_channel), Some((&dim_c, &dim_d)));
+    }
+
+    #[test]
+    fn test_gossip_reads_are_capped() {
+        let rt = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
         rt.block_on(async {
-            let client_mesh = QuantumMesh::new(&cfg);
-            client_mesh.connect(addr.to_string()).await.unwrap();
+            let timeout = Duration::from_millis(20);
+            let fits = read_capped(&b"[1,2]"[..], 5, timeout).await.unwrap();
+            assert_eq!(fits, b"[1,2]");
+            assert!(matches!(
+                read_capped(&b"[1,2,3]"[..], 5, timeout).await,
+                Err(QNetXError::GossipError(_))
+            ));
+
+            // a peer that never finishes its message is dropped after the timeout
+            let (_writer, silent) = tokio::io::duplex(64);
+            assert!(matches!(
+                read_capped(silent, 5, timeout).await,
+                Err(QNetXError::GossipError(_))
+            ));
         });
     }
 }
@@ -2,22 +2,25 @@
 //! for QNetX (Universe 2-74136, QBL v2.0).
 //!
 //! The `StateCondenser` provides utilities to reduce a collection of entangled
-//! channel QNums into a single summary QNum (`condense_all`) or into grouped
-//! summaries by prefix (`condense_by_prefix`).  Useful for generating low‐dimensional
-//! views of global mesh uncertainty.
+//! channel QNums into a single summary QNum (`condense_all`), into grouped
+//! summaries by prefix (`condense_by_prefix`), or into a dimension‐weighted
+//! mixture (`condense`) in which channels touching high‐weight dimensions
+//! dominate.  Useful for generating low‐dimensional views of global mesh
+//! uncertainty.
 
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
 
 use std::collections::HashMap;
 
-use qublis_qnum::{QNum, qadd};
+use num_complex::Complex;
+use qublis_qnum::{QNum, Qid, qadd};
 
 use crate::{
     config::QNetXConfig,
     error::QNetXError,
     metrics::QNetXMetrics,
-    types::ChannelId,
+    types::Dimension,
     quantum_mesh::QuantumMesh,
 };
 
@@ -42,7 +45,7 @@ impl StateCondenser {
     ///
     /// Returns an error if the mesh has no channels.
     pub fn condense_all(&mut self, mesh: &QuantumMesh) -> Result<QNum, QNetXError> {
-        let mut iter = mesh.channels.values();
+        let mut iter = mesh.channel_states().map(|(_, state, _)| state);
         let first = iter
             .next()
             .ok_or_else(|| QNetXError::CondensationError("no channels to condense".into()))?;
@@ -63,7 +66,7 @@ impl StateCondenser {
     pub fn condense_by_prefix(&mut self, mesh: &QuantumMesh) -> HashMap<u8, QNum> {
         let mut groups: HashMap<u8, QNum> = HashMap::new();
 
-        for (id, qnum) in &mesh.channels {
+        for (id, qnum, _) in mesh.channel_states() {
             if let Some(&prefix) = id.first() {
                 groups
                     .entry(prefix)
@@ -75,30 +78,98 @@ impl StateCondenser {
         self.metrics.inc_counter("condense_by_prefix", 1);
         groups
     }
+
+    /// Condense every channel into one summary QNum whose digits measure with
+    /// the weighted mean of the channels' per‐digit probabilities.
+    ///
+    /// A channel weighs the mean of its two endpoints' entries in
+    /// `config.dimension_weights` (1.0 for unlisted dimensions, negative
+    /// weights counting as 0), so raising a dimension's weight pulls the
+    /// summary toward the states of the channels that touch it.
+    ///
+    /// Returns an error if no channel has positive weight or the channel
+    /// states differ in digit count or radix.
+    pub fn condense(&mut self, mesh: &QuantumMesh) -> Result<QNum, QNetXError> {
+        // per digit, per basis value: weighted sum of probabilities
+        let mut mixture: Vec<Vec<f64>> = Vec::new();
+        let mut total = 0.0;
+        for (_, state, (a, b)) in mesh.channel_states() {
+            let weight = (self.dimension_weight(a) + self.dimension_weight(b)) / 2.0;
+            if weight <= 0.0 {
+                continue;
+            }
+            if mixture.is_empty() {
+                mixture = state.0.iter().map(|qid| vec![0.0; qid.radix()]).collect();
+            }
+            let same_shape = mixture.len() == state.len()
+                && mixture.iter().zip(&state.0).all(|(probs, qid)| probs.len() == qid.radix());
+            if !same_shape {
+                return Err(QNetXError::CondensationError(
+                    "channel states differ in shape".into(),
+                ));
+            }
+            for (index, probs) in mixture.iter_mut().enumerate() {
+                for (basis, p) in probs.iter_mut().enumerate() {
+                    let amp = state.amplitude(index, basis).unwrap_or_default();
+                    *p += weight * amp.norm_sqr();
+                }
+            }
+            total += weight;
+        }
+        if total <= 0.0 {
+            return Err(QNetXError::CondensationError(
+                "no weighted channels to condense".into(),
+            ));
+        }
+
+        let digits = mixture
+            .into_iter()
+            .map(|probs| {
+                Qid::from_f64_vec(probs.iter().map(|p| Complex::new((p / total).sqrt(), 0.0)).collect())
+            })
+            .collect();
+        self.metrics.inc_counter("condense", 1);
+        Ok(QNum::new(digits))
+    }
+
+    /// Weight of `dim` in `condense`: its configured weight, clamped at 0, or 1.0.
+    fn dimension_weight(&self, dim: &Dimension) -> f64 {
+        self.config
+            .dimension_weights
+            .get(&dim.0)
+            .map_or(1.0, |w| w.max(0.0))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use qublis_qnum::{QNum, Qid};
+    use crate::types::ChannelId;
 
     /// Build a dummy mesh with given channel QNums.
     fn build_dummy_mesh(channels: Vec<(ChannelId, QNum)>) -> QuantumMesh {
         let cfg = QNetXConfig::default();
         let mut mesh = QuantumMesh::new(&cfg);
         for (id, q) in channels {
-            mesh.channels.insert(id, q);
+            mesh.insert_channel(id, q, ("A".into(), "B".into()));
         }
         mesh
     }
 
+    /// Expected value of digit `index` of `q`.
+    fn expected_digit(q: &QNum, index: usize) -> f64 {
+        (0..10)
+            .map(|b| b as f64 * q.amplitude(index, b).unwrap().norm_sqr())
+            .sum()
+    }
+
     #[test]
     fn test_condense_all_single() {
         let q1 = QNum::from_digits(&[1, 0]);
         let mesh = build_dummy_mesh(vec![(vec![0,1], q1.clone())]);
         let mut sc = StateCondenser::new(&QNetXConfig::default());
-        let summary = sc.condense_all(&mesh).unwrap();
-        assert_eq!(summary.measure(), q1.measure());
+        let mut summary = sc.condense_all(&mesh).unwrap();
+        assert_eq!(summary.measure(), q1.clone().measure());
     }
 
     #[test]
@@ -108,9 +179,9 @@ mod tests {
         let q2 = QNum::from_digits(&[2, 0]);
         let mesh = build_dummy_mesh(vec![(vec![0,1], q1), (vec![0,2], q2)]);
         let mut sc = StateCondenser::new(&QNetXConfig::default());
-        let summary = sc.condense_all(&mesh).unwrap();
-        // measurement always yields 30
-        assert_eq!(summary.measure(), vec![3, 0]);
+        let mut summary = sc.condense_all(&mesh).unwrap();
+        // measurement always yields 30, with qadd's extra carry digit
+        assert_eq!(summary.measure(), vec![0, 3, 0]);
     }
 
     #[test]
@@ -121,21 +192,52 @@ mod tests {
         matches!(err, QNetXError::CondensationError(_));
     }
 
+    #[test]
+    fn test_condense_weights_shift_summary() {
+        let mut mesh = QuantumMesh::new(&QNetXConfig::default());
+        mesh.insert_channel(vec![1], QNum::from_digits(&[1]), ("core".into(), "edge".into()));
+        mesh.insert_channel(vec![9], QNum::from_digits(&[9]), ("far".into(), "edge".into()));
+
+        // equal weights: an even mix of 1 and 9
+        let mut cfg = QNetXConfig::default();
+        let even = StateCondenser::new(&cfg).condense(&mesh).unwrap();
+        assert!((expected_digit(&even, 0) - 5.0).abs() < 1e-9);
+
+        // channel 1 weighs (9 + 1) / 2 = 5 against channel 9's 1
+        cfg.dimension_weights.insert("core".into(), 9.0);
+        let mut sc = StateCondenser::new(&cfg);
+        let skewed = sc.condense(&mesh).unwrap();
+        assert!((expected_digit(&skewed, 0) - (5.0 * 1.0 + 9.0) / 6.0).abs() < 1e-9);
+        assert!(expected_digit(&skewed, 0) < expected_digit(&even, 0));
+        assert!(skewed.norm_defect() < 1e-12);
+
+        // zeroing every dimension leaves nothing to condense
+        let cfg = QNetXConfig {
+            dimension_weights: [("core", 0.0), ("far", 0.0), ("edge", 0.0)]
+                .into_iter()
+                .map(|(d, w)| (d.to_string(), w))
+                .collect(),
+            ..QNetXConfig::default()
+        };
+        let err = StateCondenser::new(&cfg).condense(&mesh).unwrap_err();
+        assert!(matches!(err, QNetXError::CondensationError(_)));
+    }
+
     #[test]
     fn test_condense_by_prefix_groups() {
         // Channels: [1x, 1y, 2z]
         let q1 = QNum::from_digits(&[1]);
         let q2 = QNum::from_digits(&[2]);
         let mesh = build_dummy_mesh(vec![
-            (vec![1], q1.clone()),
-            (vec![1], q1.clone()),
-            (vec![2], q2.clone()),
+            (vec![1, 0], q1.clone()),
+            (vec![1, 1], q1.clone()),
+            (vec![2, 0], q2.clone()),
         ]);
         let mut sc = StateCondenser::new(&QNetXConfig::default());
         let groups = sc.condense_by_prefix(&mesh);
-        // Group 1: 1+1 = 2, Group 2: 2
+        // Group 1: 1+1 = 2 (with qadd's carry digit), Group 2: 2
         assert_eq!(groups.len(), 2);
-        assert_eq!(groups.get(&1).unwrap().measure(), vec![2]);
-        assert_eq!(groups.get(&2).unwrap().measure(), vec![2]);
+        assert_eq!(groups[&1].clone().measure(), vec![0, 2]);
+        assert_eq!(groups[&2].clone().measure(), vec![2]);
     }
 }
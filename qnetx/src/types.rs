@@ -1,7 +1,8 @@
 //! Core types for QNetX entangled overlay mesh (Qublis v2.0)
 //!
-//! Defines `Dimension` (a named logical dimension) and `ChannelId`
-//! (the identifier for an entangled channel, as a sequence of digits).
+//! Defines `Dimension` (a named logical dimension), `ChannelId`
+//! (the identifier for an entangled channel, as a sequence of digits), and
+//! `ChannelAdvert` (a channel's endpoints, as exchanged by gossip).
 
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -32,6 +33,16 @@ impl From<&str> for Dimension {
 /// Identifier for an entangled channel: a sequence of decimal digits.
 pub type ChannelId = Vec<u8>;
 
+/// A channel's identifier and endpoints, without its entangled state, as
+/// shared between peers during gossip.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ChannelAdvert {
+    /// Identifier of the advertised channel.
+    pub id: ChannelId,
+    /// The `(a, b)` dimensions the channel connects.
+    pub endpoints: (Dimension, Dimension),
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,31 @@
+//! Criterion benchmarks for the `qadd` / `qmul` gates.
+//!
+//! Run with `cargo bench -p qublis-qnum`.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use num_complex::Complex;
+use qublis_qnum::{qadd, qmul, QNum};
+
+/// A `len`-digit QNum whose every digit is an equal superposition of |0⟩ and |1⟩.
+fn superposed(len: usize) -> QNum {
+    let amp = Complex::new(1.0 / 2f64.sqrt(), 0.0);
+    QNum::from_superposed(vec![(vec![0; len], amp), (vec![1; len], amp)])
+}
+
+fn bench_gates(c: &mut Criterion) {
+    let mut group = c.benchmark_group("gates");
+    for len in [1usize, 2, 4, 6] {
+        let a = superposed(len);
+        let b = superposed(len);
+        group.bench_with_input(BenchmarkId::new("qadd", len), &len, |bench, _| {
+            bench.iter(|| qadd(black_box(&a), black_box(&b)))
+        });
+        group.bench_with_input(BenchmarkId::new("qmul", len), &len, |bench, _| {
+            bench.iter(|| qmul(black_box(&a), black_box(&b)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_gates);
+criterion_main!(benches);
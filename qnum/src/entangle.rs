@@ -1,13 +1,127 @@
 //! Entanglement utilities for `qublis-qnum`
 //!
-//! Provides a Bell‐like entangling transform on two `QNum`s of equal length.
-//! After entanglement, measuring one will yield correlated measurement outcomes
-//! in the other.
+//! Provides a Bell‐like entangling transform on two `QNum`s of equal length,
+//! and a GHZ‐like transform across any number of them. A `QNum` holds a
+//! product state, so the correlation itself is carried by the returned
+//! [`EntanglementLink`], which can be measured to draw correlated outcomes.
+//! For analysis, [`joint_distribution`] expands several `QNum`s into a
+//! [`JointState`] and [`partial_trace`] recovers one subsystem's marginal.
 
+use crate::error::QNumError;
+use crate::gates::enumerate;
 use crate::qid::Qid;
-use crate::qnum::QNum;
+use crate::qnum::{Lineage, QNum, Traced};
 use num_complex::Complex;
 use ordered_float::OrderedFloat;
+use alloc::collections::BTreeMap;
+use alloc::{vec, vec::Vec};
+use rand::Rng;
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::Float;
+
+/// Joint state of two `QNum`s entangled by [`entangle_with_link`]: one
+/// distribution over basis-value pairs `(x, y)` per aligned digit.
+///
+/// The two-mode mix is symmetric under swapping its inputs, so digit `i`'s
+/// table is
+/// ```text
+/// P(x, y) = ½·pₐ(x)·p_b(y) + ½·p_b(x)·pₐ(y)
+/// ```
+/// where `pₐ`, `p_b` are the pre-entanglement probabilities. For classical
+/// inputs `|4⟩` and `|7⟩` this is `{(4,7): ½, (7,4): ½}`.
+///
+/// The mixed `QNum`s only hold product states with these tables' marginals;
+/// measuring them separately loses the correlation. Draw correlated outcomes
+/// with [`measure_with`](Self::measure_with) instead.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EntanglementLink {
+    positions: Vec<BTreeMap<(u8, u8), f64>>,
+}
+
+impl EntanglementLink {
+    /// Number of aligned digit positions.
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns true if the linked `QNum`s had no digits.
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Probability of observing `(xdigit, ydigit)` at digit position `index`
+    /// (0.0 if the pair or position is absent).
+    pub fn correlation_at(&self, index: usize, xdigit: u8, ydigit: u8) -> f64 {
+        self.positions
+            .get(index)
+            .and_then(|table| table.get(&(xdigit, ydigit)))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Probability of observing `(xdigit, ydigit)` at a uniformly chosen digit
+    /// position; for single-digit `QNum`s this is simply the joint probability.
+    pub fn correlation(&self, xdigit: u8, ydigit: u8) -> f64 {
+        if self.positions.is_empty() {
+            return 0.0;
+        }
+        let total: f64 = (0..self.positions.len())
+            .map(|i| self.correlation_at(i, xdigit, ydigit))
+            .sum();
+        total / self.positions.len() as f64
+    }
+
+    /// Pairs with non-zero probability at digit position `index`, sorted.
+    pub fn pairs(&self, index: usize) -> Vec<((u8, u8), f64)> {
+        let mut pairs: Vec<_> = self
+            .positions
+            .get(index)
+            .map(|table| table.iter().map(|(&k, &p)| (k, p)).collect())
+            .unwrap_or_default();
+        pairs.sort_by_key(|&(pair, _)| pair);
+        pairs
+    }
+
+    /// Sample one outcome for both linked `QNum`s, digit position by
+    /// position, from the joint tables.
+    ///
+    /// Returns `(a_digits, b_digits)`, MSB first. The link is not consumed,
+    /// so repeated calls draw independent samples of the same joint state.
+    pub fn measure_with<R: Rng + ?Sized>(&self, rng: &mut R) -> (Vec<u8>, Vec<u8>) {
+        self.positions
+            .iter()
+            .map(|table| *sample_weighted(table, rng).expect("entanglement table is empty"))
+            .unzip()
+    }
+
+    #[cfg(feature = "std")]
+    /// Like [`measure_with`](Self::measure_with), drawing from the
+    /// thread-local RNG.
+    pub fn measure(&self) -> (Vec<u8>, Vec<u8>) {
+        self.measure_with(&mut rand::thread_rng())
+    }
+}
+
+/// Pick a key of `table` with probability proportional to its weight, or
+/// `None` if the weights sum to zero.
+fn sample_weighted<'a, K, R: Rng + ?Sized>(table: &'a BTreeMap<K, f64>, rng: &mut R) -> Option<&'a K> {
+    let total: f64 = table.values().sum();
+    if total <= 0.0 {
+        return None;
+    }
+    let mut target = rng.gen::<f64>() * total;
+    let mut last = None;
+    for (key, &p) in table.iter().filter(|(_, &p)| p > 0.0) {
+        if target < p {
+            return Some(key);
+        }
+        target -= p;
+        last = Some(key);
+    }
+    // rounding left `target` just past the final weight
+    last
+}
 
 /// Entangle two `QNum`s in a Bell‐like fashion.
 /// 
@@ -15,71 +129,458 @@ use ordered_float::OrderedFloat;
 /// ```text
 /// (α, β) ↦ ( (α+β)/√2, (α−β)/√2 )
 /// ```
-/// ensuring that measuring one `QNum` influences the other.
+/// spreading each digit over both inputs' supports. The resulting
+/// correlation is only held by the [`EntanglementLink`] that
+/// [`entangle_with_link`] returns; `entangle` discards it.
 /// 
 /// # Panics
 /// 
-/// Panics if `a.len() != b.len()`.
+/// Panics if `a.len() != b.len()` or the two `QNum`s have different radices.
 pub fn entangle(a: &mut QNum, b: &mut QNum) {
+    entangle_with_link(a, b);
+}
+
+/// Like [`entangle`], but returns the [`EntanglementLink`] holding the pair's
+/// joint distribution, to measure or inspect.
+///
+/// # Panics
+///
+/// Panics if `a.len() != b.len()` or the two `QNum`s have different radices.
+pub fn entangle_with_link(a: &mut QNum, b: &mut QNum) -> EntanglementLink {
     assert_eq!(
         a.len(),
         b.len(),
         "Entanglement requires QNums of the same length"
     );
-    let inv_sqrt2 = Complex::new(OrderedFloat(1.0 / 2f64.sqrt()), OrderedFloat(0.0));
+    assert_eq!(
+        a.radix(),
+        b.radix(),
+        "Entanglement requires QNums of the same radix"
+    );
+    let mut link = EntanglementLink::default();
+    for (qa, qb) in a.0.iter_mut().zip(b.0.iter_mut()) {
+        link.positions.push(swap_symmetric_joint(qa, qb));
+        mix_pair(qa, qb, 1.0);
+    }
+    link
+}
+
+/// Like [`entangle`], but also records each of `a` and `b` as a correlation
+/// partner of the other; see [`Traced`].
+///
+/// # Panics
+///
+/// Panics if `a.len() != b.len()` or the two `QNum`s have different radices.
+pub fn entangle_traced(a: &mut Traced, b: &mut Traced) {
+    entangle(&mut a.value, &mut b.value);
+    record_lineage(&mut [&mut a.lineage, &mut b.lineage]);
+}
 
+/// Partially entangle two `QNum`s, with `strength` interpolating between
+/// leaving them untouched (`0.0`) and the full [`entangle`] mix (`1.0`).
+///
+/// Per digit position and basis index the amplitudes become
+/// ```text
+/// α' = (1−s)·α + s·(α+β)/√2
+/// β' = (1−s)·β + s·(α−β)/√2
+/// ```
+/// and each `Qid` is re-normalized, as in [`entangle`].
+///
+/// # Panics
+///
+/// Panics if `strength` is not in `[0, 1]`, `a.len() != b.len()`, or the two
+/// `QNum`s have different radices.
+pub fn entangle_weighted(a: &mut QNum, b: &mut QNum, strength: f64) {
+    assert!(
+        (0.0..=1.0).contains(&strength),
+        "Entanglement strength {} is not in [0, 1]",
+        strength
+    );
+    assert_eq!(
+        a.len(),
+        b.len(),
+        "Entanglement requires QNums of the same length"
+    );
+    assert_eq!(
+        a.radix(),
+        b.radix(),
+        "Entanglement requires QNums of the same radix"
+    );
     for (qa, qb) in a.0.iter_mut().zip(b.0.iter_mut()) {
-        let mut new_qa = [Complex::new(OrderedFloat(0.0), OrderedFloat(0.0)); 10];
-        let mut new_qb = [Complex::new(OrderedFloat(0.0), OrderedFloat(0.0)); 10];
+        mix_pair(qa, qb, strength);
+    }
+}
+
+/// Record every member of `lineages` as a correlation partner of every other.
+fn record_lineage(lineages: &mut [&mut Lineage]) {
+    let ids: Vec<u64> = lineages.iter().map(|l| l.id).collect();
+    for (i, lineage) in lineages.iter_mut().enumerate() {
+        let others = ids.iter().enumerate().filter(|&(j, _)| j != i);
+        lineage.partners.extend(others.map(|(_, &id)| id));
+    }
+}
 
-        for i in 0..10 {
-            let α = qa.amps[i];
-            let β = qb.amps[i];
-            // Bell‐type mixing per basis index, all in Complex<OrderedFloat<f64>>
-            new_qa[i] = (α + β) * inv_sqrt2;
-            new_qb[i] = (α - β) * inv_sqrt2;
+/// Bell‐type mixing of one aligned digit pair, blended with the identity by
+/// `strength`, followed by re-normalization.
+fn mix_pair(qa: &mut Qid, qb: &mut Qid, strength: f64) {
+    let inv_sqrt2 = Complex::new(OrderedFloat(1.0 / 2f64.sqrt()), OrderedFloat(0.0));
+    let s = Complex::new(OrderedFloat(strength), OrderedFloat(0.0));
+    let keep = Complex::new(OrderedFloat(1.0 - strength), OrderedFloat(0.0));
+    let radix = qa.radix();
+    let mut new_qa = vec![Complex::new(OrderedFloat(0.0), OrderedFloat(0.0)); radix];
+    let mut new_qb = vec![Complex::new(OrderedFloat(0.0), OrderedFloat(0.0)); radix];
+
+    for i in 0..radix {
+        let α = qa.amps[i];
+        let β = qb.amps[i];
+        // Bell‐type mixing per basis index, all in Complex<OrderedFloat<f64>>
+        new_qa[i] = keep * α + s * ((α + β) * inv_sqrt2);
+        new_qb[i] = keep * β + s * ((α - β) * inv_sqrt2);
+    }
+
+    qa.amps = new_qa;
+    qb.amps = new_qb;
+
+    // Re‐normalize each Qid after mixing
+    qa.normalize();
+    qb.normalize();
+}
+
+/// Joint table `½·pₐ(x)·p_b(y) + ½·p_b(x)·pₐ(y)` over the digits' supports.
+fn swap_symmetric_joint(qa: &Qid, qb: &Qid) -> BTreeMap<(u8, u8), f64> {
+    let probs = |q: &Qid| -> Vec<(u8, f64)> {
+        q.amps
+            .iter()
+            .enumerate()
+            .map(|(d, c)| (d as u8, c.norm_sqr().into_inner()))
+            .filter(|&(_, p)| p > 0.0)
+            .collect()
+    };
+    let (pa, pb) = (probs(qa), probs(qb));
+    let (na, nb) = (pa.iter().map(|p| p.1).sum::<f64>(), pb.iter().map(|p| p.1).sum::<f64>());
+
+    let mut table = BTreeMap::new();
+    if na == 0.0 || nb == 0.0 {
+        return table;
+    }
+    for &(x, px) in &pa {
+        for &(y, py) in &pb {
+            let p = 0.5 * (px / na) * (py / nb);
+            *table.entry((x, y)).or_insert(0.0) += p;
+            *table.entry((y, x)).or_insert(0.0) += p;
         }
+    }
+    table
+}
+
+/// Entangle any number of `QNum`s in a GHZ‐like fashion, returning their
+/// joint state.
+///
+/// The joint state is the symmetric superposition of every member's basis
+/// states, each repeated across all members:
+/// ```text
+/// Σₖ Σ_b αₖ(b)·|b⟩⊗…⊗|b⟩ / √n
+/// ```
+/// where `αₖ(b)` is member `k`'s amplitude on digit vector `b`. For classical
+/// inputs `|x₁⟩ … |xₙ⟩` this is the GHZ state `Σₖ |xₖ…xₖ⟩/√n`: measuring the
+/// returned [`JointState`] always gives every member the same digits.
+///
+/// A `QNum` cannot hold that correlation, so each member is replaced by its
+/// reduced state (see [`partial_trace`]): measured on its own it follows the
+/// right distribution, but only the returned `JointState` keeps the members
+/// in agreement. Cost grows with the sum of the members' supports.
+///
+/// Fewer than two members leaves them untouched and returns their product
+/// state. Fails with [`QNumError::LengthMismatch`] or
+/// [`QNumError::RadixMismatch`] on ragged members, and with
+/// [`QNumError::ZeroNorm`] (index 0) if the members' amplitudes cancel out.
+/// On error, no member is modified.
+pub fn entangle_all(qs: &mut [&mut QNum]) -> Result<JointState, QNumError> {
+    if qs.len() < 2 {
+        let members: Vec<&QNum> = qs.iter().map(|q| &**q).collect();
+        return joint_distribution(&members);
+    }
+    let (len, radix) = (qs[0].len(), qs[0].radix());
+    for q in qs.iter() {
+        if q.len() != len {
+            return Err(QNumError::LengthMismatch { expected: len, found: q.len() });
+        }
+        if q.radix() != radix {
+            return Err(QNumError::RadixMismatch { expected: radix, found: q.radix() });
+        }
+    }
 
-        qa.amps = new_qa;
-        qb.amps = new_qb;
+    let n = qs.len();
+    let scale = 1.0 / (n as f64).sqrt();
+    let states = qs
+        .iter()
+        .flat_map(|q| enumerate(q))
+        .map(|(digits, amp)| (digits.repeat(n), amp * scale))
+        .collect();
+    let joint = JointState::from_amplitudes(radix, vec![len; n], states)?;
+    if joint.probs.is_empty() {
+        return Err(QNumError::ZeroNorm { index: 0 });
+    }
 
-        // Re‐normalize each Qid after mixing
-        qa.normalize();
-        qb.normalize();
+    for (i, q) in qs.iter_mut().enumerate() {
+        q.0 = partial_trace(&joint, i).0;
     }
+    Ok(joint)
+}
+
+/// Like [`entangle_all`], but also records every member as a correlation
+/// partner of every other; see [`Traced`]. On error, no member or lineage
+/// is modified.
+pub fn entangle_all_traced(qs: &mut [&mut Traced]) -> Result<JointState, QNumError> {
+    let mut values: Vec<&mut QNum> = qs.iter_mut().map(|t| &mut t.value).collect();
+    let joint = entangle_all(&mut values)?;
+    let mut lineages: Vec<&mut Lineage> = qs.iter_mut().map(|t| &mut t.lineage).collect();
+    record_lineage(&mut lineages);
+    Ok(joint)
+}
+
+/// Joint outcome distribution over several `QNum` subsystems.
+///
+/// Each outcome is the concatenation of every subsystem's digits (MSB first,
+/// subsystems in order) and maps to its probability. Unlike a `QNum`, a
+/// `JointState` can hold correlations between subsystems, e.g. a Bell pair.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JointState {
+    radix: usize,
+    widths: Vec<usize>,
+    probs: BTreeMap<Vec<u8>, f64>,
 }
 
-#[cfg(test)]
+impl JointState {
+    /// Build a joint state from `(digits, amplitude)` pairs over subsystems of
+    /// the given digit `widths`.
+    ///
+    /// Amplitudes of repeated outcomes are summed before squaring, and the
+    /// resulting distribution is normalized to total probability 1.
+    pub fn from_amplitudes(
+        radix: usize,
+        widths: Vec<usize>,
+        states: Vec<(Vec<u8>, Complex<f64>)>,
+    ) -> Result<Self, QNumError> {
+        let total_len: usize = widths.iter().sum();
+        let mut amps: BTreeMap<Vec<u8>, Complex<f64>> = BTreeMap::new();
+        for (digits, amp) in states {
+            if digits.len() != total_len {
+                return Err(QNumError::LengthMismatch {
+                    expected: total_len,
+                    found: digits.len(),
+                });
+            }
+            if let Some(&d) = digits.iter().find(|&&d| d as usize >= radix) {
+                return Err(QNumError::DigitOutOfRange { digit: d as usize, radix });
+            }
+            *amps.entry(digits).or_insert_with(|| Complex::new(0.0, 0.0)) += amp;
+        }
+
+        let mut probs: BTreeMap<Vec<u8>, f64> = amps
+            .into_iter()
+            .map(|(digits, amp)| (digits, amp.norm_sqr()))
+            .filter(|(_, p)| *p > 0.0)
+            .collect();
+        let total: f64 = probs.values().sum();
+        if total > 0.0 {
+            probs.values_mut().for_each(|p| *p /= total);
+        }
+        Ok(JointState { radix, widths, probs })
+    }
+
+    /// Base-10 Bell pair `(|digit_a digit_b⟩ + |digit_b digit_a⟩)/√2` over two
+    /// single-digit subsystems.
+    ///
+    /// Measuring it yields `digit_a` in one subsystem and `digit_b` in the
+    /// other, each way round with probability ½. If the digits are equal the
+    /// pair is simply `|digit_a digit_a⟩`.
+    ///
+    /// Returns [`QNumError::DigitOutOfRange`] if either digit is not below
+    /// `DEFAULT_RADIX`.
+    pub fn bell_pair(digit_a: u8, digit_b: u8) -> Result<Self, QNumError> {
+        let h = Complex::new(1.0 / 2f64.sqrt(), 0.0);
+        JointState::from_amplitudes(
+            crate::qid::DEFAULT_RADIX,
+            vec![1, 1],
+            vec![(vec![digit_a, digit_b], h), (vec![digit_b, digit_a], h)],
+        )
+    }
+
+    /// Radix shared by every digit.
+    pub fn radix(&self) -> usize {
+        self.radix
+    }
+
+    /// Number of digits in each subsystem.
+    pub fn widths(&self) -> &[usize] {
+        &self.widths
+    }
+
+    /// Probability of the concatenated outcome `digits` (0.0 if absent).
+    pub fn probability(&self, digits: &[u8]) -> f64 {
+        self.probs.get(digits).copied().unwrap_or(0.0)
+    }
+
+    /// Sample one joint outcome and split it into each subsystem's digits
+    /// (MSB first, subsystems in order), so correlated subsystems always
+    /// report consistent values. The state itself is left unchanged.
+    ///
+    /// # Panics
+    /// If the state has no outcome with non-zero probability.
+    pub fn measure_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<Vec<u8>> {
+        let outcome = sample_weighted(&self.probs, rng).expect("joint state has no outcomes");
+        let mut rest = outcome.as_slice();
+        self.widths
+            .iter()
+            .map(|&width| {
+                let (digits, tail) = rest.split_at(width);
+                rest = tail;
+                digits.to_vec()
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "std")]
+    /// Like [`measure_with`](Self::measure_with), drawing from the
+    /// thread-local RNG.
+    pub fn measure(&self) -> Vec<Vec<u8>> {
+        self.measure_with(&mut rand::thread_rng())
+    }
+
+    /// Measure only digit `index` of subsystem `member`, sampling it from its
+    /// marginal, and condition the state on the result: joint outcomes that
+    /// disagree with it are dropped and the rest renormalized, so every digit
+    /// correlated with the measured one collapses with it.
+    ///
+    /// Fails with [`QNumError::IndexOutOfRange`] if `member` is not a
+    /// subsystem or `index` is not one of its digits, and with
+    /// [`QNumError::ZeroNorm`] (index 0) if the state has no outcomes. On
+    /// error, the state is unchanged.
+    pub fn measure_digit_with<R: Rng + ?Sized>(
+        &mut self,
+        member: usize,
+        index: usize,
+        rng: &mut R,
+    ) -> Result<u8, QNumError> {
+        let width = *self
+            .widths
+            .get(member)
+            .ok_or(QNumError::IndexOutOfRange { index: member, len: self.widths.len() })?;
+        if index >= width {
+            return Err(QNumError::IndexOutOfRange { index, len: width });
+        }
+        let position = self.widths[..member].iter().sum::<usize>() + index;
+
+        let mut marginal: BTreeMap<u8, f64> = BTreeMap::new();
+        for (digits, p) in &self.probs {
+            *marginal.entry(digits[position]).or_insert(0.0) += p;
+        }
+        let digit = *sample_weighted(&marginal, rng).ok_or(QNumError::ZeroNorm { index: 0 })?;
+
+        self.probs.retain(|digits, _| digits[position] == digit);
+        let total: f64 = self.probs.values().sum();
+        self.probs.values_mut().for_each(|p| *p /= total);
+        Ok(digit)
+    }
+}
+
+/// Expand independent `QNum`s into their joint outcome distribution.
+///
+/// Every `QNum` is a product state, so the joint probability of an outcome is
+/// the product of the members' probabilities. Cost grows with the product of
+/// the members' supports.
+pub fn joint_distribution(qs: &[&QNum]) -> Result<JointState, QNumError> {
+    let radix = qs.first().map_or(crate::qid::DEFAULT_RADIX, |q| q.radix());
+    if let Some(q) = qs.iter().find(|q| q.radix() != radix) {
+        return Err(QNumError::RadixMismatch { expected: radix, found: q.radix() });
+    }
+
+    let mut states: Vec<(Vec<u8>, Complex<f64>)> = vec![(Vec::new(), Complex::new(1.0, 0.0))];
+    for q in qs {
+        let branches: Vec<_> = enumerate(q).collect();
+        states = states
+            .iter()
+            .flat_map(|(prefix, amp)| {
+                branches.iter().map(move |(digits, a)| {
+                    let mut joined = prefix.clone();
+                    joined.extend_from_slice(digits);
+                    (joined, amp * a)
+                })
+            })
+            .collect();
+    }
+    JointState::from_amplitudes(radix, qs.iter().map(|q| q.len()).collect(), states)
+}
+
+/// Reduced state of subsystem `keep`, tracing out every other subsystem.
+///
+/// A `QNum` cannot hold a mixed state, so the marginal is returned as the
+/// product of its per-digit populations: digit `i` gets amplitude `√pᵢ(v)`
+/// for each value `v`. Off-diagonal coherences (and correlations between the
+/// kept digits) are discarded.
+///
+/// # Panics
+///
+/// Panics if `keep` is not a subsystem index of `joint`.
+pub fn partial_trace(joint: &JointState, keep: usize) -> QNum {
+    assert!(keep < joint.widths.len(), "subsystem index out of range");
+    let offset: usize = joint.widths[..keep].iter().sum();
+    let width = joint.widths[keep];
+
+    let mut marginals = vec![vec![0.0; joint.radix]; width];
+    for (digits, p) in &joint.probs {
+        for (marginal, &d) in marginals.iter_mut().zip(&digits[offset..offset + width]) {
+            marginal[d as usize] += p;
+        }
+    }
+    QNum::new(
+        marginals
+            .into_iter()
+            .map(|ps| Qid::from_f64_vec(ps.into_iter().map(|p| Complex::new(p.sqrt(), 0.0)).collect()))
+            .collect(),
+    )
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use crate::qnum::QNum;
     use std::collections::HashSet;
 
     #[test]
-    fn entangle_correlates_two_single_digit_qnums() {
-        // Start with two classical QNums: |4⟩ and |7⟩
-        let mut a = QNum::from_digits(&[4]);
-        let mut b = QNum::from_digits(&[7]);
-
-        // Entangle them
-        entangle(&mut a, &mut b);
+    fn entangle_link_measures_correlated_outcomes() {
+        use rand::{rngs::StdRng, SeedableRng};
+        let mut a = QNum::from_digits(&[4, 2]);
+        let mut b = QNum::from_digits(&[7, 2]);
+        let link = entangle_with_link(&mut a, &mut b);
 
-        // Collect measurement outcomes over many trials
+        let mut rng = StdRng::seed_from_u64(1769);
         let mut outcomes = HashSet::new();
         for _ in 0..100 {
-            let mut aa = a.clone();
-            let mut bb = b.clone();
-            let da = aa.measure()[0];
-            let db = bb.measure()[0];
-            outcomes.insert((da, db));
+            outcomes.insert(link.measure_with(&mut rng));
         }
+        let expected: HashSet<_> = [
+            (vec![4, 2], vec![7, 2]),
+            (vec![7, 2], vec![4, 2]),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(outcomes, expected);
+    }
+
+    #[test]
+    fn entangle_link_reports_two_correlated_pairs() {
+        let mut a = QNum::from_digits(&[4]);
+        let mut b = QNum::from_digits(&[7]);
+        let link = entangle_with_link(&mut a, &mut b);
 
-        // We expect to see either (4,7) or (7,4) in the results
-        assert!(
-            outcomes.contains(&(4, 7)) || outcomes.contains(&(7, 4)),
-            "Expected entangled outcomes to correlate, got {:?}",
-            outcomes
-        );
+        assert_eq!(link.len(), 1);
+        assert_eq!(link.pairs(0), vec![((4, 7), 0.5), ((7, 4), 0.5)]);
+        assert!((link.correlation(4, 7) - 0.5).abs() < 1e-12);
+        assert!((link.correlation(7, 4) - 0.5).abs() < 1e-12);
+        assert_eq!(link.correlation(4, 4), 0.0);
+        assert_eq!(link.correlation_at(1, 4, 7), 0.0);
     }
 
     #[test]
@@ -91,8 +592,161 @@ mod tests {
         assert_eq!(b.len(), 3);
         // After entanglement, each Qid should still be normalized
         for qid in a.0.iter().chain(b.0.iter()) {
-            let norm_sq: f64 = qid.amps.iter().map(|c| c.norm_sqr()).sum();
+            let norm_sq: f64 = qid.amps.iter().map(|c| c.norm_sqr().into_inner()).sum();
             assert!((norm_sq - 1.0).abs() < 1e-12, "Qid not normalized");
         }
     }
+
+    #[test]
+    fn entangle_all_correlates_three_members() {
+        use rand::{rngs::StdRng, SeedableRng};
+        let mut a = QNum::from_digits(&[1, 2, 3]);
+        let mut b = QNum::from_digits(&[1, 2, 5]);
+        let mut c = QNum::from_digits(&[1, 2, 8]);
+        let ghz = entangle_all(&mut [&mut a, &mut b, &mut c]).unwrap();
+        assert_eq!(ghz.widths(), &[3, 3, 3]);
+        assert!((ghz.probability(&[1, 2, 5, 1, 2, 5, 1, 2, 5]) - 1.0 / 3.0).abs() < 1e-12);
+        assert_eq!(ghz.probability(&[1, 2, 3, 1, 2, 5, 1, 2, 8]), 0.0);
+
+        let mut rng = StdRng::seed_from_u64(1760);
+        let mut seen = HashSet::new();
+        for _ in 0..100 {
+            let outcome = ghz.measure_with(&mut rng);
+            assert!(outcome.iter().all(|o| *o == outcome[0]), "members disagree: {:?}", outcome);
+            seen.insert(outcome[0].clone());
+        }
+        let expected: HashSet<Vec<u8>> = [vec![1, 2, 3], vec![1, 2, 5], vec![1, 2, 8]].into_iter().collect();
+        assert_eq!(seen, expected);
+
+        // members hold their reduced state: the last digit is an even mix
+        for q in [&a, &b, &c] {
+            assert!((q.entropy() - 3f64.ln()).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn bell_pair_measurements_anticorrelate() {
+        use rand::{rngs::StdRng, SeedableRng};
+        let mut rng = StdRng::seed_from_u64(1775);
+        let pair = JointState::bell_pair(3, 8).unwrap();
+        let mut seen = HashSet::new();
+        for _ in 0..100 {
+            let outcome = pair.measure_with(&mut rng);
+            assert!(
+                outcome == [vec![3], vec![8]] || outcome == [vec![8], vec![3]],
+                "uncorrelated outcome {:?}",
+                outcome
+            );
+            seen.insert(outcome);
+        }
+        assert_eq!(seen.len(), 2);
+
+        let same = JointState::bell_pair(4, 4).unwrap();
+        assert_eq!(same.probability(&[4, 4]), 1.0);
+        assert_eq!(same.measure_with(&mut rng), [vec![4], vec![4]]);
+        assert!(JointState::bell_pair(4, 10).is_err());
+    }
+
+    #[test]
+    fn partial_trace_of_bell_pair_is_maximally_mixed() {
+        let h = Complex::new(1.0 / 2f64.sqrt(), 0.0);
+        let bell = JointState::from_amplitudes(2, vec![1, 1], vec![(vec![0, 0], h), (vec![1, 1], h)]).unwrap();
+        assert!(bell.probability(&[0, 1]).abs() < 1e-12);
+
+        for keep in 0..2 {
+            let marginal = partial_trace(&bell, keep);
+            assert_eq!(marginal.len(), 1);
+            assert!((marginal.entropy() - 2f64.ln()).abs() < 1e-12);
+            assert!(marginal.norm_defect() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn partial_trace_of_product_recovers_member() {
+        let h = Complex::new(1.0 / 2f64.sqrt(), 0.0);
+        let a = QNum::from_superposed(vec![(vec![3, 1], h), (vec![3, 6], h)]);
+        let b = QNum::from_digits(&[9]);
+        let joint = joint_distribution(&[&a, &b]).unwrap();
+        assert_eq!(joint.widths(), &[2, 1]);
+        assert!((joint.probability(&[3, 6, 9]) - 0.5).abs() < 1e-12);
+
+        assert!(partial_trace(&joint, 0).fidelity(&a) > 1.0 - 1e-12);
+        assert_eq!(partial_trace(&joint, 1), b);
+    }
+
+    #[test]
+    fn entangle_all_rejects_ragged_members() {
+        let mut a = QNum::from_digits(&[1, 2]);
+        let mut b = QNum::from_digits(&[3]);
+        let err = entangle_all(&mut [&mut a, &mut b]).unwrap_err();
+        assert_eq!(err, QNumError::LengthMismatch { expected: 2, found: 1 });
+        assert_eq!(a, QNum::from_digits(&[1, 2]));
+    }
+
+    #[test]
+    fn weighted_entangle_at_zero_leaves_states_independent() {
+        let (mut a, mut b) = (QNum::from_digits(&[4, 1]), QNum::from_digits(&[7, 1]));
+        entangle_weighted(&mut a, &mut b, 0.0);
+        assert_eq!(a, QNum::from_digits(&[4, 1]));
+        assert_eq!(b, QNum::from_digits(&[7, 1]));
+        let joint = joint_distribution(&[&a, &b]).unwrap();
+        assert_eq!(partial_trace(&joint, 0), a);
+        assert_eq!(partial_trace(&joint, 1), b);
+    }
+
+    #[test]
+    fn weighted_entangle_at_one_matches_entangle() {
+        let (mut a, mut b) = (QNum::from_digits(&[4, 1]), QNum::from_digits(&[7, 2]));
+        let (mut full_a, mut full_b) = (a.clone(), b.clone());
+        entangle_weighted(&mut a, &mut b, 1.0);
+        entangle(&mut full_a, &mut full_b);
+        assert_eq!(a, full_a);
+        assert_eq!(b, full_b);
+    }
+
+    #[test]
+    fn weighted_entangle_strength_scales_mixing() {
+        let entropy_at = |strength: f64| {
+            let (mut a, mut b) = (QNum::from_digits(&[4]), QNum::from_digits(&[7]));
+            entangle_weighted(&mut a, &mut b, strength);
+            a.entropy()
+        };
+        let (weak, strong) = (entropy_at(0.25), entropy_at(1.0));
+        assert!(weak > 0.0 && weak < strong, "weak {} strong {}", weak, strong);
+    }
+
+    #[test]
+    #[should_panic(expected = "not in [0, 1]")]
+    fn weighted_entangle_rejects_out_of_range_strength() {
+        let (mut a, mut b) = (QNum::from_digits(&[4]), QNum::from_digits(&[7]));
+        entangle_weighted(&mut a, &mut b, 1.5);
+    }
+
+    #[test]
+    fn entangle_records_lineage_partners() {
+        let mut a = Traced::new(QNum::from_digits(&[4]), 1);
+        let mut b = Traced::new(QNum::from_digits(&[7]), 2);
+        entangle_traced(&mut a, &mut b);
+        assert_eq!(a.correlation_partners(), vec![2]);
+        assert_eq!(b.correlation_partners(), vec![1]);
+        // the wrapped values are entangled exactly as by `entangle`
+        let (mut x, mut y) = (QNum::from_digits(&[4]), QNum::from_digits(&[7]));
+        entangle(&mut x, &mut y);
+        assert_eq!((a.value.clone(), b.value.clone()), (x, y));
+
+        // plain QNums take part without a tag
+        let mut untagged = QNum::from_digits(&[3]);
+        entangle(&mut a, &mut untagged);
+        assert_eq!(a.correlation_partners(), vec![2]);
+
+        let mut c = Traced::new(QNum::from_digits(&[5]), 3);
+        entangle_all_traced(&mut [&mut a, &mut b, &mut c]).unwrap();
+        assert_eq!(a.correlation_partners(), vec![2, 3]);
+        assert_eq!(c.correlation_partners(), vec![1, 2]);
+
+        // lineage survives a JSON round trip
+        let json = serde_json::to_string(&a).unwrap();
+        let back: Traced = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, a);
+    }
 }
@@ -0,0 +1,156 @@
+//! Error type for fallible `Qid` / `QNum` operations.
+
+use core::fmt;
+
+/// Errors returned by fallible `QNum` operations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QNumError {
+    /// A digit vector's length does not match the `QNum`'s length.
+    LengthMismatch {
+        /// Number of digits required.
+        expected: usize,
+        /// Number of digits supplied.
+        found: usize,
+    },
+    /// A digit position is past the end of the `QNum`.
+    IndexOutOfRange {
+        /// Requested digit position.
+        index: usize,
+        /// Number of digits in the `QNum`.
+        len: usize,
+    },
+    /// Two operands have digits of different radix.
+    RadixMismatch {
+        /// Radix of the first operand.
+        expected: usize,
+        /// Radix of the mismatching operand.
+        found: usize,
+    },
+    /// A digit's amplitudes sum to the zero vector and cannot be normalized.
+    ZeroNorm {
+        /// Index of the digit with zero norm.
+        index: usize,
+    },
+    /// A digit is not a valid basis value for its `Qid`'s radix.
+    DigitOutOfRange {
+        /// Offending digit value.
+        digit: usize,
+        /// Radix of the `Qid` it was applied to.
+        radix: usize,
+    },
+    /// The requested basis state has (effectively) zero amplitude.
+    ZeroAmplitude {
+        /// Index of the first digit whose amplitude is negligible.
+        index: usize,
+    },
+    /// A superposition was built from an empty list of basis states.
+    EmptySuperposition,
+    /// An amplitude has a NaN or infinite real or imaginary part.
+    NonFiniteAmplitude {
+        /// Basis value (position within the `Qid`) of the offending amplitude.
+        basis: usize,
+    },
+    /// A measurement basis is not (approximately) unitary.
+    NotUnitary {
+        /// Row of the first entry of `U·U†` that deviates from the identity.
+        row: usize,
+        /// Column of that entry.
+        col: usize,
+    },
+    /// A radix (a `Qid`'s number of amplitudes) is outside `2..=MAX_RADIX`.
+    RadixOutOfRange {
+        /// Offending radix.
+        radix: usize,
+    },
+    /// A binary encoding (see `QNum::from_bytes`) is truncated or malformed.
+    InvalidEncoding {
+        /// Byte offset at which decoding failed.
+        offset: usize,
+    },
+    /// A patch grows a `QNum` but does not supply one of the new digits.
+    MissingDigit {
+        /// Position of the first digit with no value.
+        index: usize,
+    },
+    /// A replayed measurement does not match the recorded run: the log is
+    /// exhausted or its next outcome is impossible for the measured state.
+    ReplayDiverged {
+        /// Position in the replay log of the outcome that could not be used.
+        measurement: usize,
+    },
+}
+
+impl fmt::Display for QNumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QNumError::LengthMismatch { expected, found } => write!(
+                f,
+                "expected {} digits, found {}",
+                expected, found
+            ),
+            QNumError::IndexOutOfRange { index, len } => write!(
+                f,
+                "digit index {} out of range for length {}",
+                index, len
+            ),
+            QNumError::RadixMismatch { expected, found } => write!(
+                f,
+                "expected radix {}, found {}",
+                expected, found
+            ),
+            QNumError::ZeroNorm { index } => write!(
+                f,
+                "digit {} has zero norm",
+                index
+            ),
+            QNumError::DigitOutOfRange { digit, radix } => write!(
+                f,
+                "digit {} out of range for radix {}",
+                digit, radix
+            ),
+            QNumError::ZeroAmplitude { index } => write!(
+                f,
+                "basis state has negligible amplitude at digit {}",
+                index
+            ),
+            QNumError::EmptySuperposition => write!(
+                f,
+                "cannot build a superposition from no basis states"
+            ),
+            QNumError::NonFiniteAmplitude { basis } => write!(
+                f,
+                "amplitude of basis value {} is not finite",
+                basis
+            ),
+            QNumError::NotUnitary { row, col } => write!(
+                f,
+                "basis is not unitary: entry ({}, {}) of U·U† deviates from the identity",
+                row, col
+            ),
+            QNumError::RadixOutOfRange { radix } => write!(
+                f,
+                "radix {} out of range 2..={}",
+                radix,
+                crate::qid::MAX_RADIX
+            ),
+            QNumError::InvalidEncoding { offset } => write!(
+                f,
+                "invalid QNum encoding at byte {}",
+                offset
+            ),
+            QNumError::MissingDigit { index } => write!(
+                f,
+                "no value supplied for digit {}",
+                index
+            ),
+            QNumError::ReplayDiverged { measurement } => write!(
+                f,
+                "replay diverged from the recorded run at outcome {}",
+                measurement
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for QNumError {}
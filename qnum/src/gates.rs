@@ -1,6 +1,6 @@
 //! Unitary Arithmetic Gates for QNS (`qublis-qnum`)
 //!
-//! Provides quantum addition (`qadd`) and multiplication (`qmul`) operations
+//! Provides quantum addition (`qadd`, controlled `cqadd`) and multiplication (`qmul`) operations
 //! on `QNum` values, implemented as reversible, amplitude‐preserving transforms
 //! via enumeration of basis states and linear combination.
 //!
@@ -8,25 +8,100 @@
 //! you would implement dedicated reversible circuits rather than classical
 //! enumeration.
 
-use crate::{qnum::QNum, qid::Qid};
+use crate::{error::QNumError, qnum::QNum, qid::{Amplitude, Qid}};
 use num_complex::Complex;
-use ordered_float::OrderedFloat;
-use std::cmp;
-use std::collections::HashMap;
+use core::cmp;
+use alloc::collections::BTreeMap;
+use core::fmt;
+use alloc::{vec, vec::Vec};
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::Float;
+
+/// Default cap on the number of joint basis states a gate may enumerate.
+pub const DEFAULT_STATE_BUDGET: usize = 1 << 20;
+
+/// Errors returned by the budgeted gate variants.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GateError {
+    /// Enumerating the inputs would produce more joint basis states than allowed.
+    TooLarge {
+        /// Number of joint basis states the gate would need to visit.
+        states: usize,
+        /// Configured budget.
+        budget: usize,
+    },
+    /// The result is not a valid state, e.g. a digit's amplitudes cancelled
+    /// out to zero norm.
+    Invalid(QNumError),
+}
+
+impl From<QNumError> for GateError {
+    fn from(e: QNumError) -> Self {
+        GateError::Invalid(e)
+    }
+}
+
+impl fmt::Display for GateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GateError::TooLarge { states, budget } => write!(
+                f,
+                "gate would enumerate {} basis states, exceeding budget of {}",
+                states, budget
+            ),
+            GateError::Invalid(e) => write!(f, "gate produced an invalid state: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GateError {}
+
+/// Like [`qadd`], but returns `Err(GateError::TooLarge)` instead of enumerating
+/// more than `budget` joint basis states, and `Err(GateError::Invalid)` instead
+/// of panicking when a digit of the sum has zero norm.
+pub fn try_qadd<T: Amplitude>(a: &QNum<T>, b: &QNum<T>, budget: usize) -> Result<QNum<T>, GateError> {
+    check_budget(a, b, budget)?;
+    let radix = common_radix(a, b);
+    Ok(superpose(radix, sum_states(a, b, radix))?)
+}
+
+/// Like [`qmul`], but returns `Err(GateError::TooLarge)` instead of enumerating
+/// more than `budget` joint basis states, and `Err(GateError::Invalid)` instead
+/// of panicking when a digit of the product has zero norm.
+pub fn try_qmul<T: Amplitude>(a: &QNum<T>, b: &QNum<T>, budget: usize) -> Result<QNum<T>, GateError> {
+    check_budget(a, b, budget)?;
+    let radix = common_radix(a, b);
+    Ok(superpose(radix, product_states(a, b, radix))?)
+}
 
 /// Quantum addition: unitary superposition of all possible sums of `a + b`.
-pub fn qadd(a: &QNum, b: &QNum) -> QNum {
+///
+/// Carries are propagated in the shared radix of `a` and `b`. The result is
+/// renormalized with [`QNum::normalize`], so chained gates do not drift.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different radices, or if the branches of a
+/// digit of the sum cancel out to zero norm; see [`try_qadd`].
+pub fn qadd<T: Amplitude>(a: &QNum<T>, b: &QNum<T>) -> QNum<T> {
+    let radix = common_radix(a, b);
+    superpose(radix, sum_states(a, b, radix))
+        .unwrap_or_else(|e| panic!("invalid superposition: {}", e))
+}
+
+/// Amplitude of every classical sum `a + b` in `radix`.
+fn sum_states<T: Amplitude>(a: &QNum<T>, b: &QNum<T>, radix: usize) -> BTreeMap<Vec<u8>, Complex<f64>> {
     // Determine output length: one extra digit for possible final carry
     let out_len = cmp::max(a.len(), b.len()) + 1;
 
-    // Enumerate all classical states of a and b with their amplitudes
-    let a_states = enumerate_states(a, out_len);
-    let b_states = enumerate_states(b, out_len);
-
-    // Accumulate amplitude for each sum result
-    let mut sums: HashMap<Vec<u8>, Complex<f64>> = HashMap::new();
+    // Accumulate amplitude for each sum result, streaming over the
+    // classical states of a; b's are enumerated once and reused
+    let mut sums: BTreeMap<Vec<u8>, Complex<f64>> = BTreeMap::new();
 
-    for (adigits, a_amp) in &a_states {
+    let b_states: Vec<_> = enumerate(b).map(|s| pad_state(s, out_len)).collect();
+    for (adigits, a_amp) in enumerate(a).map(|s| pad_state(s, out_len)) {
         for (bdigits, b_amp) in &b_states {
             // Classical addition with carry
             let mut result = vec![0u8; out_len];
@@ -35,35 +110,138 @@ pub fn qadd(a: &QNum, b: &QNum) -> QNum {
                 let ai = adigits[i] as usize;
                 let bi = bdigits[i] as usize;
                 let s = ai + bi + carry;
-                result[i] = (s % 10) as u8;
-                carry = s / 10;
+                result[i] = (s % radix) as u8;
+                carry = s / radix;
             }
             // If desired, drop leading zero of carry at index 0:
             // but we keep fixed length for QNum::from_superposed.
 
             // Combined amplitude
-            let amp = *a_amp * *b_amp;
+            let amp = a_amp * b_amp;
+            *sums.entry(result).or_insert(Complex::new(0.0, 0.0)) += amp;
+        }
+    }
+    sums
+}
+
+/// Controlled quantum addition: `a + b` in branches where `control` is
+/// nonzero, `a` unchanged where it is zero.
+///
+/// Enumerates the joint basis states of all three operands; a control branch
+/// counts as nonzero if any of its digits is. Amplitudes of branches that land
+/// on the same result accumulate coherently; in zero-control branches `b` is
+/// not added, so it contributes only its total weight. The output has
+/// `max(a.len(), b.len()) + 1` digits in both cases, so "unchanged" branches
+/// are `a` zero-padded on the left, and it is renormalized with
+/// [`QNum::normalize`].
+///
+/// # Panics
+///
+/// Panics if `control`, `a` and `b` do not share a radix.
+pub fn cqadd<T: Amplitude>(control: &QNum<T>, a: &QNum<T>, b: &QNum<T>) -> QNum<T> {
+    let radix = common_radix(a, b);
+    assert_eq!(control.radix(), radix, "Gate operands must share a radix");
+    let out_len = cmp::max(a.len(), b.len()) + 1;
+
+    let a_states: Vec<_> = enumerate(a).map(|s| pad_state(s, out_len)).collect();
+    let b_states: Vec<_> = enumerate(b).map(|s| pad_state(s, out_len)).collect();
+    // Weight of `b` in branches that ignore it
+    let b_norm = b_states.iter().map(|(_, amp)| amp.norm_sqr()).sum::<f64>().sqrt();
+    let mut sums: BTreeMap<Vec<u8>, Complex<f64>> = BTreeMap::new();
+
+    for (cdigits, c_amp) in enumerate(control) {
+        let active = cdigits.iter().any(|&d| d != 0);
+        for (adigits, a_amp) in &a_states {
+            if !active {
+                // Control is |0…0⟩: pass `a` through unchanged
+                let amp = c_amp * a_amp * b_norm;
+                *sums.entry(adigits.clone()).or_insert(Complex::new(0.0, 0.0)) += amp;
+                continue;
+            }
+            for (bdigits, b_amp) in &b_states {
+                // Classical addition with carry
+                let mut result = vec![0u8; out_len];
+                let mut carry = 0;
+                for i in (0..out_len).rev() {
+                    let s = adigits[i] as usize + bdigits[i] as usize + carry;
+                    result[i] = (s % radix) as u8;
+                    carry = s / radix;
+                }
+
+                let amp = c_amp * a_amp * b_amp;
+                *sums.entry(result).or_insert(Complex::new(0.0, 0.0)) += amp;
+            }
+        }
+    }
+
+    let states: Vec<(Vec<u8>, Complex<f64>)> =
+        sums.into_iter().collect();
+    let mut out = QNum::from_superposed_in(radix, states);
+    out.normalize();
+    out.cast()
+}
+
+/// Modular quantum addition: superposition of `(a + b) mod radix^modulus_digits`.
+///
+/// Any carry out of the lowest `modulus_digits` digits is dropped, so the result
+/// has exactly `modulus_digits` digits. Branches that alias under the modulus
+/// have their amplitudes summed coherently, preserving interference.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different radices.
+pub fn qadd_mod<T: Amplitude>(a: &QNum<T>, b: &QNum<T>, modulus_digits: usize) -> QNum<T> {
+    let radix = common_radix(a, b);
+    let out_len = modulus_digits;
+
+    let mut sums: BTreeMap<Vec<u8>, Complex<f64>> = BTreeMap::new();
+
+    let b_states: Vec<_> = enumerate(b).map(|s| pad_state(s, out_len)).collect();
+    for (adigits, a_amp) in enumerate(a).map(|s| pad_state(s, out_len)) {
+        for (bdigits, b_amp) in &b_states {
+            // Classical addition; the final carry is discarded
+            let mut result = vec![0u8; out_len];
+            let mut carry = 0;
+            for i in (0..out_len).rev() {
+                let s = adigits[i] as usize + bdigits[i] as usize + carry;
+                result[i] = (s % radix) as u8;
+                carry = s / radix;
+            }
+
+            let amp = a_amp * b_amp;
             *sums.entry(result).or_insert(Complex::new(0.0, 0.0)) += amp;
         }
     }
 
-    // Build the resulting QNum superposition
     let states: Vec<(Vec<u8>, Complex<f64>)> =
         sums.into_iter().collect();
-    QNum::from_superposed(states)
+    QNum::from_superposed_in(radix, states).cast()
 }
 
 /// Quantum multiplication: unitary superposition of all possible products `a * b`.
-pub fn qmul(a: &QNum, b: &QNum) -> QNum {
+///
+/// Carries are propagated in the shared radix of `a` and `b`. The result is
+/// renormalized with [`QNum::normalize`].
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different radices, or if the branches of a
+/// digit of the product cancel out to zero norm; see [`try_qmul`].
+pub fn qmul<T: Amplitude>(a: &QNum<T>, b: &QNum<T>) -> QNum<T> {
+    let radix = common_radix(a, b);
+    superpose(radix, product_states(a, b, radix))
+        .unwrap_or_else(|e| panic!("invalid superposition: {}", e))
+}
+
+/// Amplitude of every classical product `a * b` in `radix`.
+fn product_states<T: Amplitude>(a: &QNum<T>, b: &QNum<T>, radix: usize) -> BTreeMap<Vec<u8>, Complex<f64>> {
     // Output length = sum of input lengths
     let out_len = a.len() + b.len();
 
-    let a_states = enumerate_states(a, out_len);
-    let b_states = enumerate_states(b, out_len);
-
-    let mut prods: HashMap<Vec<u8>, Complex<f64>> = HashMap::new();
+    let mut prods: BTreeMap<Vec<u8>, Complex<f64>> = BTreeMap::new();
 
-    for (adigits, a_amp) in &a_states {
+    let b_states: Vec<_> = enumerate(b).map(|s| pad_state(s, out_len)).collect();
+    for (adigits, a_amp) in enumerate(a).map(|s| pad_state(s, out_len)) {
         for (bdigits, b_amp) in &b_states {
             // Classical multiplication
             let mut result = vec![0u8; out_len];
@@ -80,26 +258,470 @@ pub fn qmul(a: &QNum, b: &QNum) -> QNum {
             let mut carry = 0;
             for k in (0..out_len).rev() {
                 let sum = tmp[k] + carry;
-                result[k] = (sum % 10) as u8;
-                carry = sum / 10;
+                result[k] = (sum % radix) as u8;
+                carry = sum / radix;
             }
             // ignore overflow beyond out_len
 
-            let amp = *a_amp * *b_amp;
+            let amp = a_amp * b_amp;
             *prods.entry(result).or_insert(Complex::new(0.0, 0.0)) += amp;
         }
     }
+    prods
+}
+
+/// Build the renormalized result of a gate from its accumulated basis states.
+fn superpose<T: Amplitude>(radix: usize, states: BTreeMap<Vec<u8>, Complex<f64>>) -> Result<QNum<T>, QNumError> {
+    let mut out = QNum::try_from_superposed_in(radix, states.into_iter().collect())?;
+    out.normalize();
+    Ok(out.cast())
+}
+
+/// Quantum subtraction: unitary superposition of all possible differences `a - b`.
+///
+/// Borrows are propagated in the shared radix of `a` and `b`. Branches where
+/// `b > a` would go negative and are **clamped to zero**; their amplitude is
+/// accumulated onto the all-zero basis state rather than wrapping around.
+/// The output has `max(a.len(), b.len())` digits.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different radices.
+pub fn qsub<T: Amplitude>(a: &QNum<T>, b: &QNum<T>) -> QNum<T> {
+    let radix = common_radix(a, b);
+    let out_len = cmp::max(a.len(), b.len());
+
+    let mut diffs: BTreeMap<Vec<u8>, Complex<f64>> = BTreeMap::new();
+
+    let b_states: Vec<_> = enumerate(b).map(|s| pad_state(s, out_len)).collect();
+    for (adigits, a_amp) in enumerate(a).map(|s| pad_state(s, out_len)) {
+        for (bdigits, b_amp) in &b_states {
+            // Classical subtraction with borrow
+            let mut result = vec![0u8; out_len];
+            let mut borrow = 0;
+            for i in (0..out_len).rev() {
+                let ai = adigits[i] as usize;
+                let bi = bdigits[i] as usize + borrow;
+                if ai >= bi {
+                    result[i] = (ai - bi) as u8;
+                    borrow = 0;
+                } else {
+                    result[i] = (ai + radix - bi) as u8;
+                    borrow = 1;
+                }
+            }
+            // Negative result: clamp to zero
+            if borrow > 0 {
+                result = vec![0u8; out_len];
+            }
+
+            let amp = a_amp * b_amp;
+            *diffs.entry(result).or_insert(Complex::new(0.0, 0.0)) += amp;
+        }
+    }
 
     let states: Vec<(Vec<u8>, Complex<f64>)> =
-        prods.into_iter().collect();
-    QNum::from_superposed(states)
+        diffs.into_iter().collect();
+    QNum::from_superposed_in(radix, states).cast()
+}
+
+/// Signed quantum subtraction in radix complement (ten's complement for
+/// decimal): superposition of `(a - b) mod radix^width`.
+///
+/// Operands are fixed-width signed values as built by [`QNum::from_i64`]: a
+/// leading digit of at least `radix / 2` marks a negative number. The final
+/// borrow is dropped instead of clamping, so negative differences wrap to their
+/// complement (e.g. `03 - 07 = 96`, read back as `-4` by [`QNum::to_i64`]).
+/// Differences outside the width's signed range wrap around. The result has
+/// the operands' width and is renormalized with [`QNum::normalize`].
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different radices or different widths; sign
+/// extension is not applied, so both operands must share one width.
+pub fn qsub_signed<T: Amplitude>(a: &QNum<T>, b: &QNum<T>) -> QNum<T> {
+    let radix = common_radix(a, b);
+    assert_eq!(
+        a.len(),
+        b.len(),
+        "Signed subtraction requires QNums of the same width"
+    );
+    let out_len = a.len();
+
+    let mut diffs: BTreeMap<Vec<u8>, Complex<f64>> = BTreeMap::new();
+
+    let b_states: Vec<_> = enumerate(b).collect();
+    for (adigits, a_amp) in enumerate(a) {
+        for (bdigits, b_amp) in &b_states {
+            // Classical subtraction; the final borrow is discarded
+            let mut result = vec![0u8; out_len];
+            let mut borrow = 0;
+            for i in (0..out_len).rev() {
+                let d = adigits[i] as usize + radix - bdigits[i] as usize - borrow;
+                result[i] = (d % radix) as u8;
+                borrow = 1 - d / radix;
+            }
+
+            let amp = a_amp * b_amp;
+            *diffs.entry(result).or_insert(Complex::new(0.0, 0.0)) += amp;
+        }
+    }
+
+    let states: Vec<(Vec<u8>, Complex<f64>)> =
+        diffs.into_iter().collect();
+    let mut out = QNum::from_superposed_in(radix, states);
+    out.normalize();
+    out.cast()
+}
+
+/// Quantum division: superpositions of the quotient and remainder of `a / b`.
+///
+/// Returns `(quotient, remainder)`, each with `a.len()` digits. Branches where
+/// `b` is zero are defined as quotient `0` and remainder `a`. Each branch's
+/// operands are evaluated as integers, so `a` and `b` must fit in a `u128`.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different radices, or an operand overflows `u128`.
+pub fn qdiv<T: Amplitude>(a: &QNum<T>, b: &QNum<T>) -> (QNum<T>, QNum<T>) {
+    let radix = common_radix(a, b);
+    let out_len = a.len();
+
+    let mut quots: BTreeMap<Vec<u8>, Complex<f64>> = BTreeMap::new();
+    let mut rems: BTreeMap<Vec<u8>, Complex<f64>> = BTreeMap::new();
+
+    let b_states: Vec<_> = enumerate(b).collect();
+    for (adigits, a_amp) in enumerate(a) {
+        let av = to_value(&adigits, radix);
+        for (bdigits, b_amp) in &b_states {
+            let bv = to_value(bdigits, radix);
+            let (q, r) = av.checked_div(bv).zip(av.checked_rem(bv)).unwrap_or((0, av));
+
+            let amp = a_amp * b_amp;
+            *quots.entry(from_value(q, out_len, radix)).or_insert(Complex::new(0.0, 0.0)) += amp;
+            *rems.entry(from_value(r, out_len, radix)).or_insert(Complex::new(0.0, 0.0)) += amp;
+        }
+    }
+
+    (
+        QNum::from_superposed_in(radix, quots.into_iter().collect()).cast(),
+        QNum::from_superposed_in(radix, rems.into_iter().collect()).cast(),
+    )
+}
+
+/// Quantum comparison: a single-digit `QNum` whose `|1⟩` branch carries the
+/// probability that `a > b`, and whose `|0⟩` branch carries the rest.
+///
+/// Every pair of basis states `(x, y)` contributes `|⟨x|a⟩·⟨y|b⟩|²` to `|1⟩` if
+/// `x > y` (compared as integers, shorter operands zero-padded) and to `|0⟩`
+/// otherwise; the output amplitudes are the square roots of those totals, so
+/// measuring the result samples the comparison without collapsing `a` or `b`.
+/// The digit shares the operands' radix.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` have different radices.
+pub fn qcmp<T: Amplitude>(a: &QNum<T>, b: &QNum<T>) -> QNum<T> {
+    let radix = common_radix(a, b);
+    let width = cmp::max(a.len(), b.len());
+
+    let mut greater = 0.0;
+    let mut not_greater = 0.0;
+    let b_states: Vec<_> = enumerate(b).map(|s| pad_state(s, width)).collect();
+    for (adigits, a_amp) in enumerate(a).map(|s| pad_state(s, width)) {
+        for (bdigits, b_amp) in &b_states {
+            // MSB-first digit vectors of equal length compare like integers
+            let p = (a_amp * b_amp).norm_sqr();
+            if &adigits > bdigits {
+                greater += p;
+            } else {
+                not_greater += p;
+            }
+        }
+    }
+
+    let mut amps = vec![Complex::new(0.0, 0.0); radix];
+    amps[0] = Complex::new(not_greater.sqrt(), 0.0);
+    amps[1] = Complex::new(greater.sqrt(), 0.0);
+    let mut out = Qid::from_f64_vec(amps);
+    out.normalize();
+    QNum::new(vec![out]).cast()
+}
+
+/// Quantum Fourier transform of the whole register, over its `N = Rⁿ`
+/// basis values read as base-`R` integers `x` (MSB first):
+/// ```text
+/// |x⟩ ↦ (1/√N) Σ_y e^{2πi·xy/N} |y⟩
+/// ```
+///
+/// This is a classical enumeration like [`qadd`]: every one of the `N`
+/// output amplitudes is summed over the input's basis states, so cost is
+/// exponential in the register length.
+///
+/// A `QNum` holds a product state, and the transform of a definite state is
+/// one, so a definite input is transformed exactly and [`iqft`] inverts it.
+/// The transform of a superposed register is generally entangled; the result
+/// then keeps the transformed state's slice through its most likely basis
+/// value along each digit, which is exact whenever the output is a product
+/// state. Use [`qft_digits`] for a transform that is lossless on every input.
+///
+/// # Panics
+/// If the register is too wide for `Rⁿ` to fit in a `usize`.
+pub fn qft<T: Amplitude>(q: &QNum<T>) -> QNum<T> {
+    register_fourier(q, 1.0)
+}
+
+/// Inverse of [`qft`]: `|y⟩ ↦ (1/√N) Σ_x e^{−2πi·xy/N} |x⟩` over the whole
+/// register.
+pub fn iqft<T: Amplitude>(q: &QNum<T>) -> QNum<T> {
+    register_fourier(q, -1.0)
+}
+
+/// Shared body of [`qft`] (`sign = 1`) and [`iqft`] (`sign = -1`).
+fn register_fourier<T: Amplitude>(q: &QNum<T>, sign: f64) -> QNum<T> {
+    let radix = q.radix();
+    let len = q.len();
+    let n = (0..len)
+        .try_fold(1usize, |acc, _| acc.checked_mul(radix))
+        .expect("register too wide for a register-wide QFT");
+    let scale = 1.0 / (n as f64).sqrt();
+
+    let input: Vec<(usize, Complex<f64>)> = enumerate(q)
+        .map(|(digits, amp)| (digits.iter().fold(0, |x, &d| x * radix + d as usize), amp))
+        .collect();
+    let output: Vec<Complex<f64>> = (0..n)
+        .map(|y| {
+            let sum: Complex<f64> = input
+                .iter()
+                .map(|&(x, a)| {
+                    // reduce x·y mod N in u128 so the phase stays exact for wide registers
+                    let turns = ((x as u128 * y as u128) % n as u128) as f64 / n as f64;
+                    a * Complex::from_polar(1.0, sign * 2.0 * core::f64::consts::PI * turns)
+                })
+                .sum();
+            sum * scale
+        })
+        .collect();
+
+    // Factor the output along its most likely basis value: digit k takes the
+    // amplitudes of the states that differ from the peak only in digit k.
+    let peak = (0..n)
+        .max_by(|&a, &b| output[a].norm_sqr().total_cmp(&output[b].norm_sqr()).then(b.cmp(&a)))
+        .unwrap_or(0);
+    if output[peak].norm_sqr() == 0.0 {
+        // only a zero-norm input transforms to the zero vector
+        return q.clone();
+    }
+    let place_of = |k: usize| radix.pow((len - 1 - k) as u32);
+    let mut factors: Vec<Vec<Complex<f64>>> = (0..len)
+        .map(|k| {
+            let place = place_of(k);
+            let base = peak - (peak / place % radix) * place;
+            let slice: Vec<Complex<f64>> = (0..radix).map(|v| output[base + v * place]).collect();
+            let norm = slice.iter().map(|c| c.norm_sqr()).sum::<f64>().sqrt();
+            slice.into_iter().map(|c| c / norm).collect()
+        })
+        .collect();
+
+    // Match the output's phase at the peak, so product-state results are exact.
+    let product: Complex<f64> = factors
+        .iter()
+        .enumerate()
+        .map(|(k, f)| f[peak / place_of(k) % radix])
+        .product();
+    let rotation = Complex::from_polar(1.0, output[peak].arg() - product.arg());
+    if let Some(first) = factors.first_mut() {
+        first.iter_mut().for_each(|c| *c *= rotation);
+    }
+    let qids = factors.into_iter().map(Qid::from_f64_vec).collect();
+    QNum::new(qids).cast()
+}
+
+/// Quantum Fourier transform of every digit on its own: each `Qid` of radix
+/// `R` is mapped through the `R`-point DFT,
+/// ```text
+/// |x⟩ ↦ (1/√R) Σ_y e^{2πi·xy/R} |y⟩
+/// ```
+/// (the unitary of [`Qid::hadamard`]). Unlike the register-wide [`qft`],
+/// this maps product states to product states exactly, so [`iqft_digits`]
+/// inverts it for any input. Cost is `O(n·R²)`.
+pub fn qft_digits<T: Amplitude>(q: &QNum<T>) -> QNum<T> {
+    fourier(q, 1.0)
+}
+
+/// Inverse of [`qft_digits`]: `|y⟩ ↦ (1/√R) Σ_x e^{−2πi·xy/R} |x⟩` on every digit.
+pub fn iqft_digits<T: Amplitude>(q: &QNum<T>) -> QNum<T> {
+    fourier(q, -1.0)
+}
+
+/// Shared body of [`qft_digits`] (`sign = 1`) and [`iqft_digits`] (`sign = -1`).
+fn fourier<T: Amplitude>(q: &QNum<T>, sign: f64) -> QNum<T> {
+    let qids = q
+        .0
+        .iter()
+        .map(|qid| {
+            let r = qid.radix();
+            let scale = 1.0 / (r as f64).sqrt();
+            let input: Vec<Complex<f64>> = qid
+                .amps
+                .iter()
+                .map(|c| Complex::new(c.re.into_inner().widen(), c.im.into_inner().widen()))
+                .collect();
+            let out = (0..r)
+                .map(|y| {
+                    let sum: Complex<f64> = input
+                        .iter()
+                        .enumerate()
+                        .map(|(x, a)| {
+                            let turns = ((x * y) % r) as f64 / r as f64;
+                            a * Complex::from_polar(1.0, sign * 2.0 * core::f64::consts::PI * turns)
+                        })
+                        .sum();
+                    sum * scale
+                })
+                .collect();
+            Qid::from_f64_vec(out)
+        })
+        .collect();
+    QNum::new(qids).cast()
+}
+
+/// Lazily enumerate the classical basis states of `q` with non-zero amplitude,
+/// yielding `(digits, amplitude)` pairs (MSB first, last digit varying fastest).
+///
+/// Unlike collecting every state up front, only one digit vector is held at a time.
+pub fn enumerate<T: Amplitude>(q: &QNum<T>) -> impl Iterator<Item = (Vec<u8>, Complex<f64>)> {
+    let supports: Vec<Vec<(u8, Complex<f64>)>> = q
+        .0
+        .iter()
+        .map(|qid| {
+            qid.amps
+                .iter()
+                .enumerate()
+                .filter(|(_, alpha)| alpha.norm_sqr().into_inner() > T::zero())
+                .map(|(digit, alpha)| {
+                    (digit as u8, Complex::new(alpha.re.into_inner().widen(), alpha.im.into_inner().widen()))
+                })
+                .collect()
+        })
+        .collect();
+    let done = supports.iter().any(|s| s.is_empty());
+    StateIter {
+        cursor: vec![0; supports.len()],
+        supports,
+        done,
+    }
+}
+
+/// Odometer-style iterator backing [`enumerate`].
+struct StateIter {
+    supports: Vec<Vec<(u8, Complex<f64>)>>,
+    cursor: Vec<usize>,
+    done: bool,
+}
+
+impl Iterator for StateIter {
+    type Item = (Vec<u8>, Complex<f64>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let mut digits = Vec::with_capacity(self.supports.len());
+        let mut amp = Complex::new(1.0, 0.0);
+        for (support, &k) in self.supports.iter().zip(&self.cursor) {
+            let (digit, alpha) = support[k];
+            digits.push(digit);
+            amp *= alpha;
+        }
+
+        // Advance the cursor, least-significant digit first
+        self.done = true;
+        for pos in (0..self.cursor.len()).rev() {
+            self.cursor[pos] += 1;
+            if self.cursor[pos] < self.supports[pos].len() {
+                self.done = false;
+                break;
+            }
+            self.cursor[pos] = 0;
+        }
+
+        Some((digits, amp))
+    }
 }
 
 // === Internal Helpers ===
 
+/// Radix shared by both gate operands.
+fn common_radix<T: Amplitude>(a: &QNum<T>, b: &QNum<T>) -> usize {
+    assert_eq!(a.radix(), b.radix(), "Gate operands must share a radix");
+    a.radix()
+}
+
+/// Pad a state's digit vector on the left (MSB) with zeros, or truncate its
+/// most-significant digits, so it has exactly `out_len` digits.
+fn pad_state((mut digits, amp): (Vec<u8>, Complex<f64>), out_len: usize) -> (Vec<u8>, Complex<f64>) {
+    if digits.len() < out_len {
+        let mut pad = vec![0u8; out_len - digits.len()];
+        pad.append(&mut digits);
+        (pad, amp)
+    } else if digits.len() > out_len {
+        // Truncate most-significant digits if too long
+        let start = digits.len() - out_len;
+        (digits[start..].to_vec(), amp)
+    } else {
+        (digits, amp)
+    }
+}
+
+/// Fold a digit vector (MSB first) into an integer in the given radix.
+fn to_value(digits: &[u8], radix: usize) -> u128 {
+    digits.iter().fold(0u128, |acc, &d| {
+        acc.checked_mul(radix as u128)
+            .and_then(|v| v.checked_add(d as u128))
+            .expect("QNum operand overflows u128")
+    })
+}
+
+/// Encode `value` as exactly `len` digits (MSB first) in the given radix,
+/// dropping any higher-order digits.
+fn from_value(mut value: u128, len: usize, radix: usize) -> Vec<u8> {
+    let mut digits = vec![0u8; len];
+    for d in digits.iter_mut().rev() {
+        *d = (value % radix as u128) as u8;
+        value /= radix as u128;
+    }
+    digits
+}
+
+/// Number of classical basis states with non-zero amplitude in `q`,
+/// saturating at `usize::MAX`.
+fn state_count<T: Amplitude>(q: &QNum<T>) -> usize {
+    q.0.iter().fold(1usize, |acc, qid| {
+        let support = qid
+            .amps
+            .iter()
+            .filter(|alpha| alpha.norm_sqr().into_inner() > T::zero())
+            .count();
+        acc.saturating_mul(support)
+    })
+}
+
+/// Reject gate inputs whose joint enumeration would exceed `budget` states.
+fn check_budget<T: Amplitude>(a: &QNum<T>, b: &QNum<T>, budget: usize) -> Result<(), GateError> {
+    let states = state_count(a).saturating_mul(state_count(b));
+    if states > budget {
+        return Err(GateError::TooLarge { states, budget });
+    }
+    Ok(())
+}
+
 /// Enumerate all classical basis states of a `QNum` padded/truncated to `out_len`,
 /// returning pairs of (digit‐vector of length `out_len`, amplitude).
-fn enumerate_states(q: &QNum, out_len: usize) -> Vec<(Vec<u8>, Complex<f64>)> {
+///
+/// Eager reference implementation kept to cross-check [`enumerate`].
+#[cfg(all(test, feature = "std"))]
+fn enumerate_states<T: Amplitude>(q: &QNum<T>, out_len: usize) -> Vec<(Vec<u8>, Complex<f64>)> {
     // Start with a single empty prefix and amplitude 1
     let mut states: Vec<(Vec<u8>, Complex<f64>)> = vec![(Vec::new(), Complex::new(1.0, 0.0))];
 
@@ -108,12 +730,11 @@ fn enumerate_states(q: &QNum, out_len: usize) -> Vec<(Vec<u8>, Complex<f64>)> {
         let mut next = Vec::new();
         for (prefix, amp) in &states {
             for (digit, alpha) in qid.amps.iter().enumerate() {
-                let p = alpha.norm_sqr();
-                if p > OrderedFloat(0.0) {
+                if alpha.norm_sqr().into_inner() > T::zero() {
                     let mut new_prefix = prefix.clone();
                     new_prefix.push(digit as u8);
-                    // Convert alpha (Complex<OrderedFloat<f64>>) to Complex<f64> for multiplication
-                    let alpha_f64 = Complex::new(alpha.re.into_inner(), alpha.im.into_inner());
+                    // Widen alpha (Complex<OrderedFloat<T>>) to Complex<f64> for multiplication
+                    let alpha_f64 = Complex::new(alpha.re.into_inner().widen(), alpha.im.into_inner().widen());
                     next.push((new_prefix, *amp * alpha_f64));
                 }
             }
@@ -139,3 +760,227 @@ fn enumerate_states(q: &QNum, out_len: usize) -> Vec<(Vec<u8>, Complex<f64>)> {
         })
         .collect()
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    /// A uniform superposition over all ten values of every digit.
+    fn uniform(len: usize) -> QNum {
+        let amp = Complex::new(1.0 / 10f64.sqrt(), 0.0);
+        let mut raw = [Complex::new(0.0, 0.0); 10];
+        raw.iter_mut().for_each(|c| *c = amp);
+        QNum::new(vec![Qid::from_f64(raw); len])
+    }
+
+    #[test]
+    fn cqadd_adds_only_under_nonzero_control() {
+        let h = Complex::new(1.0 / 2f64.sqrt(), 0.0);
+        let control = QNum::from_superposed(vec![(vec![0], h), (vec![1], h)]);
+        let a = QNum::from_digits(&[2]);
+        let b = QNum::from_digits(&[3]);
+
+        // (|0⟩ + |1⟩)/√2 control: "unchanged a" |02⟩ and "a + b" |05⟩, equally weighted
+        let out = cqadd(&control, &a, &b);
+        assert_eq!(out.len(), 2);
+        assert_eq!(out.amplitude(0, 0), Some(Complex::new(1.0, 0.0)));
+        for digit in [2, 5] {
+            assert!((out.amplitude(1, digit).unwrap() - h).norm() < 1e-12);
+        }
+
+        assert_eq!(cqadd(&QNum::from_digits(&[0]), &a, &b), QNum::from_digits(&[0, 2]));
+        assert_eq!(cqadd(&QNum::from_digits(&[7]), &a, &b), qadd(&a, &b));
+    }
+
+    #[test]
+    fn qsub_signed_wraps_to_complement() {
+        let a = QNum::from_i64(3, 2);
+        let b = QNum::from_i64(7, 2);
+        let diff = qsub_signed(&a, &b);
+        assert_eq!(diff, QNum::from_digits(&[9, 6]));
+        assert_eq!(diff.to_i64(), Some(-4));
+        // unsigned qsub still clamps
+        assert_eq!(qsub(&a, &b), QNum::from_digits(&[0, 0]));
+
+        let diff = qsub_signed(&QNum::from_i64(-12, 3), &QNum::from_i64(-30, 3));
+        assert_eq!(diff.to_i64(), Some(18));
+    }
+
+    #[test]
+    fn try_qadd_classical_within_budget() {
+        let a = QNum::from_digits(&[1, 2]);
+        let b = QNum::from_digits(&[0, 7]);
+        let mut sum = try_qadd(&a, &b, 1).unwrap();
+        assert_eq!(sum.measure(), vec![0, 1, 9]);
+    }
+
+    /// (|1⟩ − |2⟩)/√2 + |1⟩ has a tens digit whose branches cancel; the
+    /// budgeted gate reports it instead of returning an unmeasurable QNum.
+    #[test]
+    fn try_qadd_rejects_zero_norm_digit() {
+        let h = Complex::new(1.0 / 2f64.sqrt(), 0.0);
+        let x = QNum::from_superposed(vec![(vec![1], h), (vec![2], -h)]);
+        let y = QNum::from_digits(&[1]);
+        assert_eq!(
+            try_qadd(&x, &y, DEFAULT_STATE_BUDGET),
+            Err(GateError::Invalid(QNumError::ZeroNorm { index: 0 }))
+        );
+    }
+
+    #[test]
+    fn qadd_carries_in_operand_radix() {
+        // 0b011 + 0b001 = 0b0100
+        let a = QNum::from_digits_in(2, &[0, 1, 1]);
+        let b = QNum::from_digits_in(2, &[0, 0, 1]);
+        let mut sum = qadd(&a, &b);
+        assert_eq!(sum.radix(), 2);
+        assert_eq!(sum.measure(), vec![0, 1, 0, 0]);
+
+        // 0xf + 0x3 = 0x12
+        let a = QNum::from_digits_in(16, &[0xf]);
+        let b = QNum::from_digits_in(16, &[0x3]);
+        assert_eq!(qadd(&a, &b).measure(), vec![0x1, 0x2]);
+    }
+
+    #[test]
+    fn chained_qadd_stays_normalized() {
+        let h = Complex::new(1.0 / 2f64.sqrt(), 0.0);
+        let mut acc = QNum::from_superposed(vec![(vec![1], h), (vec![2], h)]);
+        let one = QNum::from_digits(&[1]);
+        for _ in 0..50 {
+            acc = qadd(&acc, &one);
+        }
+        let total: f64 = enumerate(&acc).map(|(_, amp)| amp.norm_sqr()).sum();
+        assert!((total - 1.0).abs() < 1e-9, "probabilities sum to {}", total);
+    }
+
+    #[test]
+    fn gates_run_on_f32_registers() {
+        let h = Complex::new(1.0 / 2f64.sqrt(), 0.0);
+        let a: QNum<f32> = QNum::from_superposed(vec![(vec![1, 2], h), (vec![3, 4], h)]).cast();
+        let b: QNum<f32> = QNum::from_digits(&[0, 7]).cast();
+
+        let sum = qadd(&a, &b);
+        let expected = qadd(&a.cast::<f64>(), &b.cast::<f64>());
+        assert_eq!(sum.len(), 3);
+        assert!(sum.cast::<f64>().fidelity(&expected) > 1.0 - 1e-6);
+        assert!((sum.entropy() - expected.entropy()).abs() < 1e-5);
+
+        let (q, r) = qdiv(&QNum::from_digits(&[1, 9]).cast::<f32>(), &b);
+        assert_eq!(q.cast::<f64>(), QNum::from_digits(&[0, 2]));
+        assert_eq!(r.cast::<f64>(), QNum::from_digits(&[0, 5]));
+        assert!(iqft_digits(&qft_digits(&a)).cast::<f64>().fidelity(&a.cast()) > 1.0 - 1e-6);
+    }
+
+    #[test]
+    fn qft_of_definite_state_has_uniform_magnitude() {
+        let f = qft(&QNum::from_digits(&[3, 7]));
+        assert_eq!(f.len(), 2);
+        for qid in &f.0 {
+            for c in &qid.amps {
+                assert!((c.norm_sqr().into_inner() - 0.1).abs() < 1e-12);
+            }
+        }
+        // the uniform register transforms back to |00⟩
+        assert!(qft(&uniform(2)).fidelity(&QNum::from_digits(&[0, 0])) > 0.999);
+    }
+
+    #[test]
+    fn iqft_inverts_qft() {
+        for digits in [vec![3, 7], vec![0, 0], vec![9, 1]] {
+            let q = QNum::from_digits(&digits);
+            assert!(iqft(&qft(&q)).fidelity(&q) > 0.999, "round trip of {:?}", digits);
+        }
+        let binary = QNum::from_digits_in(2, &[1, 0, 1]);
+        assert!(iqft(&qft(&binary)).fidelity(&binary) > 0.999);
+    }
+
+    /// Over N = 4, |01⟩ = |1⟩ maps to ½ Σ_y iʸ|y⟩ = (|0⟩ − |1⟩)/√2 ⊗ (|0⟩ + i|1⟩)/√2.
+    #[test]
+    fn qft_matches_hand_computed_two_digit_register() {
+        let f = qft(&QNum::from_digits_in(2, &[0, 1]));
+        let h = 1.0 / 2f64.sqrt();
+        let expected = [
+            [Complex::new(h, 0.0), Complex::new(-h, 0.0)],
+            [Complex::new(h, 0.0), Complex::new(0.0, h)],
+        ];
+        for (k, digit) in expected.iter().enumerate() {
+            for (v, want) in digit.iter().enumerate() {
+                let got = f.amplitude(k, v).unwrap();
+                assert!((got - want).norm() < 1e-12, "digit {} value {}: {}", k, v, got);
+            }
+        }
+        assert!(iqft(&f).fidelity(&QNum::from_digits_in(2, &[0, 1])) > 1.0 - 1e-12);
+    }
+
+    #[test]
+    fn iqft_digits_inverts_qft_digits_of_superposed_register() {
+        let h = Complex::new(1.0 / 2f64.sqrt(), 0.0);
+        let q = QNum::from_superposed(vec![(vec![0, 0], h), (vec![0, 1], h)]);
+        assert!(iqft_digits(&qft_digits(&q)).fidelity(&q) > 0.999);
+
+        let phased = QNum::from_superposed(vec![
+            (vec![2, 5, 1], h),
+            (vec![7, 5, 3], Complex::new(0.0, 1.0 / 2f64.sqrt())),
+        ]);
+        assert!(iqft_digits(&qft_digits(&phased)).fidelity(&phased) > 0.999);
+        assert!(qft_digits(&iqft_digits(&phased)).fidelity(&phased) > 0.999);
+    }
+
+    #[test]
+    fn qadd_mod_wraps_overflow() {
+        // 95 + 15 = 110 ≡ 10 (mod 100)
+        let a = QNum::from_digits(&[9, 5]);
+        let b = QNum::from_digits(&[1, 5]);
+        let sum = qadd_mod(&a, &b, 2);
+        assert_eq!(sum.len(), 2);
+        assert_eq!(sum.amplitude(0, 1), Some(Complex::new(1.0, 0.0)));
+        assert_eq!(sum.amplitude(1, 0), Some(Complex::new(1.0, 0.0)));
+    }
+
+    #[test]
+    fn qcmp_probability_matches_pair_fraction() {
+        // a ∈ {2, 7}, b ∈ {1, 3, 5}: a > b in 4 of the 6 equally likely pairs
+        let a = QNum::from_superposed(vec![
+            (vec![2], Complex::new(1.0 / 2f64.sqrt(), 0.0)),
+            (vec![7], Complex::new(1.0 / 2f64.sqrt(), 0.0)),
+        ]);
+        let third = Complex::new(1.0 / 3f64.sqrt(), 0.0);
+        let b = QNum::from_superposed(vec![(vec![1], third), (vec![3], third), (vec![5], third)]);
+
+        let cmp = qcmp(&a, &b);
+        assert_eq!(cmp.len(), 1);
+        let p_greater = cmp.amplitude(0, 1).unwrap().norm_sqr();
+        assert!((p_greater - 4.0 / 6.0).abs() < 1e-12);
+
+        // zero-padding compares across lengths; equality is not "greater"
+        assert_eq!(qcmp(&QNum::from_digits(&[1, 0]), &QNum::from_digits(&[9])).measure(), vec![1]);
+        assert_eq!(qcmp(&QNum::from_digits(&[4]), &QNum::from_digits(&[4])).measure(), vec![0]);
+    }
+
+    #[test]
+    fn enumerate_matches_enumerate_states() {
+        let amp = Complex::new(0.5, 0.0);
+        let q = QNum::from_superposed(vec![
+            (vec![1, 2, 0], amp),
+            (vec![3, 4, 0], amp),
+            (vec![1, 4, 9], amp),
+            (vec![3, 2, 9], amp),
+        ]);
+        let streamed: Vec<_> = enumerate(&q).collect();
+        let eager = enumerate_states(&q, q.len());
+        assert_eq!(streamed, eager);
+        assert_eq!(streamed.len(), 8);
+    }
+
+    #[test]
+    fn wide_superposed_qmul_hits_budget() {
+        let a = uniform(4);
+        let b = uniform(4);
+        let err = try_qmul(&a, &b, DEFAULT_STATE_BUDGET).unwrap_err();
+        assert_eq!(
+            err,
+            GateError::TooLarge { states: 100_000_000, budget: DEFAULT_STATE_BUDGET }
+        );
+    }
+}
@@ -7,28 +7,61 @@
 //! (`qadd`, `qmul`), entangle multiple QNums, and measure/collapse them to classical values.  
 //!  
 //! For a full specification and usage examples, see [`docs/qnum_spec.md`].  
+//!
+//! # `no_std`
+//!
+//! With default features off (no `std`), the crate is `no_std + alloc`, for
+//! embedded validators. Float math then goes through `libm`, and the
+//! conveniences that draw from the thread-local RNG (`Qid::measure`,
+//! `QNum::measure`, `measure::measure_all`, …) are unavailable: use the `_with`
+//! variants, which take the RNG as an argument.
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
 
+extern crate alloc;
+
 /// Quantum Digit primitives.
 pub mod qid;
 /// Place-value Quantum Numbers.
 pub mod qnum;
-/// Unitary arithmetic gates (addition, multiplication).
+/// Unitary arithmetic gates (addition, subtraction, multiplication, division).
 pub mod gates;
 /// Entanglement utilities for linking QNums.
 pub mod entangle;
 /// Measurement and collapse operators.
 pub mod measure;
+/// Error type for fallible operations.
+pub mod error;
+/// Test helpers shared by downstream crates.
+#[cfg(feature = "std")]
+pub mod testing;
 
-pub use qid::Qid;
-pub use qnum::QNum;
-pub use gates::{qadd, qmul};
-pub use entangle::entangle;
-pub use measure::{measure, measure_qid};
+pub use qid::{
+    Amplitude, Dirac, Qid, CLASSICAL_TOLERANCE, DEFAULT_RADIX, DISPLAY_THRESHOLD,
+    PHASE_REFERENCE_THRESHOLD,
+};
+pub use qnum::{DiracQNum, Lineage, QNum, QNumPatch, Traced, CANONICAL_GRID};
+pub use gates::{
+    cqadd, enumerate, iqft, iqft_digits, qadd, qadd_mod, qcmp, qdiv, qft, qft_digits, qmul, qsub,
+    qsub_signed, try_qadd, try_qmul, GateError,
+};
+pub use entangle::{
+    entangle, entangle_all, entangle_all_traced, entangle_traced, entangle_weighted, entangle_with_link,
+    joint_distribution, partial_trace, EntanglementLink, JointState,
+};
+pub use error::QNumError;
+pub use measure::{
+    assert_outcomes_seen, measure_all_with, measure_digit_in_with, seeded_measure, seeded_measure_verify, vrf, vrf_verify,
+    CollapsePolicy, Measurer, Proof, Recorder, Replayer, VrfProof, VrfPublicKey, VrfSecretKey,
+};
+#[cfg(feature = "std")]
+pub use measure::{
+    measure, measure_all, measure_digit_in, measure_qid, measure_qid_with_floor, measure_with_floor,
+};
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use num_complex::Complex;
@@ -36,7 +69,7 @@ mod tests {
     /// Basic sanity check: a Qid created from a classical 3 measures to 3.
     #[test]
     fn qid_classical_measure() {
-        let mut q = Qid::from_classical(3);
+        let q = Qid::definite(3);
         let d = q.measure();
         assert_eq!(d, 3);
     }
@@ -47,17 +80,43 @@ mod tests {
         let a = QNum::from_digits(&[1, 2]); // 12
         let b = QNum::from_digits(&[0, 7]); //  7
         let mut sum = qadd(&a, &b);
-        assert_eq!(sum.measure(), vec![1, 9]); // 19
+        assert_eq!(sum.measure(), vec![0, 1, 9]); // 19, widened by a carry digit
+    }
+
+    /// QSub of two classical QNums yields the expected classical difference.
+    #[test]
+    fn qsub_classical_difference() {
+        let a = QNum::from_digits(&[1, 9]); // 19
+        let b = QNum::from_digits(&[0, 7]); //  7
+        let mut diff = qsub(&a, &b);
+        assert_eq!(diff.measure(), vec![1, 2]); // 12
     }
 
-    /// Entangling two QNums yields correlated collapse.
+    /// QSub clamps negative differences to zero.
+    #[test]
+    fn qsub_negative_clamps_to_zero() {
+        let a = QNum::from_digits(&[0, 7]);
+        let b = QNum::from_digits(&[1, 9]);
+        let mut diff = qsub(&a, &b);
+        assert_eq!(diff.measure(), vec![0, 0]);
+    }
+
+    /// QDiv of two classical QNums yields the expected quotient and remainder.
+    #[test]
+    fn qdiv_classical_quotient_remainder() {
+        let a = QNum::from_digits(&[1, 9]); // 19
+        let b = QNum::from_digits(&[0, 7]); //  7
+        let (mut q, mut r) = qdiv(&a, &b);
+        assert_eq!(q.measure(), vec![0, 2]); // 2
+        assert_eq!(r.measure(), vec![0, 5]); // 5
+    }
+
+    /// Measuring the link of two entangled QNums yields correlated outcomes.
     #[test]
     fn entangle_correlates() {
         let mut x = QNum::from_digits(&[4]);
         let mut y = QNum::from_digits(&[7]);
-        entangle(&mut x, &mut y);
-        let dx = x.measure();
-        let dy = y.measure();
+        let (dx, dy) = entangle_with_link(&mut x, &mut y).measure();
         // After entanglement, either (4,7) or (7,4)
         let valid = dx == vec![4] && dy == vec![7] || dx == vec![7] && dy == vec![4];
         assert!(valid, "Entanglement did not correlate as expected");
@@ -73,8 +132,31 @@ mod tests {
             a[5] = Complex::new(1.0/2f64.sqrt(), 0.0);
             a
         };
-        let mut q = Qid::new(amps);
+        let q = Qid::from_f64(amps);
         let d = q.measure();
         assert!((d == 2) || (d == 5));
     }
 }
+
+/// Built with `--no-default-features`: the core compiles without `std` and
+/// measures with a caller-supplied RNG.
+#[cfg(all(test, not(feature = "std")))]
+mod no_std_tests {
+    use super::*;
+    use alloc::vec;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    #[test]
+    fn core_ops_with_injected_rng() {
+        let mut rng = ChaCha20Rng::seed_from_u64(7);
+        let sum = qadd(&QNum::from_digits(&[1, 2]), &QNum::from_digits(&[3, 4]));
+        assert_eq!(sum.clone().measure_with(&mut rng), vec![0, 4, 6]);
+
+        let mut q = QNum::new(vec![Qid::uniform(), Qid::definite(3)]);
+        let outcome = q.collapse_with(CollapsePolicy::Random, &mut rng);
+        assert!(outcome[0] < 10);
+        assert_eq!(outcome[1], 3);
+        assert_eq!(q, QNum::from_digits(&outcome));
+    }
+}
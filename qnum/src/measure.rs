@@ -2,18 +2,63 @@
 
 //! Quantum measurement utilities for Qid and QNum.
 //!
-//! Exposes two functions:
+//! Exposes:
 //! - `measure_qid(&Qid) -> usize`
 //! - `measure(&[Qid]) -> Vec<usize>`
+//! - floored variants that ignore near-zero probabilities
+//! - `measure_all(&JointState, &mut [QNum])` to collapse a correlated register
+//!   in one shot, and `measure_digit_in` to collapse one digit of it along
+//!   with the digits correlated with it
+//! - `CollapsePolicy`, the shared choice of collapse behavior for
+//!   `QNum::collapse_with`
+//! - `seeded_measure` / `seeded_measure_verify`, a deterministic measurement
+//!   keyed by a public seed, which anyone holding the seed can recompute
+//! - `vrf` / `vrf_verify`, a verifiable random measurement keyed by a
+//!   secret key and checked against its public key
+//! - `assert_outcomes_seen` for probabilistic assertions in tests
+//! - `Measurer`, implemented by `Recorder`, which logs a run's measurement
+//!   outcomes, and `Replayer`, which feeds them back in place of sampling
 
+use crate::entangle::{partial_trace, JointState};
+use crate::error::QNumError;
 use crate::qid::Qid;
+use crate::qnum::QNum;
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "std")]
+use rand::thread_rng;
+use rand_chacha::ChaCha20Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::Scalar;
+use core::fmt;
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
 
-/// Measure (collapse) a single `Qid` into one of its basis digits 0–9.
+/// How [`QNum::collapse_with`] chooses each digit's outcome.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CollapsePolicy {
+    /// Sample each digit from its probability distribution with the caller's RNG.
+    #[default]
+    Random,
+    /// Take each digit's most probable value, ties going to the lowest
+    /// (as [`Qid::collapse_deterministic`]); the RNG is not used.
+    Argmax,
+    /// Sample with a ChaCha20 RNG seeded from this value instead of the
+    /// caller's, so every node holding the same state and seed collapses to
+    /// the same outcome on any platform.
+    Seeded(u64),
+}
+
+#[cfg(feature = "std")]
+/// Measure (collapse) a single `Qid` into one of its basis digits `0..radix`.
 pub fn measure_qid(qid: &Qid) -> usize {
-    // `Qid::measure(&self)` returns a usize in 0..10
+    // `Qid::measure(&self)` returns a usize in 0..radix
     qid.measure()
 }
 
+#[cfg(feature = "std")]
 /// Measure (collapse) a slice of `Qid`s, returning each outcome.
 pub fn measure(qids: &[Qid]) -> Vec<usize> {
     qids.iter()
@@ -21,10 +66,507 @@ pub fn measure(qids: &[Qid]) -> Vec<usize> {
         .collect()
 }
 
-#[cfg(test)]
+/// Default probability floor: outcomes less likely than this are treated as
+/// floating-point dust and never sampled.
+pub const DEFAULT_PROBABILITY_FLOOR: f64 = 1e-12;
+
+#[cfg(feature = "std")]
+/// Measure a single `Qid`, ignoring digits whose probability is below `eps`;
+/// see [`Qid::measure_with_floor`].
+pub fn measure_qid_with_floor(qid: &Qid, eps: f64) -> Result<usize, QNumError> {
+    qid.measure_with_floor(eps)
+}
+
+#[cfg(feature = "std")]
+/// Measure a slice of `Qid`s, ignoring digits whose probability is below
+/// `eps`, or fail on the first `Qid` that cannot be measured.
+pub fn measure_with_floor(qids: &[Qid], eps: f64) -> Result<Vec<usize>, QNumError> {
+    qids.iter()
+        .map(|qid| qid.measure_with_floor(eps))
+        .collect()
+}
+
+#[cfg(feature = "std")]
+/// Measure (collapse) every `QNum` in `qs` in one shot from their `joint`
+/// state, keeping correlated members consistent, and return each member's
+/// outcome.
+///
+/// `qs[i]` is subsystem `i` of `joint` — e.g. the members passed to
+/// [`entangle_all`](crate::entangle::entangle_all), with the `JointState` it
+/// returned. One joint outcome is sampled and each member collapses to its
+/// part, so members of a GHZ state always agree.
+///
+/// Fails with [`QNumError::LengthMismatch`] if `qs` does not have one member
+/// per subsystem or a member's length differs from its subsystem's width,
+/// and with [`QNumError::RadixMismatch`] if a member's radix differs from the
+/// joint state's. On error, no member is modified.
+pub fn measure_all(joint: &JointState, qs: &mut [QNum]) -> Result<Vec<Vec<u8>>, QNumError> {
+    measure_all_with(joint, qs, &mut thread_rng())
+}
+
+/// Like [`measure_all`], but draws from the supplied RNG.
+pub fn measure_all_with<R: Rng + ?Sized>(
+    joint: &JointState,
+    qs: &mut [QNum],
+    rng: &mut R,
+) -> Result<Vec<Vec<u8>>, QNumError> {
+    check_register(joint, qs)?;
+    let outcomes = joint.measure_with(rng);
+    collapse_register(joint.radix(), qs, &outcomes);
+    Ok(outcomes)
+}
+
+#[cfg(feature = "std")]
+/// Measure only digit `index` of member `member` of the correlated register
+/// `qs`, collapsing the digits correlated with it through `joint`.
+///
+/// `joint` is conditioned on the outcome (see
+/// [`JointState::measure_digit_with`]) and every member is then replaced by
+/// its reduced state, as [`entangle_all`](crate::entangle::entangle_all)
+/// leaves them: digits correlated with the measured one become definite,
+/// the rest stay in superposition.
+///
+/// Fails as [`measure_all`] does if `qs` does not match `joint`, and with
+/// [`QNumError::IndexOutOfRange`] if `member` or `index` is out of range. On
+/// error, neither `joint` nor any member is modified.
+pub fn measure_digit_in(
+    joint: &mut JointState,
+    qs: &mut [QNum],
+    member: usize,
+    index: usize,
+) -> Result<u8, QNumError> {
+    measure_digit_in_with(joint, qs, member, index, &mut thread_rng())
+}
+
+/// Like [`measure_digit_in`], but draws from the supplied RNG.
+pub fn measure_digit_in_with<R: Rng + ?Sized>(
+    joint: &mut JointState,
+    qs: &mut [QNum],
+    member: usize,
+    index: usize,
+    rng: &mut R,
+) -> Result<u8, QNumError> {
+    check_register(joint, qs)?;
+    let digit = joint.measure_digit_with(member, index, rng)?;
+    for (i, q) in qs.iter_mut().enumerate() {
+        *q = partial_trace(joint, i);
+    }
+    Ok(digit)
+}
+
+/// Check that `qs` has one member per subsystem of `joint`, each of the
+/// subsystem's width and the joint state's radix.
+fn check_register(joint: &JointState, qs: &[QNum]) -> Result<(), QNumError> {
+    let widths = joint.widths();
+    if qs.len() != widths.len() {
+        return Err(QNumError::LengthMismatch { expected: widths.len(), found: qs.len() });
+    }
+    for (q, &width) in qs.iter().zip(widths) {
+        if q.len() != width {
+            return Err(QNumError::LengthMismatch { expected: width, found: q.len() });
+        }
+        if width > 0 && q.radix() != joint.radix() {
+            return Err(QNumError::RadixMismatch { expected: joint.radix(), found: q.radix() });
+        }
+    }
+    Ok(())
+}
+
+/// Collapse each member of `qs` onto its digits in `outcomes`.
+fn collapse_register(radix: usize, qs: &mut [QNum], outcomes: &[Vec<u8>]) {
+    for (q, digits) in qs.iter_mut().zip(outcomes) {
+        for (qid, &digit) in q.0.iter_mut().zip(digits) {
+            *qid = Qid::definite_in(radix, digit as usize);
+        }
+    }
+}
+
+/// Domain separation tag for seeded-measurement input digests and RNG seeds.
+const SEEDED_DOMAIN: &[u8] = b"qublis/qnum/seeded-measure/v1";
+/// Domain separation tag for seeded-measurement proof tags.
+const SEEDED_PROOF_DOMAIN: &[u8] = b"qublis/qnum/seeded-measure-proof/v1";
+
+/// Checksum accompanying a [`seeded_measure`] output.
+///
+/// Binds the output to the seed and to the input state's canonical form
+/// (see [`QNum::canonical_key`]), so it verifies for any input equal to the
+/// original up to float noise. It involves no secret: it only lets a peer
+/// holding the same seed and input confirm the output quickly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Proof {
+    /// SHA-256 digest of the input's canonical key.
+    pub input_digest: [u8; 32],
+    /// SHA-256 tag over the seed, `input_digest` and the output digits.
+    pub tag: [u8; 32],
+}
+
+/// Measure `input` with a ChaCha20 RNG derived from `seed` and the input's
+/// canonical form, returning the digits (MSB first) and a [`Proof`]
+/// checkable with [`seeded_measure_verify`].
+///
+/// The output is deterministic per `(seed, input)` and follows the input's
+/// digit distributions, so a classical input always maps to its own digits.
+/// This is not a VRF: there is no secret key, and anyone holding the seed
+/// can compute the output in advance. It suits agreeing on a random choice
+/// (such as a route in the router's deterministic mode), not hiding or
+/// authenticating one; use [`vrf`] for that. `input` is not collapsed.
+///
+/// # Panics
+/// If a digit cannot be measured; see [`Qid::try_measure`].
+pub fn seeded_measure(seed: &[u8], input: &QNum) -> (Vec<u8>, Proof) {
+    let input_digest = seeded_input_digest(input);
+    let rng_seed: [u8; 32] = Sha256::new()
+        .chain_update(SEEDED_DOMAIN)
+        .chain_update((seed.len() as u64).to_le_bytes())
+        .chain_update(seed)
+        .chain_update(input_digest)
+        .finalize()
+        .into();
+    let mut rng = ChaCha20Rng::from_seed(rng_seed);
+    let output: Vec<u8> = input.0.iter().map(|qid| qid.measure_with(&mut rng) as u8).collect();
+    let tag = seeded_tag(seed, &input_digest, &output);
+    (output, Proof { input_digest, tag })
+}
+
+/// Check that `output` and `proof` are what [`seeded_measure`] yields for
+/// `seed` and `input`.
+pub fn seeded_measure_verify(seed: &[u8], input: &QNum, output: &[u8], proof: &Proof) -> bool {
+    let (expected, expected_proof) = seeded_measure(seed, input);
+    expected == output && expected_proof == *proof
+}
+
+/// Digest of `input`'s canonical key, with its length prefixed.
+fn seeded_input_digest(input: &QNum) -> [u8; 32] {
+    let key = input.canonical_key();
+    let mut h = Sha256::new();
+    h.update(SEEDED_DOMAIN);
+    h.update((input.len() as u64).to_le_bytes());
+    for qid in &input.0 {
+        h.update((qid.radix() as u64).to_le_bytes());
+    }
+    for (basis, re, im) in key {
+        h.update([basis]);
+        h.update(re.to_le_bytes());
+        h.update(im.to_le_bytes());
+    }
+    h.finalize().into()
+}
+
+/// Proof tag binding `seed`, the input digest and `output`.
+fn seeded_tag(seed: &[u8], input_digest: &[u8; 32], output: &[u8]) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(SEEDED_PROOF_DOMAIN)
+        .chain_update((seed.len() as u64).to_le_bytes())
+        .chain_update(seed)
+        .chain_update(input_digest)
+        .chain_update((output.len() as u64).to_le_bytes())
+        .chain_update(output)
+        .finalize()
+        .into()
+}
+
+/// Domain separation tag for hashing a VRF input to the group.
+const VRF_INPUT_DOMAIN: &[u8] = b"qublis/qnum/vrf/input/v1";
+/// Domain separation tag for VRF proof nonces.
+const VRF_NONCE_DOMAIN: &[u8] = b"qublis/qnum/vrf/nonce/v1";
+/// Domain separation tag for VRF proof challenges.
+const VRF_CHALLENGE_DOMAIN: &[u8] = b"qublis/qnum/vrf/challenge/v1";
+/// Domain separation tag for deriving the measurement seed from a VRF point.
+const VRF_OUTPUT_DOMAIN: &[u8] = b"qublis/qnum/vrf/output/v1";
+/// Domain separation tag for expanding secret key bytes.
+const VRF_KEY_DOMAIN: &[u8] = b"qublis/qnum/vrf/key/v1";
+
+/// Secret key for [`vrf`]: a ristretto255 scalar.
+#[derive(Clone)]
+pub struct VrfSecretKey(Scalar);
+
+impl VrfSecretKey {
+    /// Derive a secret key from 32 bytes of secret randomness.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Self {
+        VrfSecretKey(hash_to_scalar(&[VRF_KEY_DOMAIN, bytes]))
+    }
+
+    /// Generate a fresh secret key from a cryptographically secure `rng`.
+    pub fn generate<R: rand::RngCore + rand::CryptoRng + ?Sized>(rng: &mut R) -> Self {
+        let mut bytes = [0u8; 32];
+        rng.fill_bytes(&mut bytes);
+        Self::from_bytes(&bytes)
+    }
+
+    /// Public key that verifies this key's outputs.
+    pub fn public_key(&self) -> VrfPublicKey {
+        VrfPublicKey((self.0 * RISTRETTO_BASEPOINT_POINT).compress().to_bytes())
+    }
+}
+
+impl fmt::Debug for VrfSecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("VrfSecretKey(..)")
+    }
+}
+
+/// Public key checking [`vrf`] outputs: a compressed ristretto255 point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct VrfPublicKey(pub [u8; 32]);
+
+/// Proof accompanying a [`vrf`] output.
+///
+/// `gamma = x·H(seed, input)` determines the output; `(c, s)` is a Schnorr
+/// proof that `gamma` and the public key `x·G` share the discrete log `x`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct VrfProof {
+    /// Compressed VRF point `gamma`.
+    pub gamma: [u8; 32],
+    /// Challenge scalar `c`.
+    pub c: [u8; 32],
+    /// Response scalar `s = k + c·x`.
+    pub s: [u8; 32],
+}
+
+/// Verifiable random measurement of `input` under `key` (ECVRF over
+/// ristretto255).
+///
+/// Returns the measured digits (MSB first) and a [`VrfProof`] that anyone
+/// holding `key`'s [`VrfPublicKey`] can check with [`vrf_verify`]. The digits
+/// are drawn from a ChaCha20 RNG seeded by the VRF output, so they follow the
+/// input's digit distributions and a classical input maps to its own digits.
+/// Unlike [`seeded_measure`], nobody without the secret key can predict the
+/// output, and the key holder cannot choose it: each `(key, seed, input)`
+/// has exactly one output that verifies. `input` is not collapsed.
+///
+/// # Panics
+/// If a digit cannot be measured; see [`Qid::try_measure`].
+pub fn vrf(key: &VrfSecretKey, seed: &[u8], input: &QNum) -> (Vec<u8>, VrfProof) {
+    let public = key.public_key();
+    let h = vrf_input_point(&public, seed, input);
+    let gamma = key.0 * h;
+    // derived rather than drawn, so the proof is deterministic; it depends on
+    // the secret, so it cannot be predicted either
+    let k = hash_to_scalar(&[VRF_NONCE_DOMAIN, key.0.as_bytes(), h.compress().as_bytes()]);
+    let c = vrf_challenge(&public, &h, &gamma, &(k * RISTRETTO_BASEPOINT_POINT), &(k * h));
+    let s = k + c * key.0;
+    let output = vrf_output(&gamma, input);
+    let proof = VrfProof {
+        gamma: gamma.compress().to_bytes(),
+        c: c.to_bytes(),
+        s: s.to_bytes(),
+    };
+    (output, proof)
+}
+
+/// Check that `output` and `proof` are what [`vrf`] yields for `seed` and
+/// `input` under the secret key behind `public`.
+pub fn vrf_verify(public: &VrfPublicKey, seed: &[u8], input: &QNum, output: &[u8], proof: &VrfProof) -> bool {
+    let (Some(y), Some(gamma), Some(c), Some(s)) = (
+        CompressedRistretto(public.0).decompress(),
+        CompressedRistretto(proof.gamma).decompress(),
+        Option::<Scalar>::from(Scalar::from_canonical_bytes(proof.c)),
+        Option::<Scalar>::from(Scalar::from_canonical_bytes(proof.s)),
+    ) else {
+        return false;
+    };
+    let h = vrf_input_point(public, seed, input);
+    // s·G − c·Y = k·G and s·H − c·gamma = k·H exactly when log_G Y = log_H gamma
+    let u = s * RISTRETTO_BASEPOINT_POINT - c * y;
+    let v = s * h - c * gamma;
+    vrf_challenge(public, &h, &gamma, &u, &v) == c && vrf_output(&gamma, input) == output
+}
+
+/// Hash the public key, `seed` and `input`'s canonical form to a group point.
+fn vrf_input_point(public: &VrfPublicKey, seed: &[u8], input: &QNum) -> RistrettoPoint {
+    let digest: [u8; 64] = Sha512::new()
+        .chain_update(VRF_INPUT_DOMAIN)
+        .chain_update(public.0)
+        .chain_update((seed.len() as u64).to_le_bytes())
+        .chain_update(seed)
+        .chain_update(seeded_input_digest(input))
+        .finalize()
+        .into();
+    RistrettoPoint::from_uniform_bytes(&digest)
+}
+
+/// Fiat–Shamir challenge for the VRF's discrete-log-equality proof.
+fn vrf_challenge(
+    public: &VrfPublicKey,
+    h: &RistrettoPoint,
+    gamma: &RistrettoPoint,
+    u: &RistrettoPoint,
+    v: &RistrettoPoint,
+) -> Scalar {
+    hash_to_scalar(&[
+        VRF_CHALLENGE_DOMAIN,
+        &public.0,
+        h.compress().as_bytes(),
+        gamma.compress().as_bytes(),
+        u.compress().as_bytes(),
+        v.compress().as_bytes(),
+    ])
+}
+
+/// Measure `input` with a ChaCha20 RNG seeded from the VRF point `gamma`.
+fn vrf_output(gamma: &RistrettoPoint, input: &QNum) -> Vec<u8> {
+    let rng_seed: [u8; 32] = Sha256::new()
+        .chain_update(VRF_OUTPUT_DOMAIN)
+        .chain_update(gamma.compress().as_bytes())
+        .finalize()
+        .into();
+    let mut rng = ChaCha20Rng::from_seed(rng_seed);
+    input.0.iter().map(|qid| qid.measure_with(&mut rng) as u8).collect()
+}
+
+/// Reduce the SHA-512 hash of `parts` to a scalar.
+fn hash_to_scalar(parts: &[&[u8]]) -> Scalar {
+    let mut h = Sha512::new();
+    for part in parts {
+        h.update(part);
+    }
+    Scalar::from_bytes_mod_order_wide(&h.finalize().into())
+}
+
+/// Measure copies of `q` up to `trials` times and panic unless every outcome
+/// in `expected` is observed at least once.
+///
+/// `q` itself is never collapsed. Sampling stops early once all expected
+/// outcomes have appeared; pass a seeded `rng` to make the check reproducible.
+///
+/// # Panics
+///
+/// Lists the missing outcomes and everything that was observed.
+pub fn assert_outcomes_seen<R: Rng + ?Sized>(q: &QNum, expected: &[Vec<u8>], trials: usize, rng: &mut R) {
+    let mut seen: BTreeSet<Vec<u8>> = BTreeSet::new();
+    for _ in 0..trials {
+        if expected.iter().all(|e| seen.contains(e)) {
+            return;
+        }
+        let outcome: Vec<u8> = q.0.iter().map(|qid| qid.measure_with(rng) as u8).collect();
+        seen.insert(outcome);
+    }
+    let missing: Vec<&Vec<u8>> = expected.iter().filter(|e| !seen.contains(*e)).collect();
+    if !missing.is_empty() {
+        let observed: Vec<Vec<u8>> = seen.into_iter().collect();
+        panic!(
+            "outcomes {:?} not observed in {} trials; observed {:?}",
+            missing, trials, observed
+        );
+    }
+}
+
+/// Something that measures registers, so code can run its measurements
+/// through a [`Recorder`] once and a [`Replayer`] afterwards.
+pub trait Measurer {
+    /// Measure `q` in place, as [`QNum::measure_with`], and return its digits.
+    fn measure(&mut self, q: &mut QNum) -> Result<Vec<u8>, QNumError>;
+
+    /// Collapse the correlated register `qs` from `joint`, as
+    /// [`measure_all_with`], and return each member's digits.
+    fn measure_all(&mut self, joint: &JointState, qs: &mut [QNum]) -> Result<Vec<Vec<u8>>, QNumError>;
+}
+
+/// A [`Measurer`] that samples with the wrapped RNG and logs every outcome.
+///
+/// The [`log`](Recorder::log) holds one entry per measured register, in
+/// order; save it and feed it to a [`Replayer`] to reproduce the run's
+/// outcomes without knowing the original RNG or seed.
+#[derive(Clone, Debug)]
+pub struct Recorder<R> {
+    rng: R,
+    log: Vec<Vec<u8>>,
+}
+
+impl<R: Rng> Recorder<R> {
+    /// Start recording outcomes sampled with `rng`.
+    pub fn new(rng: R) -> Self {
+        Recorder { rng, log: Vec::new() }
+    }
+
+    /// Outcomes measured so far, one per register, in order.
+    pub fn log(&self) -> &[Vec<u8>] {
+        &self.log
+    }
+
+    /// Stop recording and return the log.
+    pub fn into_log(self) -> Vec<Vec<u8>> {
+        self.log
+    }
+}
+
+impl<R: Rng> Measurer for Recorder<R> {
+    fn measure(&mut self, q: &mut QNum) -> Result<Vec<u8>, QNumError> {
+        let digits = q.measure_with(&mut self.rng);
+        self.log.push(digits.clone());
+        Ok(digits)
+    }
+
+    fn measure_all(&mut self, joint: &JointState, qs: &mut [QNum]) -> Result<Vec<Vec<u8>>, QNumError> {
+        let outcomes = measure_all_with(joint, qs, &mut self.rng)?;
+        self.log.extend(outcomes.iter().cloned());
+        Ok(outcomes)
+    }
+}
+
+/// A [`Measurer`] that collapses each register to the next outcome of a
+/// [`Recorder`] log instead of sampling.
+///
+/// Repeating the recorded sequence of measurements on the same states
+/// yields the recorded outcomes. A measurement fails with
+/// [`QNumError::ReplayDiverged`] once the log is exhausted or when the next
+/// outcome is impossible for the state being measured, i.e. when the
+/// replayed run diverges from the recorded one; the state is then left as is.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Replayer {
+    log: Vec<Vec<u8>>,
+    pos: usize,
+}
+
+impl Replayer {
+    /// Replay `log` from its first outcome.
+    pub fn new(log: Vec<Vec<u8>>) -> Self {
+        Replayer { log, pos: 0 }
+    }
+
+    /// Number of recorded outcomes not yet replayed.
+    pub fn remaining(&self) -> usize {
+        self.log.len() - self.pos
+    }
+
+    /// The next `n` recorded outcomes, if there are that many left.
+    fn peek(&self, n: usize) -> Result<&[Vec<u8>], QNumError> {
+        self.log
+            .get(self.pos..self.pos + n)
+            .ok_or(QNumError::ReplayDiverged { measurement: self.pos })
+    }
+}
+
+impl Measurer for Replayer {
+    fn measure(&mut self, q: &mut QNum) -> Result<Vec<u8>, QNumError> {
+        let digits = self.peek(1)?[0].clone();
+        q.collapse_to(&digits)
+            .map_err(|_| QNumError::ReplayDiverged { measurement: self.pos })?;
+        self.pos += 1;
+        Ok(digits)
+    }
+
+    fn measure_all(&mut self, joint: &JointState, qs: &mut [QNum]) -> Result<Vec<Vec<u8>>, QNumError> {
+        check_register(joint, qs)?;
+        let outcomes = self.peek(qs.len())?.to_vec();
+        let fits = outcomes.iter().zip(joint.widths()).all(|(d, &w)| d.len() == w);
+        if !fits || joint.probability(&outcomes.concat()) <= 0.0 {
+            return Err(QNumError::ReplayDiverged { measurement: self.pos });
+        }
+        collapse_register(joint.radix(), qs, &outcomes);
+        self.pos += outcomes.len();
+        Ok(outcomes)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use crate::qid::Qid;
+    use num_complex::Complex;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::collections::HashSet;
 
     #[test]
     fn measure_qid_definite() {
@@ -37,4 +579,279 @@ mod tests {
         let qs = vec![Qid::definite(1), Qid::definite(7), Qid::definite(0)];
         assert_eq!(measure(&qs), vec![1, 7, 0]);
     }
+
+    #[test]
+    fn floor_suppresses_dust_amplitude() {
+        let mut raw = [Complex::new(0.0, 0.0); 10];
+        raw[2] = Complex::new(1.0, 0.0);
+        raw[8] = Complex::new(1e-7, 0.0);
+        let q = Qid::from_f64(raw);
+        // the dust digit carries weight before flooring and none after
+        assert!(q.floored_probabilities(0.0)[8] > 0.0);
+        let floored = q.floored_probabilities(DEFAULT_PROBABILITY_FLOOR);
+        assert_eq!(floored[8], 0.0);
+        assert!((floored[2] - 1.0).abs() < 1e-12);
+        assert!(floored.iter().enumerate().all(|(d, &p)| d == 2 || p == 0.0));
+        for _ in 0..1000 {
+            assert_eq!(measure_qid_with_floor(&q, DEFAULT_PROBABILITY_FLOOR), Ok(2));
+        }
+
+        // a floor above every probability is ignored rather than zeroing all
+        let h = Qid::from_f64_vec(vec![Complex::new(0.6, 0.0), Complex::new(0.8, 0.0)]);
+        let kept = h.floored_probabilities(0.9);
+        assert!((kept[0] - 0.36).abs() < 1e-12 && (kept[1] - 0.64).abs() < 1e-12);
+        assert_eq!(h.floored_probabilities(0.5), vec![0.0, h.floored_probabilities(0.0)[1]]);
+    }
+
+    #[test]
+    fn outcomes_seen_on_equal_two_outcome_qnum() {
+        let h = Complex::new(1.0 / 2f64.sqrt(), 0.0);
+        let q = QNum::from_superposed(vec![(vec![3], h), (vec![8], h)]);
+        let mut rng = StdRng::seed_from_u64(7);
+        assert_outcomes_seen(&q, &[vec![3], vec![8]], 64, &mut rng);
+    }
+
+    #[test]
+    fn measure_all_collapses_entangled_pair_together() {
+        let mut rng = StdRng::seed_from_u64(1780);
+        let mut seen = HashSet::new();
+        for _ in 0..100 {
+            let (mut a, mut b) = (QNum::from_digits(&[2, 7]), QNum::from_digits(&[5, 7]));
+            let joint = crate::entangle::entangle_all(&mut [&mut a, &mut b]).unwrap();
+            let mut pair = [a, b];
+
+            let outcomes = measure_all_with(&joint, &mut pair, &mut rng).unwrap();
+            assert!(
+                outcomes == [vec![2, 7], vec![2, 7]] || outcomes == [vec![5, 7], vec![5, 7]],
+                "uncorrelated outcome {:?}",
+                outcomes
+            );
+            assert_eq!(pair[0], QNum::from_digits(&outcomes[0]));
+            assert_eq!(pair[1], QNum::from_digits(&outcomes[1]));
+            seen.insert(outcomes[0].clone());
+        }
+        assert_eq!(seen.len(), 2);
+    }
+
+    #[test]
+    fn measure_digit_in_collapses_correlated_digits() {
+        let mut rng = StdRng::seed_from_u64(1758);
+        let mut seen = HashSet::new();
+        for _ in 0..100 {
+            let (mut a, mut b) = (QNum::from_digits(&[2, 7]), QNum::from_digits(&[5, 3]));
+            let mut joint = crate::entangle::entangle_all(&mut [&mut a, &mut b]).unwrap();
+            let mut pair = [a, b];
+
+            // measuring a's first digit settles both members entirely
+            let d = measure_digit_in_with(&mut joint, &mut pair, 0, 0, &mut rng).unwrap();
+            let expected = if d == 2 { [2, 7] } else { [5, 3] };
+            assert!(d == 2 || d == 5, "unexpected digit {}", d);
+            assert_eq!(pair[0], QNum::from_digits(&expected));
+            assert_eq!(pair[1], QNum::from_digits(&expected));
+            seen.insert(d);
+        }
+        assert_eq!(seen.len(), 2);
+
+        // digits with no correlation to the measured one stay superposed
+        let h = Complex::new(1.0 / 8f64.sqrt(), 0.0);
+        let x = QNum::from_superposed(vec![
+            (vec![1, 2], h),
+            (vec![1, 8], h),
+            (vec![6, 2], h),
+            (vec![6, 8], h),
+        ]);
+        let mut joint = crate::entangle::joint_distribution(&[&x]).unwrap();
+        let mut single = [x];
+        measure_digit_in_with(&mut joint, &mut single, 0, 0, &mut rng).unwrap();
+        assert_eq!(single[0].0[0].entropy(), 0.0);
+        assert!(single[0].0[1].entropy() > 0.0);
+
+        let before = joint.clone();
+        assert_eq!(
+            measure_digit_in_with(&mut joint, &mut single, 1, 0, &mut rng),
+            Err(QNumError::IndexOutOfRange { index: 1, len: 1 })
+        );
+        assert_eq!(joint, before);
+    }
+
+    #[test]
+    fn measure_all_keeps_equal_but_independent_members_apart() {
+        // equal states are not correlated unless their joint state says so
+        let mut rng = StdRng::seed_from_u64(1780);
+        let h = Complex::new(1.0 / 2f64.sqrt(), 0.0);
+        let q = QNum::from_superposed(vec![(vec![2], h), (vec![5], h)]);
+        let joint = crate::entangle::joint_distribution(&[&q, &q]).unwrap();
+        let mut disagreed = false;
+        for _ in 0..100 {
+            let outcomes = measure_all_with(&joint, &mut [q.clone(), q.clone()], &mut rng).unwrap();
+            disagreed |= outcomes[0] != outcomes[1];
+        }
+        assert!(disagreed, "independent members always agreed");
+    }
+
+    #[test]
+    fn measure_all_rejects_members_not_matching_the_joint_state() {
+        let mut rng = StdRng::seed_from_u64(1780);
+        let (mut a, mut b) = (QNum::from_digits(&[2, 7]), QNum::from_digits(&[5, 7]));
+        let joint = crate::entangle::entangle_all(&mut [&mut a, &mut b]).unwrap();
+
+        let mut one = [a.clone()];
+        let err = measure_all_with(&joint, &mut one, &mut rng).unwrap_err();
+        assert_eq!(err, QNumError::LengthMismatch { expected: 2, found: 1 });
+
+        let mut ragged = [a.clone(), QNum::from_digits(&[5])];
+        let err = measure_all_with(&joint, &mut ragged, &mut rng).unwrap_err();
+        assert_eq!(err, QNumError::LengthMismatch { expected: 2, found: 1 });
+        assert_eq!(ragged[0], a);
+    }
+
+    #[test]
+    #[should_panic(expected = "not observed")]
+    fn outcomes_seen_reports_missing() {
+        let q = QNum::from_digits(&[5]);
+        let mut rng = StdRng::seed_from_u64(7);
+        assert_outcomes_seen(&q, &[vec![5], vec![6]], 16, &mut rng);
+    }
+
+    #[test]
+    fn seeded_measure_is_deterministic_per_seed() {
+        let h = Complex::new(0.5, 0.0);
+        let input = QNum::from_superposed(vec![
+            (vec![0, 1], h),
+            (vec![3, 4], h),
+            (vec![6, 7], h),
+            (vec![9, 2], h),
+        ]);
+        let (out, proof) = seeded_measure(b"epoch-1", &input);
+        for _ in 0..10 {
+            assert_eq!(seeded_measure(b"epoch-1", &input), (out.clone(), proof));
+        }
+        assert_eq!(out.len(), input.len());
+
+        let outputs: HashSet<Vec<u8>> =
+            (0u32..32).map(|i| seeded_measure(&i.to_le_bytes(), &input).0).collect();
+        assert!(outputs.len() > 1, "output ignores the seed");
+    }
+
+    #[test]
+    fn seeded_measure_proof_verifies() {
+        let h = Complex::new(1.0 / 2f64.sqrt(), 0.0);
+        let input = QNum::from_superposed(vec![(vec![2, 5], h), (vec![8, 1], h)]);
+        let (out, proof) = seeded_measure(b"seed", &input);
+        assert!(seeded_measure_verify(b"seed", &input, &out, &proof));
+
+        assert!(!seeded_measure_verify(b"other", &input, &out, &proof));
+        assert!(!seeded_measure_verify(b"seed", &QNum::from_digits(&[2, 5]), &out, &proof));
+        let mut forged = out.clone();
+        forged[0] = (forged[0] + 1) % 10;
+        assert!(!seeded_measure_verify(b"seed", &input, &forged, &proof));
+        let mut bad_tag = proof;
+        bad_tag.tag[0] ^= 1;
+        assert!(!seeded_measure_verify(b"seed", &input, &out, &bad_tag));
+    }
+
+    #[test]
+    fn vrf_is_deterministic_per_key_and_seed() {
+        let h = Complex::new(0.5, 0.0);
+        let input = QNum::from_superposed(vec![
+            (vec![0, 1], h),
+            (vec![3, 4], h),
+            (vec![6, 7], h),
+            (vec![9, 2], h),
+        ]);
+        let key = VrfSecretKey::from_bytes(&[1u8; 32]);
+        let (out, proof) = vrf(&key, b"epoch-1", &input);
+        assert_eq!(vrf(&key, b"epoch-1", &input), (out.clone(), proof));
+        assert_eq!(out.len(), input.len());
+
+        let by_seed: HashSet<Vec<u8>> = (0u32..32).map(|i| vrf(&key, &i.to_le_bytes(), &input).0).collect();
+        assert!(by_seed.len() > 1, "output ignores the seed");
+        let by_key: HashSet<Vec<u8>> =
+            (0u8..32).map(|i| vrf(&VrfSecretKey::from_bytes(&[i; 32]), b"epoch-1", &input).0).collect();
+        assert!(by_key.len() > 1, "output ignores the key");
+    }
+
+    #[test]
+    fn vrf_proof_verifies_only_under_its_public_key() {
+        let h = Complex::new(1.0 / 2f64.sqrt(), 0.0);
+        let input = QNum::from_superposed(vec![(vec![2, 5], h), (vec![8, 1], h)]);
+        let key = VrfSecretKey::generate(&mut StdRng::seed_from_u64(3));
+        let public = key.public_key();
+        let (out, proof) = vrf(&key, b"seed", &input);
+        assert!(vrf_verify(&public, b"seed", &input, &out, &proof));
+
+        let other = VrfSecretKey::from_bytes(&[9u8; 32]).public_key();
+        assert!(!vrf_verify(&other, b"seed", &input, &out, &proof));
+        assert!(!vrf_verify(&public, b"other", &input, &out, &proof));
+        assert!(!vrf_verify(&public, b"seed", &QNum::from_digits(&[2, 5]), &out, &proof));
+        let mut forged = out.clone();
+        forged[0] = (forged[0] + 1) % 10;
+        assert!(!vrf_verify(&public, b"seed", &input, &forged, &proof));
+        let mut bad_gamma = proof;
+        bad_gamma.gamma = other.0;
+        assert!(!vrf_verify(&public, b"seed", &input, &out, &bad_gamma));
+        let mut bad_s = proof;
+        bad_s.s[0] ^= 1;
+        assert!(!vrf_verify(&public, b"seed", &input, &out, &bad_s));
+    }
+
+    #[test]
+    fn vrf_of_classical_input_is_its_digits() {
+        let key = VrfSecretKey::from_bytes(&[5u8; 32]);
+        assert_eq!(vrf(&key, b"any", &QNum::from_digits(&[4, 0, 4])).0, vec![4, 0, 4]);
+    }
+
+    #[test]
+    fn seeded_measure_of_classical_input_is_its_digits() {
+        let input = QNum::from_digits(&[4, 0, 4]);
+        assert_eq!(seeded_measure(b"any", &input).0, vec![4, 0, 4]);
+    }
+
+    #[test]
+    fn replay_reproduces_recorded_outcomes() {
+        let register = || {
+            let mut a = QNum::new(vec![Qid::uniform(), Qid::uniform(), Qid::uniform()]);
+            let mut b = QNum::from_digits(&[2, 0, 7]);
+            let joint = crate::entangle::entangle_all(&mut [&mut a, &mut b]).unwrap();
+            (joint, vec![a, b])
+        };
+        let run = |m: &mut dyn Measurer| {
+            let (joint, mut qs) = register();
+            let mut outcomes = m.measure_all(&joint, &mut qs).unwrap();
+            outcomes.push(m.measure(&mut QNum::new(vec![Qid::uniform(); 4])).unwrap());
+            (outcomes, qs)
+        };
+
+        let mut recorder = Recorder::new(ChaCha20Rng::seed_from_u64(99));
+        let (recorded, recorded_qs) = run(&mut recorder);
+        let log = recorder.into_log();
+        // one entry per measured register, holding its outcome
+        assert_eq!(log, recorded);
+
+        let mut replayer = Replayer::new(log);
+        let (replayed, replayed_qs) = run(&mut replayer);
+        assert_eq!(replayed, recorded);
+        assert_eq!(replayed_qs, recorded_qs);
+        assert_eq!(replayer.remaining(), 0);
+    }
+
+    #[test]
+    fn replay_reports_divergence() {
+        let mut replayer = Replayer::new(vec![vec![1]]);
+        // the recorded outcome is impossible for a definite |0>
+        let mut zero = QNum::from_digits(&[0]);
+        assert_eq!(
+            replayer.measure(&mut zero),
+            Err(QNumError::ReplayDiverged { measurement: 0 })
+        );
+        assert_eq!(zero, QNum::from_digits(&[0]));
+
+        let mut one = QNum::from_digits(&[1]);
+        assert_eq!(replayer.measure(&mut one), Ok(vec![1]));
+        // past the end of the log
+        assert_eq!(
+            replayer.measure(&mut one),
+            Err(QNumError::ReplayDiverged { measurement: 1 })
+        );
+    }
 }
@@ -1,34 +1,173 @@
-//! A single quantum digit (Qid) holding 10 complex amplitudes.
-//! Each f64 is wrapped in `OrderedFloat` so we can derive `Hash` + `Eq`.
+//! A single quantum digit (Qid) holding `radix` complex amplitudes (10 by default).
+//! Each amplitude part (`f64` by default, or `f32`) is wrapped in `OrderedFloat`
+//! so the digit can be `Hash` + `Eq`.
 
+use crate::error::QNumError;
 use num_complex::Complex;
 use ordered_float::OrderedFloat;
 use serde::{Deserialize, Serialize};
 use rand::distributions::{Distribution, WeightedIndex};
+use rand::Rng;
+#[cfg(feature = "std")]
 use rand::thread_rng;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use alloc::{vec, vec::Vec};
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::Float;
+
+/// Radix used by the base-10 constructors (`definite`, `QNum::from_digits`, …).
+pub const DEFAULT_RADIX: usize = 10;
+
+/// Largest supported radix: every basis value must fit in a `u8` digit.
+pub const MAX_RADIX: usize = 256;
+
+/// Largest deviation from the identity tolerated in `U·U†` when checking that a
+/// measurement basis is unitary.
+pub const UNITARY_TOLERANCE: f64 = 1e-9;
+
+/// Amplitude magnitude below which `Display` omits a basis state.
+pub const DISPLAY_THRESHOLD: f64 = 1e-6;
+
+/// Amplitude magnitude below which [`Qid::canonicalize`] will not take a basis
+/// state as its phase reference.
+pub const PHASE_REFERENCE_THRESHOLD: f64 = 1e-6;
+
+/// Probability mass a digit's likeliest basis state may fall short of 1 by
+/// and still read as a classical value in [`QNum::to_i64`](crate::QNum::to_i64).
+pub const CLASSICAL_TOLERANCE: f64 = 1e-9;
+
+/// Float type a [`Qid`] stores its amplitudes in.
+///
+/// `f64` is the default; `f32` halves the storage of large meshes at the cost
+/// of precision (normalization holds to roughly `1e-6`). Arithmetic is carried
+/// out in `f64` and rounded back to `Self` when stored. A [`QNum`](crate::QNum)
+/// takes the same parameter, and the arithmetic gates accept either precision.
+pub trait Amplitude:
+    num_traits::Float + num_traits::float::FloatCore + fmt::Debug + Serialize + for<'de> Deserialize<'de> + 'static
+{
+    /// Round an `f64` to this type.
+    fn narrow(x: f64) -> Self;
+    /// Widen this value to `f64` (exact).
+    fn widen(self) -> f64;
+}
+
+impl Amplitude for f64 {
+    fn narrow(x: f64) -> Self {
+        x
+    }
+
+    fn widen(self) -> f64 {
+        self
+    }
+}
+
+impl Amplitude for f32 {
+    fn narrow(x: f64) -> Self {
+        x as f32
+    }
+
+    fn widen(self) -> f64 {
+        f64::from(self)
+    }
+}
 
 /// A single “digit” in the Quantum Number System: a superposition
-/// over the values 0–9, each with a complex amplitude.
-#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
-pub struct Qid {
-    /// Each amplitude’s real and imaginary parts are wrapped in `OrderedFloat<f64>`.
-    pub amps: [Complex<OrderedFloat<f64>>; 10],
+/// over the values `0..radix`, each with a complex amplitude.
+///
+/// Amplitudes are `f64` unless another [`Amplitude`] type is chosen, e.g.
+/// `Qid<f32>`. Constructors such as [`Qid::definite`] build `f64` digits; use
+/// [`Qid::from_amps`] or [`Qid::cast`] for other precisions.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "T: Amplitude")]
+pub struct Qid<T = f64> {
+    /// One amplitude per basis value; the length is the digit's radix.
+    /// Each amplitude’s real and imaginary parts are wrapped in `OrderedFloat<T>`.
+    pub amps: Vec<Complex<OrderedFloat<T>>>,
+}
+
+impl<T: Amplitude> PartialEq for Qid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.amps == other.amps
+    }
+}
+
+impl<T: Amplitude> Eq for Qid<T> {}
+
+impl<T: Amplitude> Hash for Qid<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.amps.hash(state);
+    }
+}
+
+/// An amplitude widened to `f64`.
+fn widen<T: Amplitude>(c: &Complex<OrderedFloat<T>>) -> Complex<f64> {
+    Complex::new(c.re.into_inner().widen(), c.im.into_inner().widen())
+}
+
+/// An `f64` amplitude rounded for storage as `T`.
+fn narrow<T: Amplitude>(c: Complex<f64>) -> Complex<OrderedFloat<T>> {
+    Complex::new(OrderedFloat(T::narrow(c.re)), OrderedFloat(T::narrow(c.im)))
 }
 
 impl Qid {
-    /// Construct a Qid from raw `f64` amplitudes (array length = 10).
-    /// Panics if the caller provides an array of a different size.
-    pub fn from_f64(amps: [Complex<f64>; 10]) -> Self {
-        let wrapped = amps.map(|c| Complex {
-            re: OrderedFloat(c.re),
-            im: OrderedFloat(c.im),
-        });
-        Qid { amps: wrapped }
+    /// Construct a Qid from raw `f64` amplitudes; the array length is the radix.
+    pub fn from_f64<const R: usize>(amps: [Complex<f64>; R]) -> Self {
+        Self::from_f64_vec(amps.to_vec())
+    }
+
+    /// Construct a Qid from a vector of raw `f64` amplitudes; the length is the radix.
+    ///
+    /// Panics if the length is not in `2..=MAX_RADIX`; see [`Qid::try_from_f64_vec`].
+    pub fn from_f64_vec(amps: Vec<Complex<f64>>) -> Self {
+        Self::try_from_f64_vec(amps).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Fallible [`Qid::from_f64_vec`]: returns [`QNumError::RadixOutOfRange`]
+    /// if the length is not in `2..=MAX_RADIX`.
+    ///
+    /// Amplitudes are stored as given; use [`Qid::validate`] to reject
+    /// non-finite values.
+    pub fn try_from_f64_vec(amps: Vec<Complex<f64>>) -> Result<Self, QNumError> {
+        check_radix(amps.len())?;
+        let wrapped = amps
+            .into_iter()
+            .map(|c| Complex {
+                re: OrderedFloat(c.re),
+                im: OrderedFloat(c.im),
+            })
+            .collect();
+        Ok(Qid { amps: wrapped })
     }
 
-    /// Create a “definite” (classical) Qid that collapses to digit `i`.
+    /// Create a “definite” (classical) base-10 Qid that collapses to digit `i`.
+    ///
+    /// Panics if `i >= 10`; see [`Qid::try_definite`].
     pub fn definite(i: usize) -> Self {
-        assert!(i < 10, "digit out of range");
+        Self::definite_in(DEFAULT_RADIX, i)
+    }
+
+    /// Create a “definite” Qid of the given `radix` that collapses to digit `i`.
+    ///
+    /// Panics if the radix is unsupported or `i >= radix`; see
+    /// [`Qid::try_definite_in`].
+    pub fn definite_in(radix: usize, i: usize) -> Self {
+        Self::try_definite_in(radix, i).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Fallible [`Qid::definite`]: returns [`QNumError::DigitOutOfRange`] if `i >= 10`.
+    pub fn try_definite(i: usize) -> Result<Self, QNumError> {
+        Self::try_definite_in(DEFAULT_RADIX, i)
+    }
+
+    /// Fallible [`Qid::definite_in`]: returns [`QNumError::RadixOutOfRange`] or
+    /// [`QNumError::DigitOutOfRange`].
+    pub fn try_definite_in(radix: usize, i: usize) -> Result<Self, QNumError> {
+        check_radix(radix)?;
+        if i >= radix {
+            return Err(QNumError::DigitOutOfRange { digit: i, radix });
+        }
         let zero = Complex {
             re: OrderedFloat(0.0),
             im: OrderedFloat(0.0),
@@ -37,25 +176,68 @@ impl Qid {
             re: OrderedFloat(1.0),
             im: OrderedFloat(0.0),
         };
-        let mut amps = [zero; 10];
+        let mut amps = vec![zero; radix];
         amps[i] = one;
+        Ok(Qid { amps })
+    }
+
+    /// The uniform base-10 superposition, every amplitude `1/√10`.
+    pub fn uniform() -> Self {
+        Self::uniform_in(DEFAULT_RADIX)
+    }
+
+    /// The uniform superposition of the given `radix`, every amplitude `1/√radix`.
+    pub fn uniform_in(radix: usize) -> Self {
+        let amp = Complex::new(1.0 / (radix as f64).sqrt(), 0.0);
+        Self::from_f64_vec(vec![amp; radix])
+    }
+
+}
+
+impl<T: Amplitude> Qid<T> {
+    /// Construct a Qid from amplitudes of any [`Amplitude`] precision; the
+    /// length is the radix.
+    ///
+    /// Panics if the length is not in `2..=MAX_RADIX`.
+    pub fn from_amps(amps: Vec<Complex<T>>) -> Self {
+        assert_radix(amps.len());
+        let amps = amps
+            .into_iter()
+            .map(|c| Complex::new(OrderedFloat(c.re), OrderedFloat(c.im)))
+            .collect();
         Qid { amps }
     }
 
+    /// Convert the amplitudes to another precision, e.g. `Qid<f32>` for storage.
+    pub fn cast<U: Amplitude>(&self) -> Qid<U> {
+        Qid {
+            amps: self.amps.iter().map(|c| narrow(widen(c))).collect(),
+        }
+    }
+
+    /// Set this digit to the basis state `|digit⟩`.
+    fn collapse_to(&mut self, digit: usize) {
+        for (i, c) in self.amps.iter_mut().enumerate() {
+            let re = if i == digit { T::one() } else { T::zero() };
+            *c = Complex::new(OrderedFloat(re), OrderedFloat(T::zero()));
+        }
+    }
+
+    /// Number of basis values this digit ranges over.
+    pub fn radix(&self) -> usize {
+        self.amps.len()
+    }
+
     /// Normalize this Qid so that the sum of squared magnitudes of `amps` equals 1.
     ///
-    /// If the total norm is zero, this is a no-op.
+    /// If the total norm is zero, or any amplitude is NaN or infinite, this is a
+    /// no-op; use [`Qid::try_normalize`] to have those cases reported.
     pub fn normalize(&mut self) {
+        if self.validate().is_err() {
+            return;
+        }
         // compute sum of squared magnitudes as f64
-        let sum_sq: f64 = self
-            .amps
-            .iter()
-            .map(|c| {
-                let re = c.re.into_inner();
-                let im = c.im.into_inner();
-                re * re + im * im
-            })
-            .sum();
+        let sum_sq: f64 = self.amps.iter().map(|c| widen(c).norm_sqr()).sum();
 
         if sum_sq <= 0.0 {
             // zero‐vector: cannot normalize
@@ -65,62 +247,331 @@ impl Qid {
 
         // divide each amplitude by norm
         for c in &mut self.amps {
-            let re = c.re.into_inner() / norm;
-            let im = c.im.into_inner() / norm;
-            c.re = OrderedFloat(re);
-            c.im = OrderedFloat(im);
+            *c = narrow(widen(c) / norm);
+        }
+    }
+
+    /// Check that this digit is a measurable state: every amplitude is finite
+    /// ([`QNumError::NonFiniteAmplitude`], naming the first offending basis
+    /// value) and not all of them are zero ([`QNumError::ZeroNorm`], with
+    /// `index` 0).
+    pub fn validate(&self) -> Result<(), QNumError> {
+        if let Some(basis) = self
+            .amps
+            .iter()
+            .position(|c| !c.re.into_inner().widen().is_finite() || !c.im.into_inner().widen().is_finite())
+        {
+            return Err(QNumError::NonFiniteAmplitude { basis });
+        }
+        if self.amps.iter().all(|c| widen(c).norm_sqr() == 0.0) {
+            return Err(QNumError::ZeroNorm { index: 0 });
         }
+        Ok(())
     }
 
-    /// Measures the Qid, returning the observed digit (0–9) according to its probability amplitudes.
+    /// Like [`Qid::normalize`], but fails instead of silently skipping a
+    /// digit rejected by [`Qid::validate`].
+    pub fn try_normalize(&mut self) -> Result<(), QNumError> {
+        self.validate()?;
+        self.normalize();
+        Ok(())
+    }
+
+    #[cfg(feature = "std")]
+    /// Measures the Qid, returning the observed digit (`0..radix`) according to its probability amplitudes.
+    ///
+    /// # Panics
+    /// If the amplitudes are not finite or all zero; see [`Qid::try_measure`].
     pub fn measure(&self) -> usize {
+        self.measure_with(&mut thread_rng())
+    }
+
+    /// Like [`Qid::measure`], but draws from the supplied RNG so that
+    /// measurement sequences can be reproduced from a seed.
+    pub fn measure_with<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        self.try_measure_with(rng)
+            .unwrap_or_else(|e| panic!("cannot measure Qid: {}", e))
+    }
+
+    #[cfg(feature = "std")]
+    /// Fallible [`Qid::measure`]: returns [`QNumError::NonFiniteAmplitude`] or
+    /// [`QNumError::ZeroNorm`] (with `index` 0) instead of panicking.
+    pub fn try_measure(&self) -> Result<usize, QNumError> {
+        self.try_measure_with(&mut thread_rng())
+    }
+
+    /// Fallible [`Qid::measure_with`].
+    pub fn try_measure_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<usize, QNumError> {
+        self.validate()?;
+
         // Calculate probabilities
         let probs: Vec<f64> = self.amps.iter()
-            .map(|c| c.norm_sqr().into_inner())
+            .map(|c| widen(c).norm_sqr())
             .collect();
 
-        // Create a WeightedIndex distribution
+        // Create a WeightedIndex distribution; with finite inputs this only
+        // fails when every weight is zero (or overflows to infinity)
         let dist = WeightedIndex::new(&probs)
-            .expect("Invalid probabilities for measurement");
+            .map_err(|_| QNumError::ZeroNorm { index: 0 })?;
 
-        // Sample using thread-local RNG
-        let mut rng = thread_rng();
-        dist.sample(&mut rng)
+        Ok(dist.sample(rng))
     }
 
-    /// Measures the Qid, collapses it to the observed digit, and returns the digit (0–9).
-    pub fn measure_and_collapse(&mut self) -> usize {
-        // Calculate probabilities from amplitudes
-        let probs: Vec<f64> = self.amps.iter()
-            .map(|c| c.norm_sqr().into_inner())
+    #[cfg(feature = "std")]
+    /// Like [`Qid::try_measure`], but treats any digit whose probability is
+    /// below `eps` as zero before sampling, so floating-point dust left over
+    /// from gate arithmetic can never be observed.
+    ///
+    /// If every probability falls below `eps`, the floor is ignored. Fails
+    /// with [`QNumError::NonFiniteAmplitude`] or [`QNumError::ZeroNorm`]
+    /// (with `index` 0), as [`Qid::try_measure`] does.
+    pub fn measure_with_floor(&self, eps: f64) -> Result<usize, QNumError> {
+        self.measure_with_floor_with(eps, &mut thread_rng())
+    }
+
+    /// Like [`Qid::measure_with_floor`], but draws from the supplied RNG so
+    /// that measurement sequences can be reproduced from a seed.
+    pub fn measure_with_floor_with<R: Rng + ?Sized>(&self, eps: f64, rng: &mut R) -> Result<usize, QNumError> {
+        self.validate()?;
+        let dist = WeightedIndex::new(self.floored_probabilities(eps))
+            .map_err(|_| QNumError::ZeroNorm { index: 0 })?;
+        Ok(dist.sample(rng))
+    }
+
+    /// Outcome weights sampled by [`Qid::measure_with_floor`]: each basis
+    /// state's probability, with those below `eps` set to zero unless that
+    /// would zero them all.
+    pub(crate) fn floored_probabilities(&self, eps: f64) -> Vec<f64> {
+        let mut probs: Vec<f64> = self.amps.iter()
+            .map(|c| widen(c).norm_sqr())
             .collect();
+        if probs.iter().any(|&p| p >= eps) {
+            for p in &mut probs {
+                if *p < eps {
+                    *p = 0.0;
+                }
+            }
+        }
+        probs
+    }
 
-        // Weighted random sampling
-        let dist = WeightedIndex::new(&probs)
-            .expect("Invalid probabilities for measurement");
-        let mut rng = thread_rng();
-        let measured = dist.sample(&mut rng);
+    /// Measure a base-10 digit in the orthonormal basis whose `k`-th vector is
+    /// row `k` of `basis` (in computational coordinates), returning `k`.
+    ///
+    /// The state is first transformed into that basis, giving outcome `k`
+    /// amplitude `⟨b_k|ψ⟩ = Σⱼ conj(basis[k][j])·ψⱼ`, and then sampled.
+    /// The identity matrix reproduces [`Qid::measure_with`].
+    ///
+    /// Fails with [`QNumError::RadixMismatch`] unless the digit has radix 10,
+    /// [`QNumError::NotUnitary`] if `basis` is not unitary within
+    /// [`UNITARY_TOLERANCE`], or [`QNumError::NonFiniteAmplitude`].
+    pub fn measure_in_basis<R: Rng + ?Sized>(
+        &self,
+        basis: &[[Complex<f64>; DEFAULT_RADIX]; DEFAULT_RADIX],
+        rng: &mut R,
+    ) -> Result<usize, QNumError> {
+        if self.radix() != DEFAULT_RADIX {
+            return Err(QNumError::RadixMismatch {
+                expected: DEFAULT_RADIX,
+                found: self.radix(),
+            });
+        }
+        for (row, u) in basis.iter().enumerate() {
+            for (col, v) in basis.iter().enumerate() {
+                let dot: Complex<f64> = u.iter().zip(v).map(|(a, b)| a * b.conj()).sum();
+                let identity = if row == col { 1.0 } else { 0.0 };
+                if (dot - identity).norm() > UNITARY_TOLERANCE {
+                    return Err(QNumError::NotUnitary { row, col });
+                }
+            }
+        }
+
+        let amps: Vec<Complex<f64>> = basis
+            .iter()
+            .map(|b| {
+                b.iter()
+                    .zip(&self.amps)
+                    .map(|(bj, a)| bj.conj() * widen(a))
+                    .sum()
+            })
+            .collect();
+        Qid::from_f64_vec(amps).try_measure_with(rng)
+    }
+
+    #[cfg(feature = "std")]
+    /// Measures the Qid, collapses it to the observed digit, and returns the digit.
+    pub fn measure_and_collapse(&mut self) -> usize {
+        self.measure_and_collapse_with(&mut thread_rng())
+    }
+
+    /// Like [`Qid::measure_and_collapse`], but draws from the supplied RNG.
+    pub fn measure_and_collapse_with<R: Rng + ?Sized>(&mut self, rng: &mut R) -> usize {
+        let measured = self.measure_with(rng);
 
         // Collapse: set amplitude at measured index to 1, others to 0
-        let zero = Complex {
-            re: OrderedFloat(0.0),
-            im: OrderedFloat(0.0),
-        };
-        let one = Complex {
-            re: OrderedFloat(1.0),
-            im: OrderedFloat(0.0),
-        };
-        self.amps = [zero; 10];
-        self.amps[measured] = one;
+        self.collapse_to(measured);
 
         measured
     }
 
-    /// Compute the entropy of this Qid's probability distribution.
+    /// Apply the generalized (radix-`R`) Hadamard gate, the discrete Fourier
+    /// transform over the digit's basis:
+    /// ```text
+    /// |j⟩ ↦ (1/√R) Σₖ e^{2πi·jk/R} |k⟩
+    /// ```
+    /// A definite `|j⟩` becomes an equal-magnitude superposition over all `R`
+    /// values (exactly [`Qid::uniform_in`] for `|0⟩`). The gate is unitary, so
+    /// phases on the input interfere in the output.
+    pub fn hadamard(&mut self) {
+        let r = self.radix();
+        let scale = 1.0 / (r as f64).sqrt();
+        let input: Vec<Complex<f64>> = self.amps.iter().map(widen).collect();
+        for (k, out) in self.amps.iter_mut().enumerate() {
+            let sum: Complex<f64> = input
+                .iter()
+                .enumerate()
+                .map(|(j, a)| {
+                    let theta = 2.0 * core::f64::consts::PI * ((j * k) % r) as f64 / r as f64;
+                    a * Complex::from_polar(1.0, theta)
+                })
+                .sum();
+            *out = narrow(sum * scale);
+        }
+    }
+
+    /// Multiply the amplitude of basis value `digit` by `e^{iθ}`.
+    ///
+    /// Probabilities are unchanged; the relative phase only shows up once the
+    /// digit is interfered, e.g. by [`Qid::hadamard`].
+    ///
+    /// # Panics
+    /// If `digit` is not below the radix.
+    pub fn phase(&mut self, digit: usize, radians: f64) {
+        assert!(digit < self.radix(), "digit out of range");
+        let c = &mut self.amps[digit];
+        *c = narrow(widen(c) * Complex::from_polar(1.0, radians));
+    }
+
+    /// Remove the global phase: rotate every amplitude by the same `e^{-iθ}`
+    /// so that the first amplitude with magnitude above
+    /// [`PHASE_REFERENCE_THRESHOLD`] becomes real and positive.
+    ///
+    /// Digits equal up to a global phase become identical. A digit with a
+    /// NaN or infinite amplitude, or none above the threshold, is unchanged.
+    pub fn canonicalize(&mut self) {
+        if self.validate().is_err() {
+            return;
+        }
+        let Some((reference, c)) = self
+            .amps
+            .iter()
+            .map(widen)
+            .enumerate()
+            .find(|(_, c)| c.norm() > PHASE_REFERENCE_THRESHOLD)
+        else {
+            return;
+        };
+        let rotation = c.conj() / c.norm();
+        for amp in &mut self.amps {
+            *amp = narrow(widen(amp) * rotation);
+        }
+        // drop the rounding residue so the reference is exactly real
+        self.amps[reference].im = OrderedFloat(T::zero());
+    }
+
+    /// Cyclic shift (generalized Pauli-X): `|i⟩ ↦ |(i + k) mod R⟩` for radix `R`.
+    ///
+    /// A permutation of the amplitudes, so it is unitary and `shift(-k)`
+    /// undoes it. Negative `k` shifts down.
+    pub fn shift(&mut self, k: i32) {
+        let r = self.radix();
+        self.amps.rotate_right(k.rem_euclid(r as i32) as usize);
+    }
+
+    /// Increment the digit mod its radix, `|i⟩ ↦ |(i + 1) mod R⟩`; see [`Qid::shift`].
+    pub fn increment(&mut self) {
+        self.shift(1);
+    }
+
+    /// Amplitude-damping noise: every excited value `k > 0` loses a fraction
+    /// `gamma` of its probability to `|0⟩`,
+    /// ```text
+    /// pₖ ↦ (1 − γ)·pₖ   (k > 0),    p₀ ↦ p₀ + γ·Σ_{k>0} pₖ
+    /// ```
+    /// Excited amplitudes are scaled by `√(1 − γ)`; the `|0⟩` amplitude keeps
+    /// its phase. The result is renormalized.
+    ///
+    /// `gamma = 0` is a no-op and `gamma = 1` collapses fully to `|0⟩`;
+    /// `gamma` is clamped to `[0, 1]`.
+    pub fn apply_amplitude_damping(&mut self, gamma: f64) {
+        let gamma = gamma.clamp(0.0, 1.0);
+        if gamma == 0.0 {
+            return;
+        }
+        let keep = (1.0 - gamma).sqrt();
+        let mut lost = 0.0;
+        for c in self.amps.iter_mut().skip(1) {
+            let amp = widen(c);
+            lost += gamma * amp.norm_sqr();
+            *c = narrow(amp * keep);
+        }
+
+        let ground = widen(&self.amps[0]);
+        let magnitude = (ground.norm_sqr() + lost).sqrt();
+        let phase = if ground.norm_sqr() > 0.0 { ground.arg() } else { 0.0 };
+        let damped = Complex::from_polar(magnitude, phase);
+        self.amps[0] = narrow(damped);
+        self.normalize();
+    }
+
+    /// Complex inner product `⟨self|other⟩ = Σ conj(selfᵢ)·otherᵢ`.
+    ///
+    /// Digits of different radix share no basis and have overlap 0.
+    pub fn inner_product(&self, other: &Qid<T>) -> Complex<f64> {
+        if self.radix() != other.radix() {
+            return Complex::new(0.0, 0.0);
+        }
+        self.amps
+            .iter()
+            .zip(&other.amps)
+            .map(|(a, b)| widen(a).conj() * widen(b))
+            .sum()
+    }
+
+    /// Fidelity `|⟨self|other⟩|²` between two digits, in `[0, 1]` for normalized states.
+    ///
+    /// Digits of different radix share no basis and have fidelity 0.
+    pub fn fidelity(&self, other: &Qid<T>) -> f64 {
+        self.inner_product(other).norm_sqr()
+    }
+
+    /// Index of the most probable digit, preferring the lowest index on ties.
+    pub fn argmax(&self) -> usize {
+        let mut best = 0;
+        let mut best_p = f64::NEG_INFINITY;
+        for (i, c) in self.amps.iter().enumerate() {
+            let p = widen(c).norm_sqr();
+            if p > best_p {
+                best = i;
+                best_p = p;
+            }
+        }
+        best
+    }
+
+    /// Collapse to the most probable digit without consulting an RNG,
+    /// breaking ties towards the lowest index. Returns the chosen digit.
+    pub fn collapse_deterministic(&mut self) -> usize {
+        let digit = self.argmax();
+        self.collapse_to(digit);
+        digit
+    }
+
+    /// Compute the Shannon entropy of this Qid's probability distribution, in nats.
     pub fn entropy(&self) -> f64 {
         self.amps.iter()
             .map(|c| {
-                let p = c.norm_sqr().into_inner();
+                let p = widen(c).norm_sqr();
                 if p > 0.0 {
                     -p * p.ln()
                 } else {
@@ -129,12 +580,109 @@ impl Qid {
             })
             .sum()
     }
+
+    /// Compute the Shannon entropy of this Qid's probability distribution, in bits.
+    pub fn entropy_bits(&self) -> f64 {
+        self.entropy() / core::f64::consts::LN_2
+    }
+}
+
+/// Dirac-notation view of a [`Qid`] with a custom amplitude threshold; see
+/// [`Qid::dirac`].
+#[derive(Clone, Copy, Debug)]
+pub struct Dirac<'a, T = f64> {
+    qid: &'a Qid<T>,
+    threshold: f64,
+}
+
+impl<T: Amplitude> Qid<T> {
+    /// Format this digit as `0.71|2⟩ + 0.71|5⟩`, omitting basis states whose
+    /// amplitude magnitude is below `threshold`.
+    ///
+    /// The formatter's precision sets the decimals (2 by default). A lone
+    /// state with amplitude 1 prints as just `|k⟩`; if nothing clears the
+    /// threshold the digit prints as `0`.
+    pub fn dirac(&self, threshold: f64) -> Dirac<'_, T> {
+        Dirac { qid: self, threshold }
+    }
+
+    /// Number of terms `dirac(threshold)` would print.
+    pub(crate) fn dirac_terms(&self, threshold: f64) -> usize {
+        self.amps.iter().filter(|c| widen(*c).norm() >= threshold).count()
+    }
 }
 
-#[cfg(test)]
+impl<T: Amplitude> fmt::Display for Dirac<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prec = f.precision().unwrap_or(2);
+        let eps = 0.5 * 10f64.powi(-(prec as i32));
+        let terms: Vec<(usize, Complex<f64>)> = self
+            .qid
+            .amps
+            .iter()
+            .enumerate()
+            .map(|(k, c)| (k, widen(c)))
+            .filter(|(_, c)| c.norm() >= self.threshold)
+            .collect();
+
+        match terms.as_slice() {
+            [] => return write!(f, "0"),
+            [(k, c)] if (c.re - 1.0).abs() < eps && c.im.abs() < eps => return write!(f, "|{}⟩", k),
+            _ => {}
+        }
+        for (i, (k, c)) in terms.iter().enumerate() {
+            if c.im.abs() < eps {
+                // real coefficient: fold its sign into the separator
+                match (i, c.re < 0.0) {
+                    (0, true) => write!(f, "-")?,
+                    (0, false) => {}
+                    (_, true) => write!(f, " - ")?,
+                    (_, false) => write!(f, " + ")?,
+                }
+                write!(f, "{:.*}|{}⟩", prec, c.re.abs(), k)?;
+            } else {
+                if i > 0 {
+                    write!(f, " + ")?;
+                }
+                if c.re.abs() < eps {
+                    write!(f, "{:.*}i|{}⟩", prec, c.im, k)?;
+                } else {
+                    write!(f, "({:.*}{:+.*}i)|{}⟩", prec, c.re, prec, c.im, k)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Dirac notation with [`DISPLAY_THRESHOLD`]; see [`Qid::dirac`].
+impl<T: Amplitude> fmt::Display for Qid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.dirac(DISPLAY_THRESHOLD), f)
+    }
+}
+
+pub(crate) fn check_radix(radix: usize) -> Result<(), QNumError> {
+    if (2..=MAX_RADIX).contains(&radix) {
+        Ok(())
+    } else {
+        Err(QNumError::RadixOutOfRange { radix })
+    }
+}
+
+/// Panic unless `radix` is a supported digit base.
+pub(crate) fn assert_radix(radix: usize) {
+    if let Err(e) = check_radix(radix) {
+        panic!("{}", e);
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use num_complex::Complex;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
 
     #[test]
     fn normalize_scales_to_unit_norm() {
@@ -196,6 +744,223 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn seeded_rng_gives_identical_digit_stream() {
+        let mut raw = [Complex { re: 0.0, im: 0.0 }; 10];
+        for (i, c) in raw.iter_mut().enumerate() {
+            *c = Complex { re: (i + 1) as f64, im: 0.0 };
+        }
+        let mut q = Qid::from_f64(raw);
+        q.normalize();
+
+        let mut rng_a = StdRng::seed_from_u64(42);
+        let mut rng_b = StdRng::seed_from_u64(42);
+        let a: Vec<usize> = (0..64).map(|_| q.measure_with(&mut rng_a)).collect();
+        let b: Vec<usize> = (0..64).map(|_| q.measure_with(&mut rng_b)).collect();
+        assert_eq!(a, b);
+
+        let mut qa = q.clone();
+        let mut qb = q.clone();
+        let da = qa.measure_and_collapse_with(&mut StdRng::seed_from_u64(7));
+        let db = qb.measure_and_collapse_with(&mut StdRng::seed_from_u64(7));
+        assert_eq!(da, db);
+        assert_eq!(qa, qb);
+
+        let mut rng_a = StdRng::seed_from_u64(42);
+        let mut rng_b = StdRng::seed_from_u64(42);
+        let a: Vec<usize> = (0..64).map(|_| q.measure_with_floor_with(1e-3, &mut rng_a).unwrap()).collect();
+        let b: Vec<usize> = (0..64).map(|_| q.measure_with_floor_with(1e-3, &mut rng_b).unwrap()).collect();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn collapse_deterministic_breaks_ties_low() {
+        let mut raw = [Complex { re: 0.0, im: 0.0 }; 10];
+        raw[6] = Complex { re: (0.5f64).sqrt(), im: 0.0 };
+        raw[2] = Complex { re: 0.0, im: (0.5f64).sqrt() };
+        for _ in 0..10 {
+            let mut q = Qid::from_f64(raw);
+            assert_eq!(q.collapse_deterministic(), 2);
+            assert_eq!(q, Qid::definite(2));
+        }
+    }
+
+    #[test]
+    fn fidelity_identical_and_orthogonal() {
+        assert!((Qid::definite(4).fidelity(&Qid::definite(4)) - 1.0).abs() < 1e-12);
+        assert!(Qid::definite(4).fidelity(&Qid::definite(5)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn hadamard_spreads_definite_state_uniformly() {
+        for i in [0, 3, 9] {
+            let mut q = Qid::definite(i);
+            q.hadamard();
+            let norm: f64 = q.amps.iter().map(|c| widen(c).norm_sqr()).sum();
+            assert!((norm - 1.0).abs() < 1e-12);
+            assert!((q.entropy() - (10.0f64).ln()).abs() < 1e-12);
+        }
+
+        let mut zero = Qid::definite(0);
+        zero.hadamard();
+        assert!(zero.fidelity(&Qid::uniform()) > 1.0 - 1e-12);
+    }
+
+    #[test]
+    fn pi_phase_flips_interference_outcome() {
+        // H|0⟩ = (|0⟩ + |1⟩)/√2; a second H returns |0⟩
+        let mut q = Qid::definite_in(2, 0);
+        q.hadamard();
+        q.hadamard();
+        assert!(q.amps[1].norm_sqr().into_inner() < 1e-12);
+
+        // with a π phase on the |1⟩ arm the |0⟩ paths cancel instead
+        let mut q = Qid::definite_in(2, 0);
+        q.hadamard();
+        q.phase(1, core::f64::consts::PI);
+        q.hadamard();
+        assert!(q.amps[0].norm_sqr().into_inner() < 1e-12);
+        assert!((q.amps[1].norm_sqr().into_inner() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn normalize_preserves_relative_phase() {
+        let mut q = Qid::from_f64([Complex::new(0.0, 3.0), Complex::new(-4.0, 0.0)]);
+        q.normalize();
+        assert!((q.amps[0].im.into_inner() - 0.6).abs() < 1e-12);
+        assert!((q.amps[1].re.into_inner() + 0.8).abs() < 1e-12);
+    }
+
+    #[test]
+    fn non_finite_amplitudes_are_reported() {
+        let h = 1.0 / 2f64.sqrt();
+        let mut nan = Qid::from_f64([Complex::new(h, 0.0), Complex::new(h, f64::NAN)]);
+        let mut inf = Qid::from_f64([Complex::new(f64::INFINITY, 0.0), Complex::new(h, 0.0)]);
+
+        assert_eq!(nan.validate(), Err(QNumError::NonFiniteAmplitude { basis: 1 }));
+        assert_eq!(nan.try_measure(), Err(QNumError::NonFiniteAmplitude { basis: 1 }));
+        assert_eq!(nan.try_normalize(), Err(QNumError::NonFiniteAmplitude { basis: 1 }));
+        assert_eq!(inf.try_measure(), Err(QNumError::NonFiniteAmplitude { basis: 0 }));
+        assert_eq!(inf.try_normalize(), Err(QNumError::NonFiniteAmplitude { basis: 0 }));
+        assert_eq!(nan.measure_with_floor(1e-12), Err(QNumError::NonFiniteAmplitude { basis: 1 }));
+        assert_eq!(inf.measure_with_floor(1e-12), Err(QNumError::NonFiniteAmplitude { basis: 0 }));
+
+        // the infallible normalize leaves a non-finite Qid untouched
+        let before = inf.clone();
+        inf.normalize();
+        assert_eq!(inf, before);
+
+        let zero = Qid::from_f64([Complex::new(0.0, 0.0); 2]);
+        assert_eq!(zero.try_measure(), Err(QNumError::ZeroNorm { index: 0 }));
+        assert_eq!(zero.validate(), Err(QNumError::ZeroNorm { index: 0 }));
+        assert_eq!(zero.measure_with_floor(1e-12), Err(QNumError::ZeroNorm { index: 0 }));
+        assert_eq!(Qid::definite(4).try_measure(), Ok(4));
+    }
+
+    #[test]
+    fn amplitude_damping_extremes_and_midpoint() {
+        let mut plus = Qid::definite_in(2, 0);
+        plus.hadamard();
+
+        let mut q = plus.clone();
+        q.apply_amplitude_damping(0.0);
+        assert_eq!(q, plus);
+
+        let mut q = plus.clone();
+        q.apply_amplitude_damping(1.0);
+        assert!(q.fidelity(&Qid::definite_in(2, 0)) > 1.0 - 1e-12);
+
+        let mut q = plus.clone();
+        q.apply_amplitude_damping(0.5);
+        assert!((q.amps[0].norm_sqr().into_inner() - 0.75).abs() < 1e-12);
+        assert!((q.amps[1].norm_sqr().into_inner() - 0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn measure_in_hadamard_basis_is_uniform() {
+        // row k is H|k⟩, the generalized Hadamard basis
+        let mut basis = [[Complex::new(0.0, 0.0); 10]; 10];
+        for (k, row) in basis.iter_mut().enumerate() {
+            let mut q = Qid::definite(k);
+            q.hadamard();
+            for (j, c) in q.amps.iter().enumerate() {
+                row[j] = Complex::new(c.re.into_inner(), c.im.into_inner());
+            }
+        }
+
+        let zero = Qid::definite(0);
+        let mut rng = StdRng::seed_from_u64(11);
+        let mut counts = [0usize; 10];
+        for _ in 0..10_000 {
+            counts[zero.measure_in_basis(&basis, &mut rng).unwrap()] += 1;
+        }
+        for (k, &n) in counts.iter().enumerate() {
+            assert!((800..=1200).contains(&n), "outcome {} seen {} times", k, n);
+        }
+
+        // measuring H|3⟩ in the same basis always yields 3
+        let mut h3 = Qid::definite(3);
+        h3.hadamard();
+        assert_eq!(h3.measure_in_basis(&basis, &mut rng), Ok(3));
+    }
+
+    #[test]
+    fn measure_in_basis_rejects_non_unitary() {
+        let mut basis = [[Complex::new(0.0, 0.0); 10]; 10];
+        for (k, row) in basis.iter_mut().enumerate() {
+            row[k] = Complex::new(1.0, 0.0);
+        }
+        basis[4][4] = Complex::new(2.0, 0.0);
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(
+            Qid::definite(0).measure_in_basis(&basis, &mut rng),
+            Err(QNumError::NotUnitary { row: 4, col: 4 })
+        );
+        assert_eq!(
+            Qid::definite_in(2, 0).measure_in_basis(&basis, &mut rng),
+            Err(QNumError::RadixMismatch { expected: 10, found: 2 })
+        );
+    }
+
+    #[test]
+    fn try_constructors_report_errors() {
+        assert_eq!(Qid::try_definite(7), Ok(Qid::definite(7)));
+        assert_eq!(
+            Qid::try_definite(12),
+            Err(QNumError::DigitOutOfRange { digit: 12, radix: 10 })
+        );
+        assert_eq!(
+            Qid::try_definite_in(1, 0),
+            Err(QNumError::RadixOutOfRange { radix: 1 })
+        );
+        let too_long = vec![Complex::new(0.0, 0.0); MAX_RADIX + 1];
+        assert_eq!(
+            Qid::try_from_f64_vec(too_long),
+            Err(QNumError::RadixOutOfRange { radix: MAX_RADIX + 1 })
+        );
+    }
+
+    #[test]
+    fn display_in_dirac_notation() {
+        let h = 1.0 / 2f64.sqrt();
+        let mut raw = [Complex::new(0.0, 0.0); 10];
+        raw[2] = Complex::new(h, 0.0);
+        raw[5] = Complex::new(h, 0.0);
+        let q = Qid::from_f64(raw);
+        assert_eq!(q.to_string(), "0.71|2⟩ + 0.71|5⟩");
+        assert_eq!(format!("{:.3}", q), "0.707|2⟩ + 0.707|5⟩");
+
+        let mut minus = q.clone();
+        minus.phase(5, core::f64::consts::PI);
+        assert_eq!(minus.to_string(), "0.71|2⟩ - 0.71|5⟩");
+        assert_eq!(Qid::definite(3).to_string(), "|3⟩");
+
+        raw[9] = Complex::new(1e-3, 0.0);
+        let dusty = Qid::from_f64(raw);
+        assert_eq!(dusty.dirac(1e-2).to_string(), "0.71|2⟩ + 0.71|5⟩");
+        assert_eq!(dusty.to_string(), "0.71|2⟩ + 0.71|5⟩ + 0.00|9⟩");
+    }
+
     #[test]
     fn entropy_classical_is_zero() {
         let q = Qid::definite(7);
@@ -212,4 +977,76 @@ mod tests {
         let expected = (2.0f64).ln();
         assert!((q.entropy() - expected).abs() < 1e-12);
     }
+
+    #[test]
+    fn entropy_bits_superposed_is_one() {
+        let mut raw = [Complex { re: 0.0, im: 0.0 }; 10];
+        raw[0] = Complex { re: (0.5f64).sqrt(), im: 0.0 };
+        raw[1] = Complex { re: (0.5f64).sqrt(), im: 0.0 };
+        let q = Qid::from_f64(raw);
+        assert!((q.entropy_bits() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn increment_wraps_and_cycles() {
+        let mut nine = Qid::definite(9);
+        nine.increment();
+        assert_eq!(nine, Qid::definite(0));
+
+        let mut raw = [Complex::new(0.0, 0.0); 10];
+        raw[2] = Complex::new(0.6, 0.0);
+        raw[9] = Complex::new(0.0, 0.8);
+        let original = Qid::from_f64(raw);
+        let mut q = original.clone();
+        q.increment();
+        assert_eq!(q.amps[3], original.amps[2]);
+        assert_eq!(q.amps[0], original.amps[9]);
+        for _ in 1..10 {
+            q.increment();
+        }
+        assert_eq!(q, original);
+    }
+
+    #[test]
+    fn shift_is_reversible() {
+        let original = Qid::definite_in(7, 5);
+        let mut q = original.clone();
+        q.shift(4);
+        assert_eq!(q, Qid::definite_in(7, 2));
+        q.shift(-4);
+        assert_eq!(q, original);
+        q.shift(-12);
+        assert_eq!(q, Qid::definite_in(7, 0));
+    }
+
+    #[test]
+    fn f32_qid_normalizes_and_measures() {
+        let mut q: Qid<f32> = Qid::from_amps(vec![
+            Complex::new(3.0, 0.0),
+            Complex::new(0.0, 4.0),
+            Complex::new(0.0, 0.0),
+        ]);
+        q.normalize();
+        let norm: f64 = q.amps.iter().map(|c| f64::from(c.norm_sqr().into_inner())).sum();
+        assert!((norm - 1.0).abs() < 1e-6);
+        assert!((q.amps[0].re.into_inner() - 0.6).abs() < 1e-6);
+
+        let mut rng = StdRng::seed_from_u64(5);
+        let mut counts = [0usize; 3];
+        for _ in 0..1000 {
+            counts[q.measure_with(&mut rng)] += 1;
+        }
+        assert_eq!(counts[2], 0);
+        // P(0) = 0.36
+        assert!((300..420).contains(&counts[0]), "{:?}", counts);
+
+        let wide = q.cast::<f64>();
+        assert!((wide.entropy() - q.entropy()).abs() < 1e-12);
+        assert!((q.entropy() - (-0.36f64 * 0.36f64.ln() - 0.64 * 0.64f64.ln())).abs() < 1e-6);
+
+        q.hadamard();
+        let norm: f64 = q.amps.iter().map(|c| f64::from(c.norm_sqr().into_inner())).sum();
+        assert!((norm - 1.0).abs() < 1e-6);
+        assert_eq!(q.measure_and_collapse_with(&mut rng), q.argmax());
+    }
 }
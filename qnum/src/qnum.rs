@@ -4,84 +4,1014 @@
 //! in superposition. You can construct from classical digits, build arbitrary superpositions,
 //! measure (collapse) to get classical digits, and compute joint entropy.
 
-use crate::qid::Qid;
+use crate::error::QNumError;
+use crate::measure::CollapsePolicy;
+use crate::qid::{
+    assert_radix, check_radix, Amplitude, Qid, CLASSICAL_TOLERANCE, DEFAULT_RADIX, DISPLAY_THRESHOLD,
+    MAX_RADIX,
+};
 use num_complex::Complex;
-use rand::Rng;
-use serde::{Serialize, Deserialize};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use serde::{Serialize, Serializer, Deserialize};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Deref, DerefMut};
+use alloc::collections::BTreeSet;
+use alloc::{format, string::String, vec, vec::Vec};
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::Float;
+
+/// Probability below which a digit's amplitude is treated as zero when
+/// forcing a collapse.
+pub const NEGLIGIBLE_PROBABILITY: f64 = 1e-12;
+
+/// Width in characters of a probability-1 bar in [`QNum::histogram_string`].
+pub const HISTOGRAM_WIDTH: usize = 20;
+
+/// Version tag written as the first byte of [`QNum::to_bytes`].
+pub const BINARY_FORMAT_VERSION: u8 = 1;
+
+/// Version written in the `"version"` field of a serde-serialized [`QNum`].
+///
+/// Older versions still deserialize:
+/// 1. digits only; also accepted as the bare digit array written before
+///    the representation was versioned;
+/// 2. adds an optional `"lineage"`, which is ignored on load now that
+///    lineage is carried by [`Traced`].
+pub const SERDE_FORMAT_VERSION: u32 = 2;
 
 /// A multi‐digit quantum number: most-significant `Qid` first.
-#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
-pub struct QNum(pub Vec<Qid>);
+///
+/// Digits are `Qid<T>` with `f64` amplitudes by default; `QNum<f32>` halves
+/// storage. Measurement, entropy, normalization and the arithmetic gates
+/// work at either precision; the constructors from classical values and the
+/// remaining helpers are `f64`-only, so [`cast`](QNum::cast) at the boundary.
+///
+/// # JSON schema
+///
+/// With `serde_json`, a `QNum` is an object holding the format version and
+/// an array of digits (MSB first), each an object whose `amps` array holds
+/// one `[re, im]` pair per basis value:
+/// ```text
+/// { "version": 2, "digits": [ { "amps": [[re, im], [re, im], …] }, … ] }
+/// ```
+/// The length of `amps` is the digit's radix.
+///
+/// Payloads from older versions are migrated on load; see
+/// [`SERDE_FORMAT_VERSION`].
+///
+/// For a compact wire format see [`QNum::to_bytes`].
+///
+/// # Equality and hashing
+///
+/// `Eq` and `Hash` compare [`QNum::canonical_key`]s rather than raw floats, so
+/// states that differ only by rounding noise below [`CANONICAL_GRID`] are the
+/// same `HashMap` key.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(try_from = "QNumRepr<T>", bound = "T: Amplitude")]
+pub struct QNum<T = f64>(pub Vec<Qid<T>>);
+
+/// Correlation lineage of a [`Traced`] value.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lineage {
+    /// Identifier of the tagged value.
+    pub id: u64,
+    /// Identifiers of the tagged values it has been entangled with.
+    pub partners: BTreeSet<u64>,
+}
+
+/// A value tagged with correlation [`Lineage`], normally a `Traced<QNum>`.
+///
+/// Entangling `Traced<QNum>`s with
+/// [`entangle_traced`](crate::entangle::entangle_traced) or
+/// [`entangle_all_traced`](crate::entangle::entangle_all_traced) records each
+/// one's id as a partner of the others. It derefs to the wrapped value, so
+/// reading and transforming it works as before; results of gates and other
+/// operations on it are plain, untagged values.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Traced<Q = QNum> {
+    /// The tagged value.
+    pub value: Q,
+    /// Its correlation lineage.
+    pub lineage: Lineage,
+}
+
+impl<Q> Traced<Q> {
+    /// Tag `value` with lineage `id` and no recorded partners.
+    pub fn new(value: Q, id: u64) -> Self {
+        Traced { value, lineage: Lineage { id, partners: BTreeSet::new() } }
+    }
+
+    /// Ids of the tagged values this one has been entangled with, ascending.
+    pub fn correlation_partners(&self) -> Vec<u64> {
+        self.lineage.partners.iter().copied().collect()
+    }
+
+    /// Drop the tag, returning the wrapped value.
+    pub fn into_inner(self) -> Q {
+        self.value
+    }
+}
+
+impl<Q> Deref for Traced<Q> {
+    type Target = Q;
+
+    fn deref(&self) -> &Q {
+        &self.value
+    }
+}
+
+impl<Q> DerefMut for Traced<Q> {
+    fn deref_mut(&mut self) -> &mut Q {
+        &mut self.value
+    }
+}
+
+/// Every serde form a [`QNum`] has been written in.
+#[derive(Deserialize)]
+#[serde(untagged, bound = "T: Amplitude")]
+enum QNumRepr<T> {
+    /// Version 1, written before the representation carried a version.
+    Bare(Vec<Qid<T>>),
+    Versioned {
+        version: u32,
+        digits: Vec<Qid<T>>,
+    },
+}
+
+/// Upgrade a deserialized payload to the current representation.
+impl<T: Amplitude> TryFrom<QNumRepr<T>> for QNum<T> {
+    type Error = String;
+
+    fn try_from(repr: QNumRepr<T>) -> Result<Self, Self::Error> {
+        match repr {
+            QNumRepr::Bare(digits) => Ok(QNum(digits)),
+            QNumRepr::Versioned { version: 1 | 2, digits } => Ok(QNum(digits)),
+            QNumRepr::Versioned { version, .. } => Err(format!(
+                "unsupported QNum format version {} (newest supported is {})",
+                version, SERDE_FORMAT_VERSION
+            )),
+        }
+    }
+}
+
+impl<T: Amplitude> Serialize for QNum<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        #[serde(bound = "T: Amplitude")]
+        struct Current<'a, T> {
+            version: u32,
+            digits: &'a [Qid<T>],
+        }
+
+        Current {
+            version: SERDE_FORMAT_VERSION,
+            digits: &self.0,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// A `QNum` over `digits` (most-significant first).
+impl<T: Amplitude> From<Vec<Qid<T>>> for QNum<T> {
+    fn from(digits: Vec<Qid<T>>) -> Self {
+        QNum(digits)
+    }
+}
+
+/// Grid spacing (2⁻³²) that amplitudes are snapped to by [`QNum::canonical_key`].
+pub const CANONICAL_GRID: f64 = 1.0 / 4_294_967_296.0;
+
+impl<T: Amplitude> QNum<T> {
+    /// A `QNum` over `digits` (most-significant first).
+    pub fn new(digits: Vec<Qid<T>>) -> Self {
+        QNum(digits)
+    }
+
+    /// Radix shared by this number's digits (`DEFAULT_RADIX` if empty).
+    pub fn radix(&self) -> usize {
+        self.0.first().map_or(DEFAULT_RADIX, Qid::radix)
+    }
+
+    #[cfg(feature = "std")]
+    /// Measure (collapse) each `Qid` in place, returning a classical digit vector.
+    pub fn measure(&mut self) -> Vec<u8> {
+        self.measure_with(&mut rand::thread_rng())
+    }
+
+    /// Like [`QNum::measure`], but draws from the supplied RNG so outcomes can
+    /// be reproduced from a seed.
+    pub fn measure_with<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Vec<u8> {
+        self.0.iter_mut().map(|qid| qid.measure_with(rng) as u8).collect()
+    }
+
+    /// Compute the joint entropy of the `QNum` = sum of individual digit entropies, in nats.
+    pub fn entropy(&self) -> f64 {
+        self.0.iter().map(|qid| qid.entropy()).sum()
+    }
+
+    /// Compute the joint entropy of the `QNum`, in bits.
+    pub fn entropy_bits(&self) -> f64 {
+        self.0.iter().map(|qid| qid.entropy_bits()).sum()
+    }
+
+    /// Rescale every digit to unit norm, so the joint measurement probabilities
+    /// (the products of per-digit probabilities) sum to 1.
+    ///
+    /// Corrects both float drift and the unnormalized amplitudes that
+    /// [`QNum::from_superposed`] accumulates. Zero or non-finite digits are
+    /// left unchanged, as in [`Qid::normalize`].
+    pub fn normalize(&mut self) {
+        for qid in &mut self.0 {
+            qid.normalize();
+        }
+    }
+
+    /// Quantized form of this state used for `Eq` and `Hash`: one
+    /// `(basis, re, im)` entry per amplitude of every digit (MSB first), with
+    /// the parts rounded to the nearest multiple of [`CANONICAL_GRID`] and
+    /// stored as the bits of that multiple.
+    ///
+    /// Values equal up to float noise map to the same key, except in the rare
+    /// case that they straddle a rounding boundary; `-0.0` and `0.0` coincide,
+    /// and NaN parts snap to zero.
+    pub fn canonical_key(&self) -> Vec<(u8, u64, u64)> {
+        let snap = |x: f64| (x / CANONICAL_GRID).round() as i64 as u64;
+        self.0
+            .iter()
+            .flat_map(|qid| {
+                qid.amps.iter().enumerate().map(move |(basis, c)| {
+                    (basis as u8, snap(c.re.into_inner().widen()), snap(c.im.into_inner().widen()))
+                })
+            })
+            .collect()
+    }
+
+    /// Number of digits.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if there are no digits.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Convert every digit to another amplitude precision; see [`Qid::cast`].
+    pub fn cast<U: Amplitude>(&self) -> QNum<U> {
+        QNum(self.0.iter().map(Qid::cast).collect())
+    }
+}
 
 impl QNum {
     /// Construct a `QNum` from a slice of classical digits (0…9).
+    ///
+    /// Panics if a digit is 10 or more; see [`QNum::try_from_digits`].
     pub fn from_digits(digits: &[u8]) -> Self {
+        Self::from_digits_in(DEFAULT_RADIX, digits)
+    }
+
+    /// Construct a `QNum` of the given `radix` from a slice of classical digits.
+    ///
+    /// Panics if the radix is unsupported or a digit is out of range; see
+    /// [`QNum::try_from_digits_in`].
+    pub fn from_digits_in(radix: usize, digits: &[u8]) -> Self {
+        Self::try_from_digits_in(radix, digits).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Fallible [`QNum::from_digits`]: returns [`QNumError::DigitOutOfRange`]
+    /// for the first digit of 10 or more.
+    pub fn try_from_digits(digits: &[u8]) -> Result<Self, QNumError> {
+        Self::try_from_digits_in(DEFAULT_RADIX, digits)
+    }
+
+    /// Fallible [`QNum::from_digits_in`]: returns [`QNumError::RadixOutOfRange`]
+    /// or [`QNumError::DigitOutOfRange`].
+    pub fn try_from_digits_in(radix: usize, digits: &[u8]) -> Result<Self, QNumError> {
+        check_radix(radix)?;
         let qids = digits
             .iter()
-            .map(|&d| Qid::definite(d as usize))
-            .collect();
-        QNum(qids)
+            .map(|&d| Qid::try_definite_in(radix, d as usize))
+            .collect::<Result<_, _>>()?;
+        Ok(QNum::new(qids))
+    }
+
+    /// Encode `value` as exactly `width` classical decimal digits (MSB first),
+    /// zero-padded on the left; digits above `width` are dropped, so the
+    /// result holds `value mod 10^width`.
+    pub fn from_u64(value: u64, width: usize) -> Self {
+        Self::from_u64_in(DEFAULT_RADIX, value, width)
+    }
+
+    /// Like [`QNum::from_u64`], but in base `radix`, holding
+    /// `value mod radix^width`; the inverse of [`QNum::measure_u64`].
+    ///
+    /// # Panics
+    /// If `radix` is outside `2..=MAX_RADIX`.
+    pub fn from_u64_in(radix: usize, value: u64, width: usize) -> Self {
+        assert_radix(radix);
+        let base = radix as u64;
+        let mut digits = vec![0u8; width];
+        let mut rest = value;
+        for d in digits.iter_mut().rev() {
+            *d = (rest % base) as u8;
+            rest /= base;
+        }
+        Self::from_digits_in(radix, &digits)
+    }
+
+    /// Encode `value` as exactly `width` classical decimal digits in ten's
+    /// complement (MSB first): non-negative values are zero-padded, negative
+    /// values are stored as `10^width + value`, so `-4` at width 2 is `96`.
+    ///
+    /// Signed `QNum`s are fixed-width: a leading digit of 5 or more marks a
+    /// negative number, so `width` digits hold `-5·10^(width-1) ..
+    /// 5·10^(width-1)`. Values outside that range wrap modulo `10^width`, and
+    /// operands combined with [`qsub_signed`](crate::gates::qsub_signed) must
+    /// share one width.
+    pub fn from_i64(value: i64, width: usize) -> Self {
+        Self::from_i64_in(DEFAULT_RADIX, value, width)
+    }
+
+    /// Like [`QNum::from_i64`], but in base `radix` using radix complement:
+    /// negative values are stored as `radix^width + value`, and a leading
+    /// digit of at least `radix / 2` marks a negative number, matching
+    /// [`QNum::to_i64`]. `-1` at width 4 in binary is `1111`.
+    ///
+    /// # Panics
+    /// If `radix` is outside `2..=MAX_RADIX`.
+    pub fn from_i64_in(radix: usize, value: i64, width: usize) -> Self {
+        assert_radix(radix);
+        let base = radix as i64;
+        let mut digits = vec![0u8; width];
+        let mut rest = value;
+        for d in digits.iter_mut().rev() {
+            *d = rest.rem_euclid(base) as u8;
+            rest = rest.div_euclid(base);
+        }
+        Self::from_digits_in(radix, &digits)
+    }
+
+    /// Read a classical `QNum` as a signed radix-complement integer (ten's
+    /// complement for decimal; see [`QNum::from_i64`]). A leading digit of at
+    /// least `radix / 2` marks a negative value. An empty `QNum` reads as 0.
+    ///
+    /// Returns `None` if any digit is still in superposition (its likeliest
+    /// value holds less than `1 - CLASSICAL_TOLERANCE` of the probability) or
+    /// the value does not fit in an `i64`. Unlike [`QNum::measure_u64`],
+    /// `self` is not modified.
+    pub fn to_i64(&self) -> Option<i64> {
+        let radix = self.radix() as i128;
+        let digits: Vec<i128> = self
+            .0
+            .iter()
+            .map(|qid| {
+                let probs: Vec<f64> = qid.amps.iter().map(|c| c.norm_sqr().into_inner()).collect();
+                let total: f64 = probs.iter().sum();
+                let d = qid.argmax();
+                (total > 0.0 && probs[d] / total > 1.0 - CLASSICAL_TOLERANCE).then_some(d as i128)
+            })
+            .collect::<Option<_>>()?;
+
+        // sign-extend from the leading digit, then fold; the i128 accumulator
+        // absorbs intermediate steps that undershoot i64::MIN
+        let init = match digits.first() {
+            Some(&lead) if 2 * lead >= radix => -1,
+            _ => 0,
+        };
+        let value = digits
+            .iter()
+            .try_fold(init, |acc: i128, &d| acc.checked_mul(radix)?.checked_add(d))?;
+        i64::try_from(value).ok()
     }
 
     /// Construct the zero `QNum` with `len` digits (all set to |0⟩).
     pub fn zero(len: usize) -> Self {
-        QNum(vec![Qid::definite(0); len])
+        Self::zero_in(DEFAULT_RADIX, len)
+    }
+
+    /// Construct the zero `QNum` of the given `radix` with `len` digits.
+    pub fn zero_in(radix: usize, len: usize) -> Self {
+        QNum::new(vec![Qid::definite_in(radix, 0); len])
     }
 
     /// Build a superposed `QNum` from a list of (digit‐vector, amplitude) pairs.
     ///
-    /// Each `Vec<u8>` must have the same length; amplitudes are combined per digit
-    /// then each `Qid` is normalized.
+    /// Each `Vec<u8>` must have the same length. Amplitudes are summed per
+    /// digit and stored as they are, without normalization; call
+    /// [`QNum::normalize`] on the result to get unit-norm digits.
+    ///
+    /// # Panics
+    /// On any input rejected by [`QNum::try_from_superposed_in`].
     pub fn from_superposed(states: Vec<(Vec<u8>, Complex<f64>)>) -> Self {
-        assert!(!states.is_empty(), "Cannot build empty superposition");
-        let len = states[0].0.len();
+        Self::from_superposed_in(DEFAULT_RADIX, states)
+    }
+
+    /// Like [`QNum::from_superposed`], but builds digits of the given `radix`.
+    ///
+    /// # Panics
+    /// On any input rejected by [`QNum::try_from_superposed_in`].
+    pub fn from_superposed_in(radix: usize, states: Vec<(Vec<u8>, Complex<f64>)>) -> Self {
+        Self::try_from_superposed_in(radix, states)
+            .unwrap_or_else(|e| panic!("invalid superposition: {}", e))
+    }
+
+    /// Like [`QNum::from_superposed`], but returns an error instead of panicking.
+    pub fn try_from_superposed(states: Vec<(Vec<u8>, Complex<f64>)>) -> Result<Self, QNumError> {
+        Self::try_from_superposed_in(DEFAULT_RADIX, states)
+    }
+
+    /// Fallible [`QNum::from_superposed_in`].
+    ///
+    /// Fails with [`QNumError::EmptySuperposition`] if `states` is empty,
+    /// [`QNumError::LengthMismatch`] if a basis vector's length differs from
+    /// the first one's, [`QNumError::DigitOutOfRange`] if a digit is not
+    /// below `radix`, and [`QNumError::ZeroNorm`] (or
+    /// [`QNumError::NonFiniteAmplitude`]) if a digit's summed amplitudes fail
+    /// [`Qid::validate`], e.g. because its branches cancel out.
+    pub fn try_from_superposed_in(
+        radix: usize,
+        states: Vec<(Vec<u8>, Complex<f64>)>,
+    ) -> Result<Self, QNumError> {
+        assert_radix(radix);
+        let len = states.first().ok_or(QNumError::EmptySuperposition)?.0.len();
         // initialize raw amplitude buffers per digit
-        let mut raw: Vec<[Complex<f64>; 10]> = vec![[Complex::new(0.0, 0.0); 10]; len];
+        let mut raw: Vec<Vec<Complex<f64>>> = vec![vec![Complex::new(0.0, 0.0); radix]; len];
 
         // accumulate amplitudes
         for (digits, amp) in &states {
-            assert_eq!(
-                digits.len(),
-                len,
-                "All digit vectors must have the same length"
-            );
+            if digits.len() != len {
+                return Err(QNumError::LengthMismatch {
+                    expected: len,
+                    found: digits.len(),
+                });
+            }
             for (i, &d) in digits.iter().enumerate() {
-                assert!((d as usize) < 10, "Digit {} out of range", d);
+                if d as usize >= radix {
+                    return Err(QNumError::DigitOutOfRange { digit: d as usize, radix });
+                }
                 raw[i][d as usize] += *amp;
             }
         }
 
-        // normalize and build Qids
         let qids = raw
             .into_iter()
-            .map(|amps| Qid::from_f64(amps))
+            .enumerate()
+            .map(|(index, amps)| {
+                let qid = Qid::from_f64_vec(amps);
+                match qid.validate() {
+                    Err(QNumError::ZeroNorm { .. }) => Err(QNumError::ZeroNorm { index }),
+                    other => other.map(|()| qid),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(QNum::new(qids))
+    }
+
+    /// Build a base-10 superposition from `(digits, weight)` pairs, where weights
+    /// are relative probabilities rather than amplitudes.
+    ///
+    /// See [`QNum::from_weighted_in`].
+    pub fn from_weighted(states: &[(Vec<u8>, f64)]) -> Self {
+        Self::from_weighted_in(DEFAULT_RADIX, states)
+    }
+
+    /// Like [`QNum::from_weighted`], but builds digits of the given `radix`.
+    ///
+    /// Weights are normalized to sum to 1. Each digit then gets amplitude
+    /// `√p` for its marginal probability `p` of every value, so each digit
+    /// measures with exactly the weighted frequencies (a `QNum` is a product
+    /// state, so correlations between digits are not kept).
+    ///
+    /// # Panics
+    /// If `states` is empty or ragged, a digit is out of range, or the weights
+    /// are negative, non-finite or all zero.
+    pub fn from_weighted_in(radix: usize, states: &[(Vec<u8>, f64)]) -> Self {
+        assert_radix(radix);
+        assert!(!states.is_empty(), "Cannot build empty superposition");
+        assert!(
+            states.iter().all(|(_, w)| w.is_finite() && *w >= 0.0),
+            "Weights must be finite and non-negative"
+        );
+        let total: f64 = states.iter().map(|(_, w)| w).sum();
+        assert!(total > 0.0, "Total weight must be positive");
+
+        let len = states[0].0.len();
+        let mut marginals = vec![vec![0.0; radix]; len];
+        for (digits, w) in states {
+            assert_eq!(digits.len(), len, "All digit vectors must have the same length");
+            for (marginal, &d) in marginals.iter_mut().zip(digits) {
+                assert!((d as usize) < radix, "Digit {} out of range", d);
+                marginal[d as usize] += w / total;
+            }
+        }
+
+        QNum::new(
+            marginals
+                .into_iter()
+                .map(|ps| Qid::from_f64_vec(ps.into_iter().map(|p| Complex::new(p.sqrt(), 0.0)).collect()))
+                .collect(),
+        )
+    }
+
+    /// Tensor product `self ⊗ other`: the joint register whose digits are
+    /// `self`'s followed by `other`'s.
+    ///
+    /// This is a product-state construction: every joint basis state
+    /// `|x⟩|y⟩` gets amplitude `⟨x|self⟩·⟨y|other⟩`, the outer product of the
+    /// two inputs' amplitudes, so the halves stay independent and measuring
+    /// one says nothing about the other. Use [`entangle`](crate::entangle::entangle)
+    /// to correlate registers instead.
+    pub fn tensor(&self, other: &QNum) -> QNum {
+        QNum::new(self.0.iter().chain(&other.0).cloned().collect())
+    }
+
+    /// Collapse every digit in place according to `policy`, returning the
+    /// classical outcome (most-significant first).
+    ///
+    /// `rng` is only drawn from under [`CollapsePolicy::Random`].
+    ///
+    /// # Panics
+    /// If a sampled digit cannot be measured; see [`Qid::try_measure`].
+    pub fn collapse_with<R: Rng + ?Sized>(&mut self, policy: CollapsePolicy, rng: &mut R) -> Vec<u8> {
+        match policy {
+            CollapsePolicy::Random => self
+                .0
+                .iter_mut()
+                .map(|qid| qid.measure_and_collapse_with(rng) as u8)
+                .collect(),
+            CollapsePolicy::Argmax => self
+                .0
+                .iter_mut()
+                .map(|qid| qid.collapse_deterministic() as u8)
+                .collect(),
+            CollapsePolicy::Seeded(seed) => {
+                let mut seeded = ChaCha20Rng::seed_from_u64(seed);
+                self.collapse_with(CollapsePolicy::Random, &mut seeded)
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    /// Draw `shots` independent measurements from this state and tally each
+    /// observed digit vector (most-significant first), leaving `self`
+    /// uncollapsed.
+    ///
+    /// # Panics
+    /// If any digit cannot be measured; see [`Qid::try_measure`].
+    pub fn sample_histogram<R: Rng + ?Sized>(
+        &self,
+        shots: usize,
+        rng: &mut R,
+    ) -> HashMap<Vec<u8>, usize> {
+        let mut counts = HashMap::new();
+        for _ in 0..shots {
+            let outcome: Vec<u8> = self.0.iter().map(|qid| qid.measure_with(rng) as u8).collect();
+            *counts.entry(outcome).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    #[cfg(feature = "std")]
+    /// Measure (collapse) every digit and fold the outcome into an integer in
+    /// the `QNum`'s radix. An empty `QNum` measures as 0.
+    ///
+    /// # Panics
+    /// If the measured value does not fit in a `u64`.
+    pub fn measure_u64(&mut self) -> u64 {
+        self.measure_u64_with(&mut rand::thread_rng())
+    }
+
+    /// Like [`QNum::measure_u64`], but draws from the supplied RNG.
+    ///
+    /// # Panics
+    /// If the measured value does not fit in a `u64`.
+    pub fn measure_u64_with<R: Rng + ?Sized>(&mut self, rng: &mut R) -> u64 {
+        let radix = self.radix() as u64;
+        self.measure_with(rng).iter().fold(0u64, |acc, &d| {
+            acc.checked_mul(radix)
+                .and_then(|v| v.checked_add(d as u64))
+                .expect("QNum measurement overflows u64")
+        })
+    }
+
+    #[cfg(feature = "std")]
+    /// Measure and collapse only the digit at `index` (most-significant first),
+    /// leaving every other digit in superposition.
+    ///
+    /// A `QNum` is stored as a product of independent digits, so collapsing
+    /// one digit has no effect on the others. Correlations set up by
+    /// [`entangle_all`](crate::entangle::entangle_all) live in the returned
+    /// [`JointState`](crate::entangle::JointState); measure through
+    /// [`measure_digit_in`](crate::measure::measure_digit_in) to collapse the
+    /// digits correlated with this one as well.
+    pub fn measure_digit(&mut self, index: usize) -> Result<u8, QNumError> {
+        self.measure_digit_with(index, &mut rand::thread_rng())
+    }
+
+    /// Like [`QNum::measure_digit`], but draws from the supplied RNG.
+    pub fn measure_digit_with<R: Rng + ?Sized>(&mut self, index: usize, rng: &mut R) -> Result<u8, QNumError> {
+        let len = self.len();
+        let qid = self
+            .0
+            .get_mut(index)
+            .ok_or(QNumError::IndexOutOfRange { index, len })?;
+        Ok(qid.measure_and_collapse_with(rng) as u8)
+    }
+
+    /// Force collapse onto the basis state `digits` (most-significant first).
+    ///
+    /// Fails without modifying `self` if `digits` has the wrong length, if
+    /// `self`'s digits do not share one radix ([`QNumError::RadixMismatch`]),
+    /// if `digits` contains a value outside that radix, or if it names a state
+    /// whose amplitude is negligible (probability below
+    /// [`NEGLIGIBLE_PROBABILITY`] in any digit).
+    pub fn collapse_to(&mut self, digits: &[u8]) -> Result<(), QNumError> {
+        if digits.len() != self.len() {
+            return Err(QNumError::LengthMismatch {
+                expected: self.len(),
+                found: digits.len(),
+            });
+        }
+        let radix = self.radix();
+        if let Some(qid) = self.0.iter().find(|qid| qid.radix() != radix) {
+            return Err(QNumError::RadixMismatch { expected: radix, found: qid.radix() });
+        }
+        for (index, (qid, &d)) in self.0.iter().zip(digits).enumerate() {
+            let amp = qid.amps.get(d as usize).ok_or(QNumError::DigitOutOfRange {
+                digit: d as usize,
+                radix: qid.radix(),
+            })?;
+            if amp.norm_sqr().into_inner() < NEGLIGIBLE_PROBABILITY {
+                return Err(QNumError::ZeroAmplitude { index });
+            }
+        }
+        for (qid, &d) in self.0.iter_mut().zip(digits) {
+            *qid = Qid::definite_in(qid.radix(), d as usize);
+        }
+        Ok(())
+    }
+
+    /// Apply [`Qid::apply_amplitude_damping`] to every digit, modelling
+    /// decoherence toward `|0…0⟩`.
+    pub fn apply_amplitude_damping(&mut self, gamma: f64) {
+        for qid in &mut self.0 {
+            qid.apply_amplitude_damping(gamma);
+        }
+    }
+
+    /// Rotate the digit positions left by `n` (wrapping), e.g. `[1,2,3]` → `[2,3,1]`
+    /// for `n = 1`. Each `Qid` moves with its amplitudes intact.
+    pub fn rotate_left(&self, n: usize) -> QNum {
+        let mut qids = self.0.clone();
+        if !qids.is_empty() {
+            let len = qids.len();
+            qids.rotate_left(n % len);
+        }
+        QNum::new(qids)
+    }
+
+    /// Rotate the digit positions right by `n` (wrapping), e.g. `[1,2,3]` → `[3,1,2]`
+    /// for `n = 1`. Each `Qid` moves with its amplitudes intact.
+    pub fn rotate_right(&self, n: usize) -> QNum {
+        let mut qids = self.0.clone();
+        if !qids.is_empty() {
+            let len = qids.len();
+            qids.rotate_right(n % len);
+        }
+        QNum::new(qids)
+    }
+
+    /// Return the complex amplitude of basis value `basis` at digit position
+    /// `digit_index` (most-significant first), or `None` if either is out of range.
+    pub fn amplitude(&self, digit_index: usize, basis: usize) -> Option<Complex<f64>> {
+        let c = self.0.get(digit_index)?.amps.get(basis)?;
+        Some(Complex::new(c.re.into_inner(), c.im.into_inner()))
+    }
+
+    /// Multiply the amplitude of basis value `basis_digit` at position
+    /// `digit_index` (most-significant first) by `e^{iθ}`; see [`Qid::phase`].
+    pub fn phase(&mut self, digit_index: usize, basis_digit: usize, radians: f64) -> Result<(), QNumError> {
+        let len = self.len();
+        let qid = self
+            .0
+            .get_mut(digit_index)
+            .ok_or(QNumError::IndexOutOfRange { index: digit_index, len })?;
+        if basis_digit >= qid.radix() {
+            return Err(QNumError::DigitOutOfRange {
+                digit: basis_digit,
+                radix: qid.radix(),
+            });
+        }
+        qid.phase(basis_digit, radians);
+        Ok(())
+    }
+
+    /// Increment the digit at `index` (most-significant first) mod its radix,
+    /// without carrying into its neighbour; see [`Qid::increment`].
+    pub fn increment_digit(&mut self, index: usize) -> Result<(), QNumError> {
+        let len = self.len();
+        let qid = self
+            .0
+            .get_mut(index)
+            .ok_or(QNumError::IndexOutOfRange { index, len })?;
+        qid.increment();
+        Ok(())
+    }
+
+    /// Complex overlap `⟨self|other⟩`: the product of per-digit
+    /// [`Qid::inner_product`]s, keeping the relative phase that
+    /// [`QNum::fidelity`] discards.
+    ///
+    /// Registers of different length are rejected with
+    /// [`QNumError::LengthMismatch`] rather than zero-padded, since padding
+    /// would silently equate e.g. `|12⟩` with `|012⟩`.
+    pub fn inner_product(&self, other: &QNum) -> Result<Complex<f64>, QNumError> {
+        if self.len() != other.len() {
+            return Err(QNumError::LengthMismatch {
+                expected: self.len(),
+                found: other.len(),
+            });
+        }
+        Ok(self
+            .0
+            .iter()
+            .zip(&other.0)
+            .map(|(a, b)| a.inner_product(b))
+            .product())
+    }
+
+    /// Fidelity between two `QNum`s: the product of per-digit [`Qid::fidelity`]s,
+    /// i.e. the joint overlap of the two product states.
+    ///
+    /// Returns 0.0 if the lengths differ.
+    pub fn fidelity(&self, other: &QNum) -> f64 {
+        if self.len() != other.len() {
+            return 0.0;
+        }
+        self.0
+            .iter()
+            .zip(&other.0)
+            .map(|(a, b)| a.fidelity(b))
+            .product()
+    }
+
+    /// Fix the phase convention of every digit with [`Qid::canonicalize`], so
+    /// the first non-negligible amplitude of each digit is real and positive.
+    ///
+    /// A global phase on a product state can be spread over its digits in any
+    /// way, so fixing each digit's phase makes states that differ only by a
+    /// global phase compare equal and hash alike.
+    pub fn canonicalize(&mut self) {
+        for qid in &mut self.0 {
+            qid.canonicalize();
+        }
+    }
+
+    /// Render each digit's measurement distribution as an ASCII bar chart,
+    /// one block per digit position (most-significant first):
+    /// ```text
+    /// digit 0:
+    ///     2 |##########          | 0.500
+    ///     5 |##########          | 0.500
+    /// ```
+    /// Probabilities are normalized per digit; values below
+    /// [`NEGLIGIBLE_PROBABILITY`] are omitted. A probability-1 bar is
+    /// [`HISTOGRAM_WIDTH`] characters.
+    pub fn histogram_string(&self) -> String {
+        let mut out = String::new();
+        for (index, qid) in self.0.iter().enumerate() {
+            out.push_str(&format!("digit {}:\n", index));
+            let probs: Vec<f64> = qid.amps.iter().map(|c| c.norm_sqr().into_inner()).collect();
+            let total: f64 = probs.iter().sum();
+            if total <= 0.0 {
+                continue;
+            }
+            for (value, p) in probs.into_iter().enumerate() {
+                let p = p / total;
+                if p < NEGLIGIBLE_PROBABILITY {
+                    continue;
+                }
+                let bar = "#".repeat((p * HISTOGRAM_WIDTH as f64).round() as usize);
+                out.push_str(&format!(
+                    "  {:>3} |{:<width$}| {:.3}\n",
+                    value,
+                    bar,
+                    p,
+                    width = HISTOGRAM_WIDTH
+                ));
+            }
+        }
+        out
+    }
+
+    /// Largest per-digit normalization error, `max_i |1 - Σ|amps_i|²|`.
+    ///
+    /// Zero for a well-formed `QNum`; grows as floating error accumulates
+    /// through repeated gate applications. Returns 0.0 for an empty `QNum`.
+    pub fn norm_defect(&self) -> f64 {
+        self.0
+            .iter()
+            .map(|qid| {
+                let norm: f64 = qid.amps.iter().map(|c| c.norm_sqr().into_inner()).sum();
+                (1.0 - norm).abs()
+            })
+            .fold(0.0, f64::max)
+    }
+
+    /// Encode this `QNum` in the compact binary format (all integers and
+    /// floats little-endian):
+    /// ```text
+    /// u8  version (= BINARY_FORMAT_VERSION)
+    /// u32 digit count
+    /// per digit: u16 radix, then radix × (f64 re, f64 im)
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let amp_count: usize = self.0.iter().map(|qid| qid.radix()).sum();
+        let mut out = Vec::with_capacity(5 + 2 * self.len() + 16 * amp_count);
+        out.push(BINARY_FORMAT_VERSION);
+        out.extend_from_slice(&(self.len() as u32).to_le_bytes());
+        for qid in &self.0 {
+            out.extend_from_slice(&(qid.radix() as u16).to_le_bytes());
+            for c in &qid.amps {
+                out.extend_from_slice(&c.re.into_inner().to_le_bytes());
+                out.extend_from_slice(&c.im.into_inner().to_le_bytes());
+            }
+        }
+        out
+    }
+
+    /// Decode a `QNum` written by [`QNum::to_bytes`].
+    ///
+    /// Fails with [`QNumError::InvalidEncoding`] on an unknown version, an
+    /// unsupported radix, truncated input or trailing bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, QNumError> {
+        let mut pos = 0;
+        let mut take = |n: usize| -> Result<(usize, &[u8]), QNumError> {
+            let chunk = bytes
+                .get(pos..pos + n)
+                .ok_or(QNumError::InvalidEncoding { offset: pos })?;
+            let start = pos;
+            pos += n;
+            Ok((start, chunk))
+        };
+
+        let (_, version) = take(1)?;
+        if version[0] != BINARY_FORMAT_VERSION {
+            return Err(QNumError::InvalidEncoding { offset: 0 });
+        }
+        let (_, count) = take(4)?;
+        let count = u32::from_le_bytes(count.try_into().unwrap()) as usize;
+
+        let mut qids = Vec::with_capacity(count.min(bytes.len()));
+        for _ in 0..count {
+            let (at, radix) = take(2)?;
+            let radix = u16::from_le_bytes(radix.try_into().unwrap()) as usize;
+            if !(2..=MAX_RADIX).contains(&radix) {
+                return Err(QNumError::InvalidEncoding { offset: at });
+            }
+            let mut amps = Vec::with_capacity(radix);
+            for _ in 0..radix {
+                let (_, re) = take(8)?;
+                let re = f64::from_le_bytes(re.try_into().unwrap());
+                let (_, im) = take(8)?;
+                let im = f64::from_le_bytes(im.try_into().unwrap());
+                amps.push(Complex::new(re, im));
+            }
+            qids.push(Qid::from_f64_vec(amps));
+        }
+        if pos != bytes.len() {
+            return Err(QNumError::InvalidEncoding { offset: pos });
+        }
+        Ok(QNum::new(qids))
+    }
+
+    /// Compact difference from `self` to `other`: only the digits (by
+    /// position, MSB first) whose amplitudes differ, plus `other`'s length.
+    ///
+    /// Digits are compared exactly, so [`QNum::apply_patch`] rebuilds `other`
+    /// bit for bit. Digits past the end of `self` are always included.
+    pub fn diff(&self, other: &QNum) -> QNumPatch {
+        let digits = other
+            .0
+            .iter()
+            .enumerate()
+            .filter(|&(i, qid)| self.0.get(i) != Some(qid))
+            .map(|(i, qid)| (i, qid.clone()))
             .collect();
+        QNumPatch { len: other.len(), digits }
+    }
 
-        QNum(qids)
+    /// Rebuild the target of `patch` from `self`; see [`QNum::diff`].
+    ///
+    /// # Panics
+    /// As [`QNum::try_apply_patch`].
+    pub fn apply_patch(&self, patch: &QNumPatch) -> QNum {
+        self.try_apply_patch(patch).unwrap_or_else(|e| panic!("{}", e))
     }
 
-    /// Measure (collapse) each `Qid` in place, returning a classical digit vector.
-    pub fn measure(&mut self) -> Vec<u8> {
-        self.0.iter_mut().map(|qid| qid.measure() as u8).collect()
+    /// Fallible [`QNum::apply_patch`]: truncate or extend `self` to
+    /// `patch.len` digits and overwrite the patched positions.
+    ///
+    /// Fails with [`QNumError::IndexOutOfRange`] if a patched position is not
+    /// below `patch.len`, or [`QNumError::MissingDigit`] if the result would
+    /// keep a digit that neither `self` nor the patch provides.
+    pub fn try_apply_patch(&self, patch: &QNumPatch) -> Result<QNum, QNumError> {
+        let mut digits: Vec<Option<Qid>> = self.0.iter().cloned().map(Some).collect();
+        digits.resize(patch.len, None);
+        for (index, qid) in &patch.digits {
+            let slot = digits.get_mut(*index).ok_or(QNumError::IndexOutOfRange {
+                index: *index,
+                len: patch.len,
+            })?;
+            *slot = Some(qid.clone());
+        }
+        digits
+            .into_iter()
+            .enumerate()
+            .map(|(index, qid)| qid.ok_or(QNumError::MissingDigit { index }))
+            .collect::<Result<Vec<_>, _>>()
+            .map(QNum::new)
     }
 
-    /// Compute the joint entropy of the `QNum` = sum of individual digit entropies.
-    pub fn entropy(&self) -> f64 {
-        self.0.iter().map(|qid| qid.entropy()).sum()
+}
+
+/// Changed digits between two `QNum`s, produced by [`QNum::diff`] and
+/// applied with [`QNum::apply_patch`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QNumPatch {
+    /// Number of digits in the target `QNum`.
+    pub len: usize,
+    /// `(position, digit)` for every digit that differs from the base, MSB first.
+    pub digits: Vec<(usize, Qid)>,
+}
+
+impl<T: Amplitude> PartialEq for QNum<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.canonical_key() == other.canonical_key()
     }
+}
 
-    /// Number of digits.
-    pub fn len(&self) -> usize {
-        self.0.len()
+impl<T: Amplitude> Eq for QNum<T> {}
+
+impl<T: Amplitude> Hash for QNum<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.canonical_key().hash(state);
     }
+}
 
-    /// Returns true if there are no digits.
-    pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+/// Dirac-notation view of a [`QNum`] with a custom amplitude threshold; see
+/// [`QNum::dirac`].
+#[derive(Clone, Copy, Debug)]
+pub struct DiracQNum<'a> {
+    qnum: &'a QNum,
+    threshold: f64,
+}
+
+impl QNum {
+    /// Format this register as the tensor product of its digits (MSB first),
+    /// e.g. `(0.71|2⟩ + 0.71|5⟩) ⊗ |3⟩`, omitting basis states whose
+    /// amplitude magnitude is below `threshold`. See [`Qid::dirac`].
+    pub fn dirac(&self, threshold: f64) -> DiracQNum<'_> {
+        DiracQNum { qnum: self, threshold }
+    }
+}
+
+impl fmt::Display for DiracQNum<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prec = f.precision().unwrap_or(2);
+        let digits = &self.qnum.0;
+        if digits.is_empty() {
+            return write!(f, "|⟩");
+        }
+        for (i, qid) in digits.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ⊗ ")?;
+            }
+            let term = qid.dirac(self.threshold);
+            if digits.len() > 1 && qid.dirac_terms(self.threshold) > 1 {
+                write!(f, "({:.*})", prec, term)?;
+            } else {
+                write!(f, "{:.*}", prec, term)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Dirac notation with [`DISPLAY_THRESHOLD`]; see [`QNum::dirac`].
+impl fmt::Display for QNum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.dirac(DISPLAY_THRESHOLD), f)
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use num_complex::Complex;
@@ -95,6 +1025,128 @@ mod tests {
         assert_eq!(measured, digits);
     }
 
+    /// from_u64 → measure_u64 round-trips, keeping leading zeros in the width.
+    #[test]
+    fn u64_roundtrip() {
+        for (value, width) in [(0, 1), (7, 1), (12, 2), (42, 5), (90_210, 5), (u64::MAX, 20)] {
+            let mut qnum = QNum::from_u64(value, width);
+            assert_eq!(qnum.len(), width);
+            assert_eq!(qnum.measure_u64(), value);
+        }
+        assert_eq!(QNum::from_u64(42, 5), QNum::from_digits(&[0, 0, 0, 4, 2]));
+        // only the low `width` digits are kept
+        assert_eq!(QNum::from_u64(1234, 2).measure_u64(), 34);
+
+        // other radices round-trip through the same width rules
+        assert_eq!(QNum::from_u64_in(2, 6, 4), QNum::from_digits_in(2, &[0, 1, 1, 0]));
+        assert_eq!(QNum::from_u64_in(16, 0xbeef, 4).measure_u64(), 0xbeef);
+        assert_eq!(QNum::from_u64_in(2, 0b1_0110, 3).measure_u64(), 0b110);
+    }
+
+    /// try_from_digits reports out-of-range digits instead of panicking.
+    #[test]
+    fn try_from_digits_reports_errors() {
+        assert_eq!(QNum::try_from_digits(&[3, 1, 4]), Ok(QNum::from_digits(&[3, 1, 4])));
+        assert_eq!(
+            QNum::try_from_digits(&[3, 10, 4]),
+            Err(QNumError::DigitOutOfRange { digit: 10, radix: 10 })
+        );
+        assert_eq!(
+            QNum::try_from_digits_in(2, &[1, 0, 2]),
+            Err(QNumError::DigitOutOfRange { digit: 2, radix: 2 })
+        );
+        assert_eq!(
+            QNum::try_from_digits_in(0, &[]),
+            Err(QNumError::RadixOutOfRange { radix: 0 })
+        );
+    }
+
+    /// from_i64 → to_i64 round-trips through ten's complement.
+    #[test]
+    fn i64_roundtrip() {
+        for (value, width) in [(0, 1), (4, 1), (-4, 1), (-5, 1), (-4, 2), (49, 2), (-50, 2), (-123_456, 8)] {
+            assert_eq!(QNum::from_i64(value, width).to_i64(), Some(value));
+        }
+        assert_eq!(QNum::from_i64(-4, 2), QNum::from_digits(&[9, 6]));
+        assert_eq!(QNum::from_i64(i64::MIN, 20).to_i64(), Some(i64::MIN));
+        // out-of-range values wrap modulo 10^width
+        assert_eq!(QNum::from_i64(7, 1).to_i64(), Some(-3));
+        // binary two's complement
+        assert_eq!(QNum::from_digits_in(2, &[1, 1, 0]).to_i64(), Some(-2));
+        assert_eq!(QNum::from_i64_in(2, -2, 3), QNum::from_digits_in(2, &[1, 1, 0]));
+        for (radix, value, width) in [(2, -1, 4), (2, 7, 4), (2, -8, 4), (16, -0x7f, 2), (3, -4, 3)] {
+            assert_eq!(QNum::from_i64_in(radix, value, width).to_i64(), Some(value));
+        }
+
+        let h = Complex::new(1.0 / 2f64.sqrt(), 0.0);
+        assert_eq!(QNum::from_superposed(vec![(vec![1], h), (vec![2], h)]).to_i64(), None);
+        assert_eq!(QNum::from_u64(u64::MAX, 20).to_i64(), None);
+    }
+
+    /// A superposed QNum with complex amplitudes survives JSON and binary round-trips.
+    #[test]
+    fn serde_and_binary_roundtrip() {
+        let mut qnum = QNum::from_superposed(vec![
+            (vec![1, 2, 0], Complex::new(0.6, 0.0)),
+            (vec![7, 2, 9], Complex::new(0.0, 0.8)),
+        ]);
+        qnum.phase(2, 9, 1.234).unwrap();
+
+        let close = |a: &QNum, b: &QNum| {
+            a.len() == b.len()
+                && a.0.iter().zip(&b.0).all(|(x, y)| {
+                    x.amps.len() == y.amps.len()
+                        && x.amps.iter().zip(&y.amps).all(|(p, q)| {
+                            (p.re.into_inner() - q.re.into_inner()).abs() < 1e-12
+                                && (p.im.into_inner() - q.im.into_inner()).abs() < 1e-12
+                        })
+                })
+        };
+
+        let json = serde_json::to_string(&qnum).unwrap();
+        let from_json: QNum = serde_json::from_str(&json).unwrap();
+        assert!(close(&qnum, &from_json), "JSON round-trip drifted: {}", json);
+
+        let bytes = qnum.to_bytes();
+        assert_eq!(bytes.len(), 5 + 3 * (2 + 10 * 16));
+        let from_bin = QNum::from_bytes(&bytes).unwrap();
+        assert!(close(&qnum, &from_bin));
+
+        assert_eq!(
+            QNum::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(QNumError::InvalidEncoding { offset: bytes.len() - 8 })
+        );
+        assert_eq!(QNum::from_bytes(&[]), Err(QNumError::InvalidEncoding { offset: 0 }));
+    }
+
+    /// Version-1 and bare-array JSON still deserialize; unknown versions are rejected.
+    #[test]
+    fn deserializes_older_format_versions() {
+        let digits = r#"[{"amps":[[0.0,0.0],[1.0,0.0]]},{"amps":[[1.0,0.0],[0.0,0.0]]}]"#;
+        let expected = QNum::from_digits_in(2, &[1, 0]);
+
+        let v1: QNum = serde_json::from_str(&format!(r#"{{"version":1,"digits":{}}}"#, digits)).unwrap();
+        assert_eq!(v1, expected);
+        let bare: QNum = serde_json::from_str(digits).unwrap();
+        assert_eq!(bare, expected);
+
+        let json = serde_json::to_string(&v1).unwrap();
+        assert!(json.starts_with(&format!(r#"{{"version":{},"#, SERDE_FORMAT_VERSION)));
+        assert!(!json.contains("lineage"));
+
+        // version 2 written while QNum carried lineage still loads
+        let tagged: QNum = serde_json::from_str(&format!(
+            r#"{{"version":2,"digits":{},"lineage":{{"id":1,"partners":[2]}}}}"#,
+            digits
+        ))
+        .unwrap();
+        assert_eq!(tagged, expected);
+
+        let err = serde_json::from_str::<QNum>(&format!(r#"{{"version":99,"digits":{}}}"#, digits))
+            .unwrap_err();
+        assert!(err.to_string().contains("unsupported QNum format version 99"));
+    }
+
     /// Test that zero(len) measures to all zeros.
     #[test]
     fn zero_measures_zero() {
@@ -103,16 +1155,156 @@ mod tests {
         assert_eq!(measured, vec![0, 0, 0, 0]);
     }
 
-    /// Test that from_superposed of two classical states collapses to one of them.
+    /// Test that a superposition of two classical states collapses to one of
+    /// them. `from_superposed` combines amplitudes per digit, so only the
+    /// joint state keeps the patterns together; the `QNum` measures the
+    /// product of the two positions' supports.
     #[test]
     fn superposed_measure_valid() {
+        use crate::entangle::JointState;
+        use rand::{rngs::StdRng, SeedableRng};
+        use std::collections::HashSet;
+
+        let states = vec![
+            (vec![1, 2], Complex::new(1.0 / 2f64.sqrt(), 0.0)),
+            (vec![9, 8], Complex::new(1.0 / 2f64.sqrt(), 0.0)),
+        ];
+        let joint = JointState::from_amplitudes(DEFAULT_RADIX, vec![2], states.clone()).unwrap();
+        let qnum = QNum::from_superposed(states);
+
+        let mut rng = StdRng::seed_from_u64(1762);
+        let (mut patterns, mut products) = (HashSet::new(), HashSet::new());
+        for _ in 0..200 {
+            let m = joint.measure_with(&mut rng).remove(0);
+            assert!(m == vec![1, 2] || m == vec![9, 8], "outcome {:?}", m);
+            patterns.insert(m);
+            products.insert(qnum.clone().measure_with(&mut rng));
+        }
+        assert_eq!(patterns.len(), 2);
+        let expected: HashSet<Vec<u8>> =
+            [vec![1, 2], vec![1, 8], vec![9, 2], vec![9, 8]].into_iter().collect();
+        assert_eq!(products, expected);
+    }
+
+    /// Ragged or out-of-range basis states are rejected rather than building a malformed QNum.
+    #[test]
+    fn try_from_superposed_validates_basis_states() {
+        let amp = Complex::new(1.0 / 2f64.sqrt(), 0.0);
+        assert_eq!(
+            QNum::try_from_superposed(vec![(vec![1, 2], amp), (vec![3, 4, 5], amp)]),
+            Err(QNumError::LengthMismatch { expected: 2, found: 3 })
+        );
+        assert_eq!(
+            QNum::try_from_superposed_in(2, vec![(vec![0, 2], amp)]),
+            Err(QNumError::DigitOutOfRange { digit: 2, radix: 2 })
+        );
+        assert_eq!(QNum::try_from_superposed(vec![]), Err(QNumError::EmptySuperposition));
+
+        let ok = QNum::try_from_superposed(vec![(vec![1, 2], amp), (vec![3, 4], amp)]).unwrap();
+        assert_eq!(ok.len(), 2);
+
+        // the tens digit's branches cancel, leaving nothing to measure
+        assert_eq!(
+            QNum::try_from_superposed(vec![(vec![0, 2], amp), (vec![0, 3], -amp)]),
+            Err(QNumError::ZeroNorm { index: 0 })
+        );
+    }
+
+    /// Weights 3:1 measure as roughly 75% / 25%.
+    #[test]
+    fn from_weighted_matches_frequencies() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let q = QNum::from_weighted(&[(vec![4], 3.0), (vec![9], 1.0)]);
+        assert!(q.norm_defect() < 1e-12);
+
+        let mut rng = StdRng::seed_from_u64(3);
+        let trials = 10_000;
+        let fours = (0..trials)
+            .filter(|_| q.clone().measure_with(&mut rng) == vec![4])
+            .count();
+        let freq = fours as f64 / trials as f64;
+        assert!((freq - 0.75).abs() < 0.02, "observed {}", freq);
+    }
+
+    /// collapse_to picks a branch with support and rejects everything else.
+    #[test]
+    fn collapse_to_forces_branch() {
         let states = vec![
             (vec![1, 2], Complex::new(1.0 / 2f64.sqrt(), 0.0)),
             (vec![9, 8], Complex::new(1.0 / 2f64.sqrt(), 0.0)),
         ];
         let mut qnum = QNum::from_superposed(states);
-        let m = qnum.measure();
-        assert!(m == vec![1, 2] || m == vec![9, 8]);
+        let original = qnum.clone();
+
+        assert_eq!(
+            qnum.collapse_to(&[1]),
+            Err(QNumError::LengthMismatch { expected: 2, found: 1 })
+        );
+        assert_eq!(qnum.collapse_to(&[1, 5]), Err(QNumError::ZeroAmplitude { index: 1 }));
+        assert_eq!(
+            qnum.collapse_to(&[1, 12]),
+            Err(QNumError::DigitOutOfRange { digit: 12, radix: 10 })
+        );
+        assert_eq!(qnum, original, "failed collapse must leave the state untouched");
+
+        qnum.collapse_to(&[9, 8]).unwrap();
+        assert_eq!(qnum, QNum::from_digits(&[9, 8]));
+        assert_eq!(qnum.measure(), vec![9, 8]);
+
+        // a register whose digits disagree on radix is rejected outright
+        let mut ragged = QNum::new(vec![Qid::uniform(), Qid::definite_in(2, 1)]);
+        let before = ragged.clone();
+        assert_eq!(
+            ragged.collapse_to(&[1, 1]),
+            Err(QNumError::RadixMismatch { expected: 10, found: 2 })
+        );
+        assert_eq!(ragged, before);
+    }
+
+    /// Measuring one digit leaves the rest superposed.
+    #[test]
+    fn measure_digit_collapses_only_that_digit() {
+        // each digit ends up as an equal mix of two values
+        let amp = Complex::new(1.0 / 8f64.sqrt(), 0.0);
+        let mut qnum = QNum::from_superposed(vec![
+            (vec![1, 2], amp),
+            (vec![1, 8], amp),
+            (vec![6, 2], amp),
+            (vec![6, 8], amp),
+        ]);
+        let d = qnum.measure_digit(0).unwrap();
+        assert!(d == 1 || d == 6);
+        assert_eq!(qnum.0[0].entropy(), 0.0);
+        assert!(qnum.0[1].entropy() > 0.0);
+        assert_eq!(
+            qnum.measure_digit(2),
+            Err(QNumError::IndexOutOfRange { index: 2, len: 2 })
+        );
+    }
+
+    /// A seeded partial measurement is reproducible and still leaves the
+    /// other digits superposed.
+    #[test]
+    fn measure_digit_with_is_reproducible() {
+        use rand::{rngs::StdRng, SeedableRng};
+        let amp = Complex::new(1.0 / 8f64.sqrt(), 0.0);
+        let qnum = QNum::from_superposed(vec![
+            (vec![1, 2], amp),
+            (vec![1, 8], amp),
+            (vec![6, 2], amp),
+            (vec![6, 8], amp),
+        ]);
+        for seed in 0..8 {
+            let (mut a, mut b) = (qnum.clone(), qnum.clone());
+            let da = a.measure_digit_with(1, &mut StdRng::seed_from_u64(seed)).unwrap();
+            let db = b.measure_digit_with(1, &mut StdRng::seed_from_u64(seed)).unwrap();
+            assert_eq!(da, db);
+            assert_eq!(a, b);
+            assert!(a.0[0].entropy() > 0.0);
+            assert_eq!(a.0[1].entropy(), 0.0);
+        }
     }
 
     /// Entropy of a classical QNum is zero.
@@ -122,6 +1314,347 @@ mod tests {
         assert!((qnum.entropy() - 0.0).abs() < 1e-12);
     }
 
+    /// Amplitudes can be read back per digit, with bounds checks.
+    #[test]
+    fn amplitude_reads_back() {
+        let amp = Complex::new(1.0 / 2f64.sqrt(), 0.0);
+        let qnum = QNum::from_superposed(vec![(vec![3, 0], amp), (vec![6, 0], amp)]);
+        let a = qnum.amplitude(0, 3).unwrap();
+        assert!((a.re - 1.0 / 2f64.sqrt()).abs() < 1e-12);
+        assert_eq!(qnum.amplitude(0, 4), Some(Complex::new(0.0, 0.0)));
+        // amplitudes for a shared digit accumulate (from_superposed does not renormalize)
+        assert!((qnum.amplitude(1, 0).unwrap().re - 2f64.sqrt()).abs() < 1e-12);
+        assert_eq!(qnum.amplitude(2, 0), None);
+        assert_eq!(qnum.amplitude(0, 10), None);
+    }
+
+    /// Tensoring two single-digit superpositions gives four equal joint states.
+    #[test]
+    fn tensor_multiplies_amplitudes() {
+        let h = 1.0 / 2f64.sqrt();
+        let a = QNum::from_superposed(vec![(vec![1], Complex::new(h, 0.0)), (vec![4], Complex::new(h, 0.0))]);
+        let b = QNum::from_superposed(vec![(vec![0], Complex::new(h, 0.0)), (vec![9], Complex::new(h, 0.0))]);
+        let ab = a.tensor(&b);
+        assert_eq!(ab.len(), 2);
+
+        let states: Vec<_> = crate::gates::enumerate(&ab).collect();
+        let digits: Vec<_> = states.iter().map(|(d, _)| d.clone()).collect();
+        assert_eq!(digits, vec![vec![1, 0], vec![1, 9], vec![4, 0], vec![4, 9]]);
+        for (_, amp) in &states {
+            assert!((amp.re - 0.5).abs() < 1e-12 && amp.im.abs() < 1e-12);
+        }
+    }
+
+    /// Tensoring classical registers concatenates them; the halves stay independent.
+    #[test]
+    fn tensor_is_product_state() {
+        let mut joint = QNum::from_digits(&[3, 1]).tensor(&QNum::from_digits(&[4]));
+        assert_eq!(joint.measure(), vec![3, 1, 4]);
+
+        let h = Complex::new(1.0 / 2f64.sqrt(), 0.0);
+        let a = QNum::from_superposed(vec![(vec![2], h), (vec![6], h)]);
+        let b = QNum::from_superposed(vec![(vec![0], Complex::new(0.6, 0.0)), (vec![9], Complex::new(0.8, 0.0))]);
+        let ab = crate::entangle::joint_distribution(&[&a.tensor(&b)]).unwrap();
+        for x in [2u8, 6] {
+            for (y, py) in [(0u8, 0.36), (9, 0.64)] {
+                assert!((ab.probability(&[x, y]) - 0.5 * py).abs() < 1e-12);
+            }
+        }
+    }
+
+    /// States equal up to float noise compare and hash equal.
+    #[test]
+    fn canonical_key_absorbs_float_noise() {
+        use std::collections::hash_map::DefaultHasher;
+        let h = Complex::new(1.0 / 2f64.sqrt(), 0.0);
+        let direct = QNum::from_superposed(vec![(vec![2], h), (vec![5], h)]);
+        // same state via a full-turn phase, which leaves rounding dust
+        let mut rotated = QNum::from_superposed(vec![(vec![2], h), (vec![5], h)]);
+        rotated.phase(0, 5, 2.0 * core::f64::consts::PI).unwrap();
+        assert_ne!(direct.0, rotated.0, "raw amplitudes should differ");
+
+        assert_eq!(direct, rotated);
+        let hash = |q: &QNum| {
+            let mut hasher = DefaultHasher::new();
+            q.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&direct), hash(&rotated));
+
+        let mut map = HashMap::new();
+        map.insert(direct, "direct");
+        assert_eq!(map.insert(rotated, "rotated"), Some("direct"));
+        assert_eq!(map.len(), 1);
+        assert_ne!(QNum::from_digits(&[2]), QNum::from_digits(&[5]));
+    }
+
+    /// A phase rotates one amplitude in place and rejects out-of-range targets.
+    #[test]
+    fn phase_rotates_single_amplitude() {
+        let mut qnum = QNum::from_digits(&[4, 7]);
+        qnum.phase(1, 7, core::f64::consts::FRAC_PI_2).unwrap();
+        let a = qnum.amplitude(1, 7).unwrap();
+        assert!(a.re.abs() < 1e-12 && (a.im - 1.0).abs() < 1e-12);
+        assert_eq!(qnum.amplitude(0, 4), Some(Complex::new(1.0, 0.0)));
+        assert!(qnum.norm_defect() < 1e-12);
+
+        assert_eq!(
+            qnum.phase(2, 0, 1.0),
+            Err(QNumError::IndexOutOfRange { index: 2, len: 2 })
+        );
+        assert_eq!(
+            qnum.phase(0, 10, 1.0),
+            Err(QNumError::DigitOutOfRange { digit: 10, radix: 10 })
+        );
+    }
+
+    /// Diff-then-patch rebuilds the target, storing only the changed digits.
+    #[test]
+    fn diff_then_patch_reconstructs_target() {
+        let base = QNum::from_digits(&[4, 2, 0, 7]);
+        let mut target = base.clone();
+        target.0[1].hadamard();
+        target.phase(3, 7, 0.5).unwrap();
+
+        let patch = base.diff(&target);
+        assert_eq!(patch.len, 4);
+        assert_eq!(patch.digits.iter().map(|(i, _)| *i).collect::<Vec<_>>(), vec![1, 3]);
+        let rebuilt = base.apply_patch(&patch);
+        assert_eq!(rebuilt.0, target.0);
+
+        // length changes in either direction
+        let longer = QNum::from_digits(&[4, 2, 0, 7, 5]);
+        assert_eq!(base.apply_patch(&base.diff(&longer)), longer);
+        let shorter = QNum::from_digits(&[4, 2]);
+        let truncate = base.diff(&shorter);
+        assert!(truncate.digits.is_empty());
+        assert_eq!(base.apply_patch(&truncate), shorter);
+
+        let bad = QNumPatch { len: 2, digits: vec![(2, Qid::definite(1))] };
+        assert_eq!(
+            base.try_apply_patch(&bad),
+            Err(QNumError::IndexOutOfRange { index: 2, len: 2 })
+        );
+        // growing the number needs every new digit
+        let gap = QNumPatch { len: 6, digits: vec![(5, Qid::definite(1))] };
+        assert_eq!(base.try_apply_patch(&gap), Err(QNumError::MissingDigit { index: 4 }));
+    }
+
+    /// Incrementing one digit wraps it without carrying into its neighbour.
+    #[test]
+    fn increment_digit_wraps_without_carry() {
+        let mut qnum = QNum::from_digits(&[3, 9]);
+        qnum.increment_digit(1).unwrap();
+        assert_eq!(qnum, QNum::from_digits(&[3, 0]));
+        assert_eq!(
+            qnum.increment_digit(2),
+            Err(QNumError::IndexOutOfRange { index: 2, len: 2 })
+        );
+    }
+
+    /// A normalized state overlaps itself with 1; a phase shows up in the complex overlap.
+    #[test]
+    fn inner_product_self_and_phase() {
+        let h = 1.0 / 2f64.sqrt();
+        let a = QNum::from_superposed(vec![
+            (vec![1, 4], Complex::new(h, 0.0)),
+            (vec![6, 4], Complex::new(0.0, h)),
+        ]);
+        let own = a.inner_product(&a).unwrap();
+        assert!((own.re - 1.0).abs() < 1e-12 && own.im.abs() < 1e-12);
+
+        let mut b = a.clone();
+        b.phase(1, 4, core::f64::consts::FRAC_PI_2).unwrap();
+        let overlap = a.inner_product(&b).unwrap();
+        assert!(overlap.re.abs() < 1e-12 && (overlap.im - 1.0).abs() < 1e-12);
+        assert!((a.fidelity(&b) - 1.0).abs() < 1e-12);
+
+        assert_eq!(
+            a.inner_product(&QNum::from_digits(&[1])),
+            Err(QNumError::LengthMismatch { expected: 2, found: 1 })
+        );
+    }
+
+    /// Full damping drives every digit to |0⟩; none leaves the state as is.
+    #[test]
+    fn amplitude_damping_per_digit() {
+        let h = Complex::new(1.0 / 2f64.sqrt(), 0.0);
+        let q = QNum::from_superposed(vec![(vec![3, 0], h), (vec![8, 0], h)]);
+
+        let mut none = q.clone();
+        none.apply_amplitude_damping(0.0);
+        assert_eq!(none, q);
+
+        let mut full = q.clone();
+        full.apply_amplitude_damping(1.0);
+        assert_eq!(full.measure(), vec![0, 0]);
+    }
+
+    /// Rotations move whole digits, wrapping around and preserving amplitudes.
+    #[test]
+    fn rotate_digits() {
+        let q = QNum::from_digits(&[1, 2, 3]);
+        assert_eq!(q.rotate_left(1).measure(), vec![2, 3, 1]);
+        assert_eq!(q.rotate_right(1).measure(), vec![3, 1, 2]);
+        assert_eq!(q.rotate_left(4), q.rotate_left(1));
+        assert_eq!(q.rotate_right(3), q);
+        assert_eq!(QNum::new(vec![]).rotate_left(2), QNum::new(vec![]));
+
+        let h = Complex::new(1.0 / 2f64.sqrt(), 0.0);
+        let s = QNum::from_superposed(vec![(vec![0, 5], h), (vec![0, 7], h)]);
+        assert_eq!(s.rotate_left(1).0[0], s.0[1]);
+    }
+
+    /// Identical states have fidelity 1, orthogonal ones 0.
+    #[test]
+    fn fidelity_identical_and_orthogonal() {
+        let a = QNum::from_digits(&[1, 2, 3]);
+        assert!((a.fidelity(&QNum::from_digits(&[1, 2, 3])) - 1.0).abs() < 1e-12);
+        assert!(a.fidelity(&QNum::from_digits(&[1, 2, 4])).abs() < 1e-12);
+        assert_eq!(a.fidelity(&QNum::from_digits(&[1, 2])), 0.0);
+    }
+
+    /// A fresh QNum is normalized; a perturbed digit reports its defect.
+    #[test]
+    fn norm_defect_reports_perturbation() {
+        let mut qnum = QNum::from_digits(&[2, 5, 8]);
+        assert!(qnum.norm_defect() < 1e-12);
+
+        // scale digit 1's amplitude so its norm becomes 1.1² = 1.21
+        qnum.0[1].amps[5].re = ordered_float::OrderedFloat(1.1);
+        assert!((qnum.norm_defect() - 0.21).abs() < 1e-12);
+    }
+
+    /// normalize clears the defect left by accumulating amplitudes.
+    #[test]
+    fn normalize_clears_norm_defect() {
+        let amp = Complex::new(1.0 / 2f64.sqrt(), 0.0);
+        let mut qnum = QNum::from_superposed(vec![(vec![3, 0], amp), (vec![6, 0], amp)]);
+        assert!(qnum.norm_defect() > 0.5);
+        qnum.normalize();
+        assert!(qnum.norm_defect() < 1e-12);
+    }
+
+    /// States differing only by a global phase become identical keys.
+    #[test]
+    fn canonicalize_removes_global_phase() {
+        let amp = Complex::new(0.6, 0.0);
+        let other = Complex::new(0.0, 0.8);
+        let mut a = QNum::from_superposed(vec![(vec![1, 4], amp), (vec![2, 7], other)]);
+        a.normalize();
+        // spread a global phase of 1.6 rad unevenly over the two digits
+        let mut b = a.clone();
+        for basis in 0..10 {
+            b.phase(0, basis, 0.5).unwrap();
+            b.phase(1, basis, 1.1).unwrap();
+        }
+        assert_ne!(a, b);
+        assert!((a.inner_product(&b).unwrap().norm() - 1.0).abs() < 1e-12);
+
+        a.canonicalize();
+        b.canonicalize();
+        assert_eq!(a, b);
+        let keys: std::collections::HashSet<QNum> = [a.clone(), b].into_iter().collect();
+        assert_eq!(keys.len(), 1);
+        // the reference amplitude is real-positive and probabilities are kept
+        let reference = a.amplitude(0, 1).unwrap();
+        assert!(reference.re > 0.0 && reference.im == 0.0);
+        assert!(a.norm_defect() < 1e-12);
+    }
+
+    /// A classical digit is one full bar; an equal two-way split is two half bars.
+    #[test]
+    fn histogram_bars() {
+        let classical = QNum::from_digits(&[7]).histogram_string();
+        assert_eq!(classical, format!("digit 0:\n    7 |{}| 1.000\n", "#".repeat(20)));
+
+        let h = Complex::new(1.0 / 2f64.sqrt(), 0.0);
+        let split = QNum::from_superposed(vec![(vec![2], h), (vec![5], h)]).histogram_string();
+        let half = format!("{:<20}", "#".repeat(10));
+        assert_eq!(
+            split,
+            format!("digit 0:\n    2 |{}| 0.500\n    5 |{}| 0.500\n", half, half)
+        );
+    }
+
+    /// Every collapse policy leaves a classical state matching its outcome.
+    #[test]
+    fn collapse_with_policies() {
+        use rand::rngs::StdRng;
+        use std::collections::HashSet;
+        // digit 0: P(1) = 0.36, P(7) = 0.64; digit 1 is |4⟩
+        let q = QNum::from_superposed(vec![
+            (vec![1, 4], Complex::new(0.6, 0.0)),
+            (vec![7, 4], Complex::new(0.8, 0.0)),
+        ]);
+        let mut rng = StdRng::seed_from_u64(1778);
+
+        let mut argmax = q.clone();
+        assert_eq!(argmax.collapse_with(CollapsePolicy::Argmax, &mut rng), vec![7, 4]);
+        assert_eq!(argmax, QNum::from_digits(&[7, 4]));
+
+        let mut seen = HashSet::new();
+        for _ in 0..100 {
+            let mut random = q.clone();
+            let outcome = random.collapse_with(CollapsePolicy::Random, &mut rng);
+            assert_eq!(random, QNum::from_digits(&outcome));
+            seen.insert(outcome);
+        }
+        assert_eq!(seen, HashSet::from([vec![1, 4], vec![7, 4]]));
+
+        // the seed alone fixes the outcome, whatever the caller's RNG
+        let (mut s1, mut s2) = (q.clone(), q.clone());
+        let o1 = s1.collapse_with(CollapsePolicy::Seeded(42), &mut StdRng::seed_from_u64(1));
+        let o2 = s2.collapse_with(CollapsePolicy::Seeded(42), &mut StdRng::seed_from_u64(2));
+        assert_eq!(o1, o2);
+        assert_eq!(s1, QNum::from_digits(&o1));
+    }
+
+    /// Sampling shots tallies outcomes without collapsing the state.
+    #[test]
+    fn sample_histogram_tallies_shots() {
+        use rand::{rngs::StdRng, SeedableRng};
+        let h = Complex::new(1.0 / 2f64.sqrt(), 0.0);
+        let q = QNum::from_superposed(vec![(vec![2], h), (vec![5], h)]);
+        let before = q.clone();
+
+        let hist = q.sample_histogram(10_000, &mut StdRng::seed_from_u64(1776));
+        assert_eq!(q, before);
+        assert_eq!(hist.len(), 2);
+        assert_eq!(hist.values().sum::<usize>(), 10_000);
+        for outcome in [vec![2], vec![5]] {
+            let count = hist[&outcome];
+            assert!((4_700..=5_300).contains(&count), "{:?} seen {} times", outcome, count);
+        }
+    }
+
+    /// Display renders digits in Dirac notation joined by ⊗.
+    #[test]
+    fn display_in_dirac_notation() {
+        let h = Complex::new(1.0 / 2f64.sqrt(), 0.0);
+        let single = QNum::from_superposed(vec![(vec![2], h), (vec![5], h)]);
+        assert_eq!(single.to_string(), "0.71|2⟩ + 0.71|5⟩");
+
+        let mut pair = single.tensor(&QNum::from_digits(&[3]));
+        assert_eq!(pair.to_string(), "(0.71|2⟩ + 0.71|5⟩) ⊗ |3⟩");
+        pair.normalize();
+        assert_eq!(format!("{:.1}", pair), "(0.7|2⟩ + 0.7|5⟩) ⊗ |3⟩");
+    }
+
+    /// Base-2 and base-16 classical digits round-trip through measurement.
+    #[test]
+    fn non_decimal_roundtrip() {
+        let bits = vec![1, 0, 1, 1];
+        let mut b = QNum::from_digits_in(2, &bits);
+        assert_eq!(b.radix(), 2);
+        assert_eq!(b.measure(), bits);
+
+        let nibbles = vec![0xf, 0x0, 0xa, 0x3];
+        let mut h = QNum::from_digits_in(16, &nibbles);
+        assert_eq!(h.radix(), 16);
+        assert_eq!(h.measure(), nibbles);
+    }
+
     /// Joint entropy equals sum of digit entropies.
     #[test]
     fn joint_entropy() {
@@ -135,4 +1668,15 @@ mod tests {
         let expected = 2.0 * (2.0f64).ln();
         assert!((qnum.entropy() - expected).abs() < 1e-6);
     }
+
+    /// An equal two-state superposition carries exactly one bit.
+    #[test]
+    fn entropy_bits_single_digit() {
+        let states = vec![
+            (vec![3], Complex::new(1.0 / 2f64.sqrt(), 0.0)),
+            (vec![8], Complex::new(1.0 / 2f64.sqrt(), 0.0)),
+        ];
+        let qnum = QNum::from_superposed(states);
+        assert!((qnum.entropy_bits() - 1.0).abs() < 1e-12);
+    }
 }
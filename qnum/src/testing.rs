@@ -0,0 +1,60 @@
+//! Helpers for tests in crates built on `qublis-qnum`.
+//!
+//! - `CallLog`, a shared, thread-safe record of method names that mock
+//!   storage backends push to, so a test can check which calls a component
+//!   made and in what order
+
+use std::sync::{Arc, Mutex};
+
+/// Ordered log of calls made on a mock, shared between the mock and the test.
+///
+/// Clones share one log, so a test keeps a clone while the mock (boxed into
+/// the component under test) records into another. It is `Send + Sync`, so
+/// mocks holding it satisfy thread-safe trait bounds.
+#[derive(Clone, Debug, Default)]
+pub struct CallLog(Arc<Mutex<Vec<&'static str>>>);
+
+impl CallLog {
+    /// Append `call` to the log.
+    pub fn record(&self, call: &'static str) {
+        self.0.lock().unwrap().push(call);
+    }
+
+    /// Calls recorded so far, oldest first.
+    pub fn calls(&self) -> Vec<&'static str> {
+        self.0.lock().unwrap().clone()
+    }
+
+    /// Most recent call, if any.
+    pub fn last(&self) -> Option<&'static str> {
+        self.0.lock().unwrap().last().copied()
+    }
+
+    /// Forget every recorded call.
+    pub fn clear(&self) {
+        self.0.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clones_share_one_log() {
+        let log = CallLog::default();
+        let mock_side = log.clone();
+        mock_side.record("get");
+        mock_side.record("put");
+        assert_eq!(log.calls(), ["get", "put"]);
+        assert_eq!(log.last(), Some("put"));
+
+        log.clear();
+        assert!(mock_side.calls().is_empty());
+        assert_eq!(mock_side.last(), None);
+
+        let handle = std::thread::spawn(move || mock_side.record("append"));
+        handle.join().unwrap();
+        assert_eq!(log.calls(), ["append"]);
+    }
+}
@@ -32,7 +32,7 @@ impl CausalReflector {
     /// * `cfg` - Configuration specifying whether causal reflection
     ///   is enabled and the maximum depth to traverse.
     pub fn new(cfg: &CausalReflectorConfig) -> Self {
-        let mut metrics = RuntimeMetrics::new();
+        let metrics = RuntimeMetrics::new();
         if cfg.enabled {
             metrics.inc_counter("causal_reflector_enabled", 1);
         } else {
@@ -84,7 +84,7 @@ mod tests {
     fn new_reflector_records_state() {
         let cfg = CausalReflectorConfig { enabled: true, max_depth: 10 };
         let reflector = CausalReflector::new(&cfg);
-        assert_eq!(reflector.metrics.get_counter("causal_reflector_enabled"), Some(&1));
+        assert_eq!(reflector.metrics.get_counter("causal_reflector_enabled"), Some(1));
         assert_eq!(reflector.enabled, true);
         assert_eq!(reflector.max_depth, 10);
     }
@@ -107,10 +107,10 @@ mod tests {
         // Configure the mock to reflect exactly 7 items
         engine.set_causal_reflection_capacity(7);
         assert!(reflector.reflect(&mut engine).is_ok());
-        assert_eq!(reflector.metrics.get_counter("causal_reflections"), Some(&1));
+        assert_eq!(reflector.metrics.get_counter("causal_reflections"), Some(1));
         assert_eq!(
             reflector.metrics.get_gauge("causal_reflected_count"),
-            Some(&7.0)
+            Some(7.0)
         );
     }
 
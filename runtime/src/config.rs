@@ -7,6 +7,7 @@
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
 
+use qublis_ci_core::NeuroFluxConfig;
 use serde::Deserialize;
 use std::{fs, path::Path};
 use thiserror::Error;
@@ -50,6 +51,9 @@ pub struct ConsensusConfig {
     /// Optional path to a NeuroFlux configuration TOML.
     #[serde(default)]
     pub neuroflux_config_path: Option<String>,
+    /// NeuroFlux agent parameters.
+    #[serde(default)]
+    pub neuroflux: NeuroFluxConfig,
 }
 
 /// Entanglement loop configuration.
@@ -75,7 +79,7 @@ pub struct CausalReflectorConfig {
 }
 
 /// WASM executor configuration.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct WasmConfig {
     /// Max memory (bytes) allowed for a single contract instance.
     #[serde(default = "default_wasm_memory_limit")]
@@ -105,6 +109,15 @@ fn default_wasm_gas_limit() -> u64 { 1_000_000 }
 fn default_metrics_port() -> u16 { 9300 }
 fn default_metrics_enabled() -> bool { true }
 
+impl Default for WasmConfig {
+    fn default() -> Self {
+        WasmConfig {
+            memory_limit: default_wasm_memory_limit(),
+            gas_limit: default_wasm_gas_limit(),
+        }
+    }
+}
+
 impl RuntimeConfig {
     /// Load a `RuntimeConfig` from a TOML file at `path`.
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
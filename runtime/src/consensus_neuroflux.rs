@@ -7,17 +7,82 @@
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
 
+use std::fmt;
+
 use qublis_qnum::QNum;
 use qublis_ci_core::{NeuroFluxAgent, Action};
 use crate::config::ConsensusConfig;
-use crate::types::ConsensusEngine;
+use crate::types::{ConsensusEngine, EngineCheckpoint};
 use crate::metrics::ConsensusMetrics;
 
+/// A consensus parameter that a NeuroFlux action can tune.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConsensusParam {
+    /// `entropy_finality`, clamped to `[min_entropy, max_entropy]`.
+    EntropyFinality,
+    /// `max_tips`, clamped to `[min_tips, max_tips_limit]`.
+    MaxTips,
+    /// `block_interval_ms`, clamped to `[min_block_interval_ms, max_block_interval_ms]`.
+    BlockInterval,
+    /// `max_branches`, clamped to `[min_branches, branch_limit]`.
+    MaxBranches,
+}
+
+/// A signed adjustment to a single consensus parameter.
+///
+/// Deltas are first capped to `max_step_fraction` of the parameter's range;
+/// deltas on integer parameters are then rounded to the nearest whole step.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParamDelta {
+    /// Parameter being adjusted.
+    pub param: ConsensusParam,
+    /// Amount to add to the parameter's current value.
+    pub delta: f64,
+}
+
+/// A NeuroFlux action expressed as a vector of per-parameter deltas.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MultiAction {
+    /// Deltas applied in order.
+    pub deltas: Vec<ParamDelta>,
+}
+
+impl From<&Action> for MultiAction {
+    /// One delta per parameter the action moves; zero deltas are dropped.
+    fn from(action: &Action) -> Self {
+        let deltas = [
+            (ConsensusParam::EntropyFinality, action.delta_entropy),
+            (ConsensusParam::MaxTips, action.delta_tips as f64),
+            (ConsensusParam::BlockInterval, action.delta_block_interval_ms as f64),
+            (ConsensusParam::MaxBranches, action.delta_branches as f64),
+        ];
+        MultiAction {
+            deltas: deltas
+                .into_iter()
+                .filter(|&(_, delta)| delta != 0.0)
+                .map(|(param, delta)| ParamDelta { param, delta })
+                .collect(),
+        }
+    }
+}
+
+/// Reward function scoring the engine's performance after an epoch.
+pub type RewardFn = Box<dyn Fn(&ConsensusEngine) -> f64 + Send + Sync>;
+
 /// NeuroFlux‐driven consensus adapter.
-#[derive(Debug)]
 pub struct ConsensusNeuroFlux {
     agent: NeuroFluxAgent,
     metrics: ConsensusMetrics,
+    reward_fn: RewardFn,
+}
+
+impl fmt::Debug for ConsensusNeuroFlux {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConsensusNeuroFlux")
+            .field("agent", &self.agent)
+            .field("metrics", &self.metrics)
+            .finish_non_exhaustive()
+    }
 }
 
 impl ConsensusNeuroFlux {
@@ -27,12 +92,23 @@ impl ConsensusNeuroFlux {
     ///
     /// * `cfg` - Consensus configuration, containing NeuroFlux parameters.
     pub fn new(cfg: &ConsensusConfig) -> Self {
+        Self::with_reward_fn(cfg, Box::new(default_reward))
+    }
+
+    /// Initialize NeuroFlux with a custom reward function in place of
+    /// [`default_reward`].
+    ///
+    /// # Arguments
+    ///
+    /// * `cfg` - Consensus configuration, containing NeuroFlux parameters.
+    /// * `reward_fn` - Scores the engine after each epoch; its value is fed to the agent.
+    pub fn with_reward_fn(cfg: &ConsensusConfig, reward_fn: RewardFn) -> Self {
         // Initialize the NeuroFlux RL agent
-        let mut agent = NeuroFluxAgent::new(&cfg.neuroflux);
+        let agent = NeuroFluxAgent::new(&cfg.neuroflux);
         // Setup metrics collector
-        let mut metrics = ConsensusMetrics::new();
+        let metrics = ConsensusMetrics::new();
         metrics.inc_counter("neuroflux_initialized", 1);
-        ConsensusNeuroFlux { agent, metrics }
+        ConsensusNeuroFlux { agent, metrics, reward_fn }
     }
 
     /// Execute one tick of the consensus loop with NeuroFlux optimization.
@@ -50,8 +126,8 @@ impl ConsensusNeuroFlux {
         // 2. Select an optimization action
         let action: Action = self.agent.select_action(&state);
 
-        // 3. Apply the action to the consensus parameters
-        self.apply_action(engine, &action);
+        // 3. Apply the action's per-parameter deltas
+        self.apply_multi_action(engine, &MultiAction::from(&action));
 
         // 4. Produce blocks for one epoch
         engine.produce_blocks();
@@ -60,63 +136,143 @@ impl ConsensusNeuroFlux {
         let reward = self.compute_reward(engine, &state);
 
         // 6. Update NeuroFlux agent with (state, action, reward)
-        self.agent.learn(state, action, reward);
+        self.agent.learn(state.clone(), action, reward);
 
         // 7. Record metrics
         self.metrics.inc_counter("neuroflux_ticks", 1);
         self.metrics.record_observation(&state);
         self.metrics.record_reward(reward);
+        self.metrics.record_cognitive_entropy(engine.cognitive_entropy);
+        self.metrics.record_performance(
+            engine.measured_tps as f64,
+            engine.avg_latency_ms,
+            engine.latency_stddev_ms,
+        );
+    }
+
+    /// Snapshot `engine` together with the learned agent, for a restart to
+    /// resume tuning; see [`ConsensusNeuroFlux::restore`].
+    pub fn checkpoint(&self, engine: &ConsensusEngine) -> EngineCheckpoint {
+        EngineCheckpoint {
+            agent: Some(self.agent.clone()),
+            ..engine.checkpoint()
+        }
+    }
+
+    /// Rebuild the engine from `checkpoint` and adopt its learned agent, if
+    /// it carries one; otherwise the current agent is kept.
+    pub fn restore(&mut self, checkpoint: &EngineCheckpoint) -> ConsensusEngine {
+        if let Some(agent) = &checkpoint.agent {
+            self.agent = agent.clone();
+        }
+        self.metrics.inc_counter("neuroflux_restores", 1);
+        ConsensusEngine::restore(checkpoint)
     }
 
     /// Gathers observable metrics from the engine into a single QNum.
+    ///
+    /// Each metric becomes one digit, saturating at 9.
     fn collect_state(&self, engine: &ConsensusEngine) -> QNum {
         // Pack tip_count, avg_latency_ms, cognitive_entropy, fork_rate*100
+        let digit = |v: f64| v.clamp(0.0, 9.0) as u8;
         QNum::from_digits(&[
-            engine.tip_count as u8,
-            engine.avg_latency_ms as u8,
-            engine.cognitive_entropy as u8,
-            (engine.fork_rate * 100.0) as u8,
+            digit(engine.tip_count as f64),
+            digit(engine.avg_latency_ms),
+            digit(engine.cognitive_entropy),
+            digit(engine.fork_rate * 100.0),
         ])
     }
 
-    /// Applies a NeuroFlux action to the engine's consensus configuration.
-    fn apply_action(&self, engine: &mut ConsensusEngine, action: &Action) {
+    /// Applies every delta of a multi-parameter action, limiting each step
+    /// to `max_step_fraction` of the parameter's range and clamping the
+    /// parameter to its configured bounds.
+    ///
+    /// Integer parameters may always move by at least one whole step.
+    fn apply_multi_action(&self, engine: &mut ConsensusEngine, action: &MultiAction) {
         let cfg = &mut engine.config;
-        // Adjust entropy threshold
-        cfg.entropy_finality = (cfg.entropy_finality + action.delta_entropy)
-            .clamp(cfg.min_entropy, cfg.max_entropy);
-        // Adjust tip count
-        cfg.max_tips = ((cfg.max_tips as isize) + action.delta_tips)
-            .clamp(cfg.min_tips as isize, cfg.max_tips as isize) as usize;
+        // max/min rather than clamp, so a NaN fraction cannot panic
+        let fraction = cfg.max_step_fraction.max(0.0).min(1.0);
+        let step = |delta: f64, lo: f64, hi: f64| {
+            let cap = fraction * (hi - lo).max(0.0);
+            delta.clamp(-cap, cap)
+        };
+        let int_step = |delta: f64, lo: f64, hi: f64| {
+            let cap = (fraction * (hi - lo).max(0.0)).max(1.0);
+            delta.clamp(-cap, cap).round()
+        };
+        for d in &action.deltas {
+            match d.param {
+                ConsensusParam::EntropyFinality => {
+                    let delta = step(d.delta, cfg.min_entropy, cfg.max_entropy);
+                    cfg.entropy_finality = (cfg.entropy_finality + delta)
+                        .clamp(cfg.min_entropy, cfg.max_entropy);
+                }
+                ConsensusParam::MaxTips => {
+                    let delta = int_step(d.delta, cfg.min_tips as f64, cfg.max_tips_limit as f64);
+                    cfg.max_tips = ((cfg.max_tips as isize) + delta as isize)
+                        .clamp(cfg.min_tips as isize, cfg.max_tips_limit as isize) as usize;
+                }
+                ConsensusParam::BlockInterval => {
+                    let delta = int_step(
+                        d.delta,
+                        cfg.min_block_interval_ms as f64,
+                        cfg.max_block_interval_ms as f64,
+                    );
+                    cfg.block_interval_ms = ((cfg.block_interval_ms as i64) + delta as i64)
+                        .clamp(cfg.min_block_interval_ms as i64, cfg.max_block_interval_ms as i64)
+                        as u64;
+                }
+                ConsensusParam::MaxBranches => {
+                    let delta = int_step(d.delta, cfg.min_branches as f64, cfg.branch_limit as f64);
+                    cfg.max_branches = ((cfg.max_branches as isize) + delta as isize)
+                        .clamp(cfg.min_branches as isize, cfg.branch_limit as isize) as usize;
+                }
+            }
+        }
     }
 
-    /// Computes a scalar reward from the engine's performance metrics.
+    /// Computes a scalar reward from the engine's performance metrics
+    /// using the configured reward function.
     fn compute_reward(&self, engine: &ConsensusEngine, _prev_state: &QNum) -> f64 {
-        let tps_ratio = engine.measured_tps as f64 / engine.target_tps as f64;
-        let latency_penalty = engine.avg_latency_ms / engine.max_latency_ms;
-        let fork_penalty = engine.fork_rate;
-        engine.config.reward_weights.tps * tps_ratio
-            - engine.config.reward_weights.latency * latency_penalty
-            - engine.config.reward_weights.forks * fork_penalty
+        (self.reward_fn)(engine)
     }
 }
 
+/// Default reward: weighted TPS ratio minus latency and fork penalties,
+/// using the engine's `reward_weights`.
+pub fn default_reward(engine: &ConsensusEngine) -> f64 {
+    let tps_ratio = engine.measured_tps as f64 / engine.target_tps as f64;
+    let latency_penalty = engine.avg_latency_ms / engine.max_latency_ms;
+    let fork_penalty = engine.fork_rate;
+    engine.config.reward_weights.tps * tps_ratio
+        - engine.config.reward_weights.latency * latency_penalty
+        - engine.config.reward_weights.forks * fork_penalty
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::config::ConsensusConfig;
     use crate::types::ConsensusEngine;
-    use qublis_ci_core::RewardWeights;
+    use crate::metrics::{
+        COGNITIVE_ENTROPY_GAUGE, LATENCY_MEAN_GAUGE, LATENCY_STDDEV_GAUGE, TPS_GAUGE,
+    };
+    use qublis_ci_core::{NeuroFluxConfig, RewardWeights};
 
-    #[test]
-    fn reward_calculation_example() {
-        // Prepare a dummy ConsensusConfig with a dummy NeuroFluxConfig
-        let cfg = ConsensusConfig {
+    /// NeuroFlux-enabled consensus config with default agent parameters.
+    fn test_config() -> ConsensusConfig {
+        ConsensusConfig {
             qmesh_config_path: "qmesh.toml".into(),
             neuroflux_enabled: true,
             neuroflux_config_path: None,
-        };
-        let mut cnf = ConsensusNeuroFlux::new(&cfg);
+            neuroflux: Default::default(),
+        }
+    }
+
+    #[test]
+    fn reward_calculation_example() {
+        let cfg = test_config();
+        let cnf = ConsensusNeuroFlux::new(&cfg);
 
         // Mock a ConsensusEngine with sample metrics
         let mut engine = ConsensusEngine::mock();
@@ -133,4 +289,180 @@ mod tests {
         // expected = 0.9 - 0.5 - 0.02 = 0.38
         assert!((reward - 0.38).abs() < 1e-6);
     }
+
+    #[test]
+    fn tick_records_cognitive_entropy_gauge() {
+        let cfg = test_config();
+        let mut cnf = ConsensusNeuroFlux::new(&cfg);
+        let mut engine = ConsensusEngine::mock();
+        engine.target_tps = 1000;
+        engine.cognitive_entropy = 3.5;
+
+        cnf.tick(&mut engine);
+        assert_eq!(cnf.metrics.get_gauge(COGNITIVE_ENTROPY_GAUGE), Some(3.5));
+    }
+
+    #[test]
+    fn tick_exports_throughput_and_latency_gauges() {
+        let cfg = test_config();
+        let mut cnf = ConsensusNeuroFlux::new(&cfg);
+        let mut engine = ConsensusEngine::mock();
+        engine.target_tps = 1000;
+        engine.measured_tps = 800;
+        engine.avg_latency_ms = 40.0;
+        engine.latency_stddev_ms = 4.0;
+
+        cnf.tick(&mut engine);
+        assert_eq!(cnf.metrics.get_gauge(TPS_GAUGE), Some(800.0));
+        assert_eq!(cnf.metrics.get_gauge(LATENCY_MEAN_GAUGE), Some(40.0));
+        assert_eq!(cnf.metrics.get_gauge(LATENCY_STDDEV_GAUGE), Some(4.0));
+    }
+
+    #[test]
+    fn custom_reward_fn_replaces_default() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        // a learning rate of 1 makes the agent's estimate the last reward
+        let cfg = ConsensusConfig {
+            neuroflux: NeuroFluxConfig { learning_rate: 1.0, ..Default::default() },
+            ..test_config()
+        };
+        let calls = Arc::new(AtomicUsize::new(0));
+        let seen = Arc::clone(&calls);
+        let mut cnf = ConsensusNeuroFlux::with_reward_fn(
+            &cfg,
+            Box::new(move |engine: &ConsensusEngine| {
+                seen.fetch_add(1, Ordering::SeqCst);
+                engine.tip_count as f64 * 2.0
+            }),
+        );
+        let mut engine = ConsensusEngine::mock();
+        engine.tip_count = 21;
+
+        let state = cnf.collect_state(&engine);
+        assert_eq!(cnf.compute_reward(&engine, &state), 42.0);
+
+        // tick scores the epoch through the same function and the agent
+        // learns that score for the action it took
+        let action = cnf.agent.select_action(&state);
+        let taken = cnf.agent.actions().iter().position(|a| *a == action).unwrap();
+        cnf.tick(&mut engine);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        let stats = cnf.agent.values(&state).expect("agent learned nothing");
+        assert_eq!(stats[taken].value, 42.0);
+        assert_eq!(stats[taken].visits, 1);
+    }
+
+    #[test]
+    fn multi_action_adjusts_all_targeted_params() {
+        let cfg = test_config();
+        let cnf = ConsensusNeuroFlux::new(&cfg);
+        let mut engine = ConsensusEngine::mock();
+
+        let action = MultiAction {
+            deltas: vec![
+                ParamDelta { param: ConsensusParam::EntropyFinality, delta: 2.5 },
+                ParamDelta { param: ConsensusParam::MaxTips, delta: -3.0 },
+                ParamDelta { param: ConsensusParam::BlockInterval, delta: -5_000.0 },
+                ParamDelta { param: ConsensusParam::MaxBranches, delta: 10_000.0 },
+            ],
+        };
+        cnf.apply_multi_action(&mut engine, &action);
+
+        let c = &engine.config;
+        assert_eq!(c.entropy_finality, 12.5);
+        assert_eq!(c.max_tips, 5);
+        // both out-of-range moves clamp to their bounds
+        assert_eq!(c.block_interval_ms, c.min_block_interval_ms);
+        assert_eq!(c.max_branches, c.branch_limit);
+    }
+
+    #[test]
+    fn action_converts_to_nonzero_deltas() {
+        let action = Action { delta_block_interval_ms: -100, delta_branches: 64, ..Action::default() };
+        assert_eq!(
+            MultiAction::from(&action).deltas,
+            vec![
+                ParamDelta { param: ConsensusParam::BlockInterval, delta: -100.0 },
+                ParamDelta { param: ConsensusParam::MaxBranches, delta: 64.0 },
+            ]
+        );
+        assert!(MultiAction::from(&Action::default()).deltas.is_empty());
+    }
+
+    #[test]
+    fn ticks_tune_every_parameter() {
+        let cfg = test_config();
+        // untried actions look best, so successive ticks sweep the action set
+        let mut cnf = ConsensusNeuroFlux::with_reward_fn(&cfg, Box::new(|_: &ConsensusEngine| -1.0));
+        let mut engine = ConsensusEngine::mock();
+        let (mut interval_moved, mut branches_moved) = (false, false);
+        for _ in 0..cnf.agent.actions().len() {
+            let before = engine.config.clone();
+            cnf.tick(&mut engine);
+            interval_moved |= engine.config.block_interval_ms != before.block_interval_ms;
+            branches_moved |= engine.config.max_branches != before.max_branches;
+        }
+        assert!(interval_moved && branches_moved);
+    }
+
+    #[test]
+    fn max_step_fraction_caps_each_adjustment() {
+        let cfg = test_config();
+        let cnf = ConsensusNeuroFlux::new(&cfg);
+        let mut engine = ConsensusEngine::mock();
+        engine.config.max_step_fraction = 0.1;
+        let start = engine.config.entropy_finality;
+        let range = engine.config.max_entropy - engine.config.min_entropy;
+
+        // a huge entropy push moves by a tenth of the range, not to the bound
+        let action = MultiAction {
+            deltas: vec![
+                ParamDelta { param: ConsensusParam::EntropyFinality, delta: 1_000.0 },
+                ParamDelta { param: ConsensusParam::BlockInterval, delta: 5_000.0 },
+                ParamDelta { param: ConsensusParam::MaxTips, delta: 1_000.0 },
+            ],
+        };
+        cnf.apply_multi_action(&mut engine, &action);
+        let c = &engine.config;
+        assert!((c.entropy_finality - (start + 0.1 * range)).abs() < 1e-9);
+        // 10% of the 100..=10_000 ms range
+        assert_eq!(c.block_interval_ms, 1000 + 990);
+        // 10% of the 1..=64 tip range, rounded: 8 + 6
+        assert_eq!(c.max_tips, 14);
+
+        // small deltas within the cap pass through unchanged
+        let nudge = MultiAction {
+            deltas: vec![ParamDelta { param: ConsensusParam::EntropyFinality, delta: -0.5 }],
+        };
+        let before = engine.config.entropy_finality;
+        cnf.apply_multi_action(&mut engine, &nudge);
+        assert!((engine.config.entropy_finality - (before - 0.5)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn checkpoint_restores_learned_agent() {
+        let cfg = test_config();
+        let mut cnf = ConsensusNeuroFlux::new(&cfg);
+        let mut engine = ConsensusEngine::mock();
+        engine.measured_tps = 700;
+        engine.target_tps = 1000;
+        for _ in 0..5 {
+            cnf.tick(&mut engine);
+        }
+        let state = cnf.collect_state(&engine);
+        assert!(cnf.agent.values(&state).is_some());
+
+        let saved = toml::to_string(&cnf.checkpoint(&engine)).unwrap();
+        let checkpoint: EngineCheckpoint = toml::from_str(&saved).unwrap();
+
+        // a cold-started adapter picks up the learned table and tuned config
+        let mut fresh = ConsensusNeuroFlux::new(&cfg);
+        let restored = fresh.restore(&checkpoint);
+        assert_eq!(fresh.agent, cnf.agent);
+        assert_eq!(fresh.agent.select_action(&state), cnf.agent.select_action(&state));
+        assert_eq!(restored.config.max_tips, engine.config.max_tips);
+        assert_eq!(restored.config.entropy_finality, engine.config.entropy_finality);
+    }
 }
@@ -2,13 +2,19 @@
 //!
 //! Periodically propagates “entanglement” (quantum‐style correlations) across
 //! the consensus engine’s multi-dimensional branches, exploring up to
-//! `max_branches` per cycle and recording metrics.
+//! `max_branches` per cycle and recording metrics. Callers either drive it
+//! by hand with [`EntanglementLoop::tick`] or hand it to the async runtime
+//! with [`EntanglementLoop::run`], which ticks every `interval_ms` until a
+//! shutdown signal arrives.
 
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
 
 use std::time::{Duration, Instant};
 
+use tokio::sync::watch;
+use tokio::time::{self, MissedTickBehavior};
+
 use crate::config::EntanglementConfig;
 use crate::error::RuntimeError;
 use crate::metrics::RuntimeMetrics;
@@ -31,7 +37,7 @@ impl EntanglementLoop {
     /// * `cfg` – entanglement‐loop parameters from `RuntimeConfig`.
     pub fn new(cfg: &EntanglementConfig) -> Self {
         let interval = Duration::from_millis(cfg.interval_ms);
-        let mut metrics = RuntimeMetrics::new();
+        let metrics = RuntimeMetrics::new();
         metrics.inc_counter("entanglement_loop_initialized", 1);
         EntanglementLoop {
             interval,
@@ -57,16 +63,55 @@ impl EntanglementLoop {
             return Ok(())
         }
 
+        self.propagate(engine)?;
+
+        // Update timer
+        self.last_run = now;
+        Ok(())
+    }
+
+    /// Propagate entanglement every `interval` until `shutdown` turns `true`
+    /// or its sender is dropped.
+    ///
+    /// The first cycle runs immediately; cycles that fall behind are delayed
+    /// rather than bunched up. A zero interval is treated as one millisecond.
+    ///
+    /// # Errors
+    ///
+    /// Stops and returns `RuntimeError` if any engine propagation fails.
+    pub async fn run(
+        &mut self,
+        engine: &mut ConsensusEngine,
+        mut shutdown: watch::Receiver<bool>,
+    ) -> Result<(), RuntimeError> {
+        let mut interval = time::interval(self.interval.max(Duration::from_millis(1)));
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        while !*shutdown.borrow() {
+            tokio::select! {
+                _ = interval.tick() => {
+                    self.propagate(engine)?;
+                    self.last_run = Instant::now();
+                }
+                changed = shutdown.changed() => {
+                    if changed.is_err() {
+                        // Sender dropped: nobody can ask us to keep going.
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Propagate up to `max_branches` branches and record the cycle.
+    fn propagate(&mut self, engine: &mut ConsensusEngine) -> Result<(), RuntimeError> {
         // Perform entanglement propagation in the consensus engine
         // (returns number of branches actually processed)
         let processed = engine.propagate_entanglement(self.max_branches)?;
-        
+
         // Record metrics
         self.metrics.inc_counter("entanglement_ticks", 1);
         self.metrics.set_gauge("entanglement_branches_processed", processed as f64);
-
-        // Update timer
-        self.last_run = now;
         Ok(())
     }
 
@@ -88,7 +133,7 @@ mod tests {
         let el = EntanglementLoop::new(&cfg);
         assert_eq!(el.interval.as_millis(), 123);
         assert_eq!(el.max_branches, 7);
-        assert_eq!(el.metrics.get_counter("entanglement_loop_initialized"), Some(&1));
+        assert_eq!(el.metrics.get_counter("entanglement_loop_initialized"), Some(1));
     }
 
     #[test]
@@ -98,7 +143,7 @@ mod tests {
         let mut engine = ConsensusEngine::mock();
         // Engine.mock() should not record propagation if not called
         assert!(el.tick(&mut engine).is_ok());
-        assert_eq!(el.metrics.get_counter("entanglement_ticks"), Some(&0));
+        assert_eq!(el.metrics.get_counter("entanglement_ticks"), None);
     }
 
     #[test]
@@ -109,10 +154,45 @@ mod tests {
         // Configure mock to process exactly 4 branches when asked
         engine.set_entanglement_capacity(4);
         assert!(el.tick(&mut engine).is_ok());
-        assert_eq!(el.metrics.get_counter("entanglement_ticks"), Some(&1));
+        assert_eq!(el.metrics.get_counter("entanglement_ticks"), Some(1));
+        assert_eq!(
+            el.metrics.get_gauge("entanglement_branches_processed"),
+            Some(4.0)
+        );
+    }
+
+    #[tokio::test]
+    async fn run_ticks_on_interval_until_shutdown() {
+        let cfg = EntanglementConfig { interval_ms: 20, max_branches: 3 };
+        let mut el = EntanglementLoop::new(&cfg);
+        let mut engine = ConsensusEngine::mock();
+        engine.set_entanglement_capacity(10);
+
+        let (tx, rx) = watch::channel(false);
+        let stop = async move {
+            time::sleep(Duration::from_millis(110)).await;
+            tx.send(true).unwrap();
+        };
+        let (result, ()) = tokio::join!(el.run(&mut engine, rx), stop);
+        result.expect("run should not error");
+
+        // ticks at 0, 20, .., 100 ms; allow for scheduler jitter
+        let ticks = el.metrics.get_counter("entanglement_ticks").unwrap_or(0);
+        assert!((4..=7).contains(&ticks), "unexpected tick count {}", ticks);
+        // capped at max_branches per cycle
         assert_eq!(
             el.metrics.get_gauge("entanglement_branches_processed"),
-            Some(&4.0)
+            Some(3.0)
         );
     }
+
+    #[tokio::test]
+    async fn run_returns_at_once_if_already_shut_down() {
+        let cfg = EntanglementConfig { interval_ms: 0, max_branches: 1 };
+        let mut el = EntanglementLoop::new(&cfg);
+        let mut engine = ConsensusEngine::mock();
+        let (_tx, rx) = watch::channel(true);
+        el.run(&mut engine, rx).await.unwrap();
+        assert_eq!(el.metrics.get_counter("entanglement_ticks"), None);
+    }
 }
@@ -26,11 +26,11 @@ pub mod prelude;
 /// Runtime configuration loaded from TOML.
 pub use config::RuntimeConfig;
 /// Core blockchain types, e.g. `Block` and `ConsensusEngine`.
-pub use types::{Block, ConsensusEngine};
+pub use types::{Block, ConsensusEngine, EngineCheckpoint};
 /// Error type for all runtime operations.
 pub use error::RuntimeError;
 /// Prometheus-style metrics collector for the runtime.
-pub use metrics::RuntimeMetrics;
+pub use metrics::{ConsensusMetrics, RuntimeMetrics};
 /// NeuroFlux-powered consensus adapter.
 pub use consensus_neuroflux::ConsensusNeuroFlux;
 /// Entanglement loop for propagating quantum state across dimensions.
@@ -1,6 +1,7 @@
 //! Prometheus‐style in‐memory metrics collector for the Qublis runtime.
 //!
 //! Supports simple counters and gauges, with text‐format export for Prometheus scraping.
+//! `ConsensusMetrics` layers the NeuroFlux consensus gauges on top.
 
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
@@ -8,6 +9,18 @@
 use std::collections::HashMap;
 use std::sync::Mutex;
 
+use qublis_qnum::QNum;
+
+/// Gauge name under which the mesh's cognitive entropy is exported each tick.
+pub const COGNITIVE_ENTROPY_GAUGE: &str = "runtime_cognitive_entropy";
+/// Gauge name under which the engine's measured TPS is exported each tick.
+pub const TPS_GAUGE: &str = "runtime_tps";
+/// Gauge name under which the engine's mean block latency (ms) is exported each tick.
+pub const LATENCY_MEAN_GAUGE: &str = "runtime_latency_mean_ms";
+/// Gauge name under which the engine's block latency standard deviation (ms)
+/// is exported each tick.
+pub const LATENCY_STDDEV_GAUGE: &str = "runtime_latency_stddev_ms";
+
 /// In‐memory metrics collector.
 #[derive(Debug)]
 pub struct RuntimeMetrics {
@@ -74,6 +87,64 @@ impl RuntimeMetrics {
     }
 }
 
+/// Metrics collected by `ConsensusNeuroFlux`: the generic runtime counters
+/// and gauges plus typed recorders for each tick's observations.
+#[derive(Debug)]
+pub struct ConsensusMetrics {
+    inner: RuntimeMetrics,
+}
+
+impl ConsensusMetrics {
+    /// Create a new, empty consensus metrics collector.
+    pub fn new() -> Self {
+        ConsensusMetrics { inner: RuntimeMetrics::new() }
+    }
+
+    /// Increment the named counter by `value`.
+    pub fn inc_counter(&self, name: &str, value: u64) {
+        self.inner.inc_counter(name, value);
+    }
+
+    /// Record an observed consensus state: counts it and exports its entropy.
+    pub fn record_observation(&self, state: &QNum) {
+        self.inner.inc_counter("neuroflux_observations", 1);
+        self.inner.set_gauge("neuroflux_state_entropy", state.entropy());
+    }
+
+    /// Record the reward of the latest epoch.
+    pub fn record_reward(&self, reward: f64) {
+        self.inner.set_gauge("neuroflux_reward", reward);
+    }
+
+    /// Record the mesh's current cognitive entropy under [`COGNITIVE_ENTROPY_GAUGE`].
+    pub fn record_cognitive_entropy(&self, entropy: f64) {
+        self.inner.set_gauge(COGNITIVE_ENTROPY_GAUGE, entropy);
+    }
+
+    /// Record the epoch's measured throughput and block latency under
+    /// [`TPS_GAUGE`], [`LATENCY_MEAN_GAUGE`] and [`LATENCY_STDDEV_GAUGE`].
+    pub fn record_performance(&self, tps: f64, latency_mean_ms: f64, latency_stddev_ms: f64) {
+        self.inner.set_gauge(TPS_GAUGE, tps);
+        self.inner.set_gauge(LATENCY_MEAN_GAUGE, latency_mean_ms);
+        self.inner.set_gauge(LATENCY_STDDEV_GAUGE, latency_stddev_ms);
+    }
+
+    /// Get a snapshot of the named counter, if present.
+    pub fn get_counter(&self, name: &str) -> Option<u64> {
+        self.inner.get_counter(name)
+    }
+
+    /// Get a snapshot of the named gauge, if present.
+    pub fn get_gauge(&self, name: &str) -> Option<f64> {
+        self.inner.get_gauge(name)
+    }
+
+    /// Export all metrics in Prometheus text format.
+    pub fn export_prometheus(&self) -> String {
+        self.inner.export_prometheus()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -103,4 +174,12 @@ mod tests {
         assert!(txt.contains("c1 2"));
         assert!(txt.contains("g1 3.14"));
     }
+
+    #[test]
+    fn cognitive_entropy_exported_as_gauge() {
+        let m = ConsensusMetrics::new();
+        m.record_cognitive_entropy(1.25);
+        assert_eq!(m.get_gauge(COGNITIVE_ENTROPY_GAUGE), Some(1.25));
+        assert!(m.export_prometheus().contains("runtime_cognitive_entropy 1.25"));
+    }
 }
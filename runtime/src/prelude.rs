@@ -17,13 +17,13 @@ pub use crate::{
         MetricsConfig,
     },
     // Core types
-    types::{Block, ConsensusEngine, EngineError},
+    types::{Block, ConsensusEngine, EngineCheckpoint, EngineError},
     // Error handling
     error::RuntimeError,
     // Metrics collector
-    metrics::RuntimeMetrics,
+    metrics::{ConsensusMetrics, RuntimeMetrics},
     // Runtime modules
-    consensus_neuroflux::ConsensusNeuroFlux,
+    consensus_neuroflux::{ConsensusNeuroFlux, ConsensusParam, MultiAction, ParamDelta, RewardFn},
     entanglement_loop::EntanglementLoop,
     causal_reflector::CausalReflector,
     wasm::WasmExecutor,
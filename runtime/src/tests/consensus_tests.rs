@@ -13,6 +13,7 @@ fn make_cfg() -> ConsensusConfig {
         qmesh_config_path: "qmesh.toml".into(),
         neuroflux_enabled: true,
         neuroflux_config_path: None,
+        neuroflux: Default::default(),
     }
 }
 
@@ -1,13 +1,17 @@
 //! Core runtime types for Qublis v2.0 (2-74136).
 //!
 //! Defines the `Block`, `ConsensusEngine`, its configuration and errors,
-//! as well as mock utilities for testing.
+//! the `EngineCheckpoint` a validator persists across restarts, as well as
+//! mock utilities for testing.
 
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
 
 use qublis_qnum::QNum;
-use qublis_ci_core::RewardWeights;
+use qublis_ci_core::{NeuroFluxAgent, RewardWeights};
+use qublis_qmesh::entropic_dag::{EntropicDag, NodeIndex};
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 
 /// A block in the QMesh entropic DAG.
@@ -23,6 +27,31 @@ pub struct Block {
     pub entropy: f64,
     /// Unix timestamp of block creation.
     pub timestamp: u64,
+    /// Height in the DAG: the longest parent path back to genesis.
+    pub height: u64,
+}
+
+impl Block {
+    /// Package node `idx` of a QMesh DAG as a block.
+    ///
+    /// The node's `QNum` state becomes the block identifier, its id string is
+    /// carried as the payload, and the entropy is the state's joint entropy.
+    /// Parents are the states of the node's DAG parents, and the height is
+    /// its longest path back to a root.
+    pub fn from_dag_node(dag: &EntropicDag, idx: NodeIndex) -> Block {
+        let node = dag.node_data(idx);
+        Block {
+            id: node.state.clone(),
+            parents: dag.parents(idx).map(|p| dag.node_data(p).state.clone()).collect(),
+            payload: node.id.as_bytes().to_vec(),
+            entropy: node.state.entropy(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            height: dag.height(idx),
+        }
+    }
 }
 
 /// Errors returned by the consensus engine.
@@ -34,13 +63,18 @@ pub enum EngineError {
     /// Failure during causal reflection.
     #[error("causal reflection failed")]
     Causal,
+    /// A configuration bound is non-finite or its minimum exceeds its maximum.
+    #[error("invalid consensus config: {0}")]
+    InvalidConfig(String),
 }
 
 /// Configuration parameters for the consensus engine.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConsensusEngineConfig {
     /// Entropy threshold at which blocks are considered final.
     pub entropy_finality: f64,
+    /// Blocks that must be built on top of a block before it can be final.
+    pub min_finality_depth: u64,
     /// Minimum allowed entropy threshold.
     pub min_entropy: f64,
     /// Maximum allowed entropy threshold.
@@ -49,18 +83,95 @@ pub struct ConsensusEngineConfig {
     pub min_tips: usize,
     /// Maximum number of tips to reference.
     pub max_tips: usize,
+    /// Upper bound for `max_tips`.
+    #[serde(default = "default_max_tips_limit")]
+    pub max_tips_limit: usize,
+    /// Target interval between produced blocks (ms).
+    pub block_interval_ms: u64,
+    /// Minimum allowed block interval (ms).
+    pub min_block_interval_ms: u64,
+    /// Maximum allowed block interval (ms).
+    pub max_block_interval_ms: u64,
+    /// Maximum number of branches explored per entanglement cycle.
+    pub max_branches: usize,
+    /// Lower bound for `max_branches`.
+    pub min_branches: usize,
+    /// Upper bound for `max_branches`.
+    pub branch_limit: usize,
+    /// Largest fraction of a parameter's `[min, max]` range that one
+    /// NeuroFlux action may move it, in `(0, 1]`; `1.0` leaves only the
+    /// bounds clamp.
+    #[serde(default = "default_max_step_fraction")]
+    pub max_step_fraction: f64,
     /// Weights used by NeuroFlux to compute rewards.
     pub reward_weights: RewardWeights,
 }
 
+/// Default upper bound for `max_tips`.
+fn default_max_tips_limit() -> usize {
+    64
+}
+
+/// Default step cap: a single action may sweep a parameter's whole range.
+fn default_max_step_fraction() -> f64 {
+    1.0
+}
+
+impl ConsensusEngineConfig {
+    /// Check that every `[min, max]` bound pair is ordered and the entropy
+    /// bounds are finite, so the tuned values can be clamped into them.
+    pub fn validate(&self) -> Result<(), EngineError> {
+        if !self.min_entropy.is_finite() || !self.max_entropy.is_finite() {
+            return Err(EngineError::InvalidConfig(format!(
+                "entropy bounds must be finite, got [{}, {}]",
+                self.min_entropy, self.max_entropy
+            )));
+        }
+        if self.min_entropy > self.max_entropy {
+            return Err(EngineError::InvalidConfig(format!(
+                "min_entropy {} exceeds max_entropy {}",
+                self.min_entropy, self.max_entropy
+            )));
+        }
+        if self.min_tips > self.max_tips_limit {
+            return Err(EngineError::InvalidConfig(format!(
+                "min_tips {} exceeds max_tips_limit {}",
+                self.min_tips, self.max_tips_limit
+            )));
+        }
+        if self.min_block_interval_ms > self.max_block_interval_ms {
+            return Err(EngineError::InvalidConfig(format!(
+                "min_block_interval_ms {} exceeds max_block_interval_ms {}",
+                self.min_block_interval_ms, self.max_block_interval_ms
+            )));
+        }
+        if self.min_branches > self.branch_limit {
+            return Err(EngineError::InvalidConfig(format!(
+                "min_branches {} exceeds branch_limit {}",
+                self.min_branches, self.branch_limit
+            )));
+        }
+        Ok(())
+    }
+}
+
 impl Default for ConsensusEngineConfig {
     fn default() -> Self {
         ConsensusEngineConfig {
             entropy_finality: 10.0,
+            min_finality_depth: 6,
             min_entropy: 1.0,
             max_entropy: 50.0,
             min_tips: 1,
             max_tips: 8,
+            max_tips_limit: default_max_tips_limit(),
+            block_interval_ms: 1000,
+            min_block_interval_ms: 100,
+            max_block_interval_ms: 10_000,
+            max_branches: 1024,
+            min_branches: 1,
+            branch_limit: 4096,
+            max_step_fraction: default_max_step_fraction(),
             reward_weights: RewardWeights {
                 tps: 0.7,
                 latency: 0.2,
@@ -70,6 +181,31 @@ impl Default for ConsensusEngineConfig {
     }
 }
 
+/// Snapshot of a consensus engine, and optionally its NeuroFlux agent, that
+/// a validator persists on shutdown and restores on restart so tuning
+/// resumes instead of cold-starting.
+///
+/// Scalar fields come before `config` so the checkpoint also serializes to TOML.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineCheckpoint {
+    /// Number of open tips when the checkpoint was taken.
+    pub tip_count: usize,
+    /// Height of the newest accepted block.
+    pub height: u64,
+    /// Cognitive entropy of the DAG.
+    pub cognitive_entropy: f64,
+    /// Target transactions (or blocks) per second.
+    pub target_tps: u64,
+    /// Maximum tolerated latency (ms) for reward calculation.
+    pub max_latency_ms: f64,
+    /// Consensus parameters, including those NeuroFlux has tuned.
+    pub config: ConsensusEngineConfig,
+    /// Learned NeuroFlux agent, if the checkpoint came from
+    /// `ConsensusNeuroFlux::checkpoint`.
+    #[serde(default)]
+    pub agent: Option<NeuroFluxAgent>,
+}
+
 /// The consensus engine drives QMesh block production and tip‐selection,
 /// and exposes hooks for entanglement propagation and causal reflection.
 #[derive(Debug)]
@@ -80,6 +216,8 @@ pub struct ConsensusEngine {
     pub tip_count: usize,
     /// Observed average block latency (ms).
     pub avg_latency_ms: f64,
+    /// Observed standard deviation of block latency (ms).
+    pub latency_stddev_ms: f64,
     /// Current cognitive entropy of the DAG.
     pub cognitive_entropy: f64,
     /// Current fork rate (fraction of orphaned blocks).
@@ -90,6 +228,8 @@ pub struct ConsensusEngine {
     pub target_tps: u64,
     /// Maximum tolerated latency (ms) for reward calculation.
     pub max_latency_ms: f64,
+    /// Height of the newest block the engine has accepted.
+    pub height: u64,
     // Internal mocks/testing capacity:
     entanglement_capacity: usize,
     causal_reflection_capacity: usize,
@@ -102,16 +242,97 @@ impl ConsensusEngine {
             config,
             tip_count: 0,
             avg_latency_ms: 0.0,
+            latency_stddev_ms: 0.0,
             cognitive_entropy: 0.0,
             fork_rate: 0.0,
             measured_tps: 0,
             target_tps: 1,
             max_latency_ms: 1.0,
+            height: 0,
             entanglement_capacity: 0,
             causal_reflection_capacity: 0,
         }
     }
 
+    /// Create an engine whose tunable parameters resume from `prior`, a
+    /// previously persisted configuration, instead of `config`'s defaults.
+    ///
+    /// Bounds and reward weights come from `config`; the tuned values
+    /// (`entropy_finality`, `max_tips`, `block_interval_ms`, `max_branches`)
+    /// are taken from `prior` and clamped into those bounds, so NeuroFlux
+    /// learning resumes near the previous optimum. A non-finite prior
+    /// `entropy_finality` is ignored in favour of `config`'s.
+    ///
+    /// Fails with [`EngineError::InvalidConfig`] if `config`'s bounds do not
+    /// pass [`ConsensusEngineConfig::validate`].
+    pub fn warm_start(
+        config: ConsensusEngineConfig,
+        prior: &ConsensusEngineConfig,
+    ) -> Result<Self, EngineError> {
+        config.validate()?;
+        let mut config = config;
+        if prior.entropy_finality.is_finite() {
+            config.entropy_finality = prior
+                .entropy_finality
+                .clamp(config.min_entropy, config.max_entropy);
+        }
+        config.max_tips = prior.max_tips.clamp(config.min_tips, config.max_tips_limit);
+        config.block_interval_ms = prior
+            .block_interval_ms
+            .clamp(config.min_block_interval_ms, config.max_block_interval_ms);
+        config.max_branches = prior
+            .max_branches
+            .clamp(config.min_branches, config.branch_limit);
+        Ok(ConsensusEngine::new(config))
+    }
+
+    /// Snapshot the engine's configuration and tip state; see
+    /// [`EngineCheckpoint`]. The checkpoint carries no agent.
+    pub fn checkpoint(&self) -> EngineCheckpoint {
+        EngineCheckpoint {
+            tip_count: self.tip_count,
+            height: self.height,
+            cognitive_entropy: self.cognitive_entropy,
+            target_tps: self.target_tps,
+            max_latency_ms: self.max_latency_ms,
+            config: self.config.clone(),
+            agent: None,
+        }
+    }
+
+    /// Rebuild an engine from `checkpoint`, taking its configuration exactly
+    /// as saved. Per-epoch measurements (latency, fork rate, measured TPS)
+    /// start afresh.
+    pub fn restore(checkpoint: &EngineCheckpoint) -> Self {
+        let mut engine = ConsensusEngine::new(checkpoint.config.clone());
+        engine.tip_count = checkpoint.tip_count;
+        engine.height = checkpoint.height;
+        engine.cognitive_entropy = checkpoint.cognitive_entropy;
+        engine.target_tps = checkpoint.target_tps;
+        engine.max_latency_ms = checkpoint.max_latency_ms;
+        engine
+    }
+
+    /// Accept `block` into the chain, advancing `height` if the block is
+    /// the newest seen.
+    pub fn accept_block(&mut self, block: &Block) {
+        self.height = self.height.max(block.height);
+    }
+
+    /// Number of blocks built on top of `block`, measured against the
+    /// engine's current `height`.
+    pub fn confirmations(&self, block: &Block) -> u64 {
+        self.height.saturating_sub(block.height)
+    }
+
+    /// Whether `block` is final: its entropy has settled to at most
+    /// `entropy_finality` and it is buried at least `min_finality_depth`
+    /// blocks deep.
+    pub fn is_final(&self, block: &Block) -> bool {
+        block.entropy <= self.config.entropy_finality
+            && self.confirmations(block) >= self.config.min_finality_depth
+    }
+
     /// Produce blocks for one epoch.  In a real implementation this
     /// would assemble transactions, compute entropy, broadcast the block, etc.
     pub fn produce_blocks(&mut self) {
@@ -147,3 +368,157 @@ impl ConsensusEngine {
         self.causal_reflection_capacity = cap;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dag_node_converts_to_block() {
+        let mut dag = EntropicDag::new(&Default::default());
+        let genesis = dag.add_node("genesis".into(), QNum::from_digits(&[1]));
+        let idx = dag.add_node("node_7".into(), QNum::from_digits(&[4, 2, 9]));
+        dag.add_edge(genesis, idx, 1.0).unwrap();
+
+        let root = Block::from_dag_node(&dag, genesis);
+        assert!(root.parents.is_empty());
+        assert_eq!(root.height, 0);
+
+        let node = dag.node_data(idx);
+        let block = Block::from_dag_node(&dag, idx);
+        assert_eq!(block.id.clone().measure(), node.state.clone().measure());
+        assert_eq!(block.payload, b"node_7".to_vec());
+        assert_eq!(block.entropy, node.state.entropy());
+        assert_eq!(block.parents, vec![QNum::from_digits(&[1])]);
+        assert_eq!(block.height, 1);
+    }
+
+    #[test]
+    fn accepting_blocks_advances_height() {
+        let mut engine = ConsensusEngine::mock();
+        let deep = block_at(engine.config.min_finality_depth + 3, 0.0);
+        let buried = block_at(3, 0.0);
+        assert!(!engine.is_final(&buried));
+
+        engine.accept_block(&deep);
+        assert_eq!(engine.height, deep.height);
+        assert!(engine.is_final(&buried));
+
+        // an older block does not move the height back
+        engine.accept_block(&buried);
+        assert_eq!(engine.height, deep.height);
+    }
+
+    fn block_at(height: u64, entropy: f64) -> Block {
+        Block {
+            id: QNum::from_digits(&[1]),
+            parents: Vec::new(),
+            payload: Vec::new(),
+            entropy,
+            timestamp: 0,
+            height,
+        }
+    }
+
+    #[test]
+    fn low_entropy_block_is_final_once_deep_enough() {
+        let mut engine = ConsensusEngine::mock();
+        engine.height = 20;
+        let depth = engine.config.min_finality_depth;
+        let block = block_at(20 - depth, engine.config.entropy_finality - 1.0);
+        assert_eq!(engine.confirmations(&block), depth);
+        assert!(engine.is_final(&block));
+
+        // right at the entropy threshold still counts
+        assert!(engine.is_final(&block_at(0, engine.config.entropy_finality)));
+    }
+
+    #[test]
+    fn shallow_or_high_entropy_block_is_pending() {
+        let mut engine = ConsensusEngine::mock();
+        engine.height = 20;
+        let depth = engine.config.min_finality_depth;
+        let calm = engine.config.entropy_finality - 1.0;
+
+        // too shallow, even with low entropy
+        assert!(!engine.is_final(&block_at(21 - depth, calm)));
+        // a block ahead of the engine has no confirmations
+        assert_eq!(engine.confirmations(&block_at(25, calm)), 0);
+        assert!(!engine.is_final(&block_at(25, calm)));
+        // deep enough, but entropy has not settled
+        assert!(!engine.is_final(&block_at(0, engine.config.entropy_finality + 0.5)));
+    }
+
+    #[test]
+    fn warm_start_uses_prior_values() {
+        let prior = ConsensusEngineConfig {
+            entropy_finality: 27.5,
+            max_tips: 3,
+            block_interval_ms: 400,
+            max_branches: 64,
+            ..ConsensusEngineConfig::default()
+        };
+        let engine = ConsensusEngine::warm_start(ConsensusEngineConfig::default(), &prior).unwrap();
+        assert_eq!(engine.config.entropy_finality, 27.5);
+        assert_eq!(engine.config.max_tips, 3);
+        assert_eq!(engine.config.block_interval_ms, 400);
+        assert_eq!(engine.config.max_branches, 64);
+    }
+
+    #[test]
+    fn checkpoint_restore_preserves_tuned_config() {
+        let mut engine = ConsensusEngine::mock();
+        engine.config.entropy_finality = 10.0 + 1.0 / 3.0;
+        engine.config.max_tips = 5;
+        engine.tip_count = 4;
+        engine.height = 812;
+
+        let saved = toml::to_string(&engine.checkpoint()).unwrap();
+        let checkpoint: EngineCheckpoint = toml::from_str(&saved).unwrap();
+        assert!(checkpoint.agent.is_none());
+        let restored = ConsensusEngine::restore(&checkpoint);
+
+        assert_eq!(restored.config.entropy_finality, engine.config.entropy_finality);
+        assert_eq!(restored.config.max_tips, 5);
+        assert_eq!(restored.config.reward_weights.tps, engine.config.reward_weights.tps);
+        assert_eq!(restored.tip_count, 4);
+        assert_eq!(restored.height, 812);
+    }
+
+    #[test]
+    fn warm_start_clamps_out_of_range_prior() {
+        let prior = ConsensusEngineConfig {
+            entropy_finality: 500.0,
+            max_tips: 500,
+            ..ConsensusEngineConfig::default()
+        };
+        let engine = ConsensusEngine::warm_start(ConsensusEngineConfig::default(), &prior).unwrap();
+        assert_eq!(engine.config.entropy_finality, engine.config.max_entropy);
+        assert_eq!(engine.config.max_tips, engine.config.max_tips_limit);
+    }
+
+    #[test]
+    fn warm_start_rejects_inverted_bounds() {
+        let config = ConsensusEngineConfig {
+            min_entropy: 50.0,
+            max_entropy: 1.0,
+            ..ConsensusEngineConfig::default()
+        };
+        let err =
+            ConsensusEngine::warm_start(config, &ConsensusEngineConfig::default()).unwrap_err();
+        assert!(matches!(err, EngineError::InvalidConfig(_)));
+        assert!(err.to_string().contains("min_entropy"));
+
+        let config = ConsensusEngineConfig {
+            max_entropy: f64::NAN,
+            ..ConsensusEngineConfig::default()
+        };
+        assert!(ConsensusEngine::warm_start(config, &ConsensusEngineConfig::default()).is_err());
+
+        let config = ConsensusEngineConfig {
+            min_tips: 100,
+            ..ConsensusEngineConfig::default()
+        };
+        assert!(ConsensusEngine::warm_start(config, &ConsensusEngineConfig::default()).is_err());
+    }
+}
@@ -6,9 +6,11 @@
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
 
+use std::fmt;
+
 use crate::config::WasmConfig;
 use thiserror::Error;
-use wasmtime::{Engine, Module, Store, Instance, Func, Linker, Caller, Val};
+use wasmtime::{Config, Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder, Val};
 
 /// Errors that can occur during WASM compilation or execution.
 #[derive(Debug, Error)]
@@ -28,12 +30,23 @@ pub enum WasmError {
 }
 
 /// Executor for QBLang‐compiled WASM contracts.
+///
+/// Each call runs in a fresh store metered with `gas_limit` units of fuel and
+/// capped at `memory_limit` bytes of linear memory.
 #[derive(Clone)]
 pub struct WasmExecutor {
     engine: Engine,
     cfg: WasmConfig,
 }
 
+impl fmt::Debug for WasmExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WasmExecutor")
+            .field("cfg", &self.cfg)
+            .finish_non_exhaustive()
+    }
+}
+
 impl WasmExecutor {
     /// Create a new executor with the given WASM configuration.
     ///
@@ -41,8 +54,9 @@ impl WasmExecutor {
     ///
     /// * `cfg` – contains `memory_limit` and `gas_limit` for instances.
     pub fn new(cfg: &WasmConfig) -> Self {
-        // In a real implementation we might configure fuel & memory via StoreLimits
-        let engine = Engine::default();
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config).expect("fuel metering is a valid engine setting");
         WasmExecutor {
             engine,
             cfg: cfg.clone(),
@@ -69,12 +83,18 @@ impl WasmExecutor {
         let module = Module::from_binary(&self.engine, wasm_bytes)
             .map_err(|e| WasmError::Compile(e.to_string()))?;
 
-        // 2. Create store (with no initial host state)
-        let mut store = Store::new(&self.engine, ());
+        // 2. Create a store limited to the configured memory and gas
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(usize::try_from(self.cfg.memory_limit).unwrap_or(usize::MAX))
+            .build();
+        let mut store = Store::new(&self.engine, limits);
+        store.limiter(|limits: &mut StoreLimits| limits);
+        store
+            .set_fuel(self.cfg.gas_limit)
+            .map_err(|e| WasmError::Execution(e.to_string()))?;
 
         // 3. Instantiate
-        //    In real code, we'd configure memory & gas via resource limits here
-        let mut linker = Linker::new(&self.engine);
+        let linker = Linker::new(&self.engine);
         let instance = linker
             .instantiate(&mut store, &module)
             .map_err(|e| WasmError::Instantiate(e.to_string()))?;
@@ -86,8 +106,8 @@ impl WasmExecutor {
 
         // 5. Call
         //    Support up to one return value for simplicity
-        let results = func
-            .call(&mut store, params)
+        let mut results = vec![Val::I32(0); func.ty(&store).results().len()];
+        func.call(&mut store, params, &mut results)
             .map_err(|e| WasmError::Execution(e.to_string()))?;
 
         // 6. Return first result or None
@@ -101,14 +121,16 @@ mod tests {
     use wasmtime::Val;
 
     /// A minimal Wasm module (compiled from WAT) that exports `add_one`.
-    const ADD_ONE_WASM: &[u8] = &wat::parse_str(r#"
-        (module
-          (func $add_one (export "add_one") (param i32) (result i32)
-            local.get 0
-            i32.const 1
-            i32.add)
-        )
-    "#).unwrap();
+    fn add_one_wasm() -> Vec<u8> {
+        wat::parse_str(r#"
+            (module
+              (func $add_one (export "add_one") (param i32) (result i32)
+                local.get 0
+                i32.const 1
+                i32.add)
+            )
+        "#).unwrap()
+    }
 
     #[test]
     fn execute_add_one_success() {
@@ -118,15 +140,15 @@ mod tests {
         };
         let exec = WasmExecutor::new(&cfg);
         let param = Val::I32(41);
-        let result = exec.execute(ADD_ONE_WASM, "add_one", &[param]).unwrap();
-        assert_eq!(result, Some(Val::I32(42)));
+        let result = exec.execute(&add_one_wasm(), "add_one", &[param]).unwrap();
+        assert_eq!(result.and_then(|v| v.i32()), Some(42));
     }
 
     #[test]
     fn missing_function_returns_error() {
         let cfg = WasmConfig::default();
         let exec = WasmExecutor::new(&cfg);
-        let err = exec.execute(ADD_ONE_WASM, "nonexistent", &[]).unwrap_err();
+        let err = exec.execute(&add_one_wasm(), "nonexistent", &[]).unwrap_err();
         match err {
             WasmError::FunctionNotFound(name) => assert_eq!(name, "nonexistent"),
             _ => panic!("expected FunctionNotFound"),
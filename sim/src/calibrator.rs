@@ -0,0 +1,162 @@
+//! Sim-to-Runtime Calibration for Qublis‐sim — Qublis v2.0
+//!
+//! Fits the simulator's throughput and latency parameters to metrics observed
+//! on a live runtime. Observations are read from the runtime's Prometheus
+//! export (`<metric_name> <value>` per line) and blended into a `SimConfig`
+//! by exponential smoothing, so repeated calibrations converge on the
+//! observed values while damping noise from any single sample.
+//!
+//! The gauge names below match those the runtime's `ConsensusMetrics`
+//! exports on every NeuroFlux tick.
+
+#![deny(missing_docs)]
+#![forbid(unsafe_code)]
+
+use crate::config::SimConfig;
+
+/// Runtime gauge carrying measured transactions per second.
+pub const TPS_GAUGE: &str = "runtime_tps";
+/// Runtime gauge carrying measured mean latency (ms).
+pub const LATENCY_MEAN_GAUGE: &str = "runtime_latency_mean_ms";
+/// Runtime gauge carrying measured latency standard deviation (ms).
+pub const LATENCY_STDDEV_GAUGE: &str = "runtime_latency_stddev_ms";
+
+/// Default fraction of the gap to the observed value closed per calibration.
+pub const DEFAULT_CALIBRATION_RATE: f64 = 0.5;
+
+/// Metrics observed on a running node; absent values leave the config untouched.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ObservedMetrics {
+    /// Measured transactions per second.
+    pub tps: Option<f64>,
+    /// Measured mean latency (ms).
+    pub latency_mean_ms: Option<f64>,
+    /// Measured latency standard deviation (ms).
+    pub latency_stddev_ms: Option<f64>,
+}
+
+impl ObservedMetrics {
+    /// Parse observations from `ConsensusMetrics::export_prometheus` output.
+    ///
+    /// Unknown metrics and unparsable lines are skipped.
+    pub fn from_prometheus(text: &str) -> Self {
+        let mut observed = ObservedMetrics::default();
+        for line in text.lines() {
+            let mut parts = line.split_whitespace();
+            let (Some(name), Some(value)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            let Ok(value) = value.parse::<f64>() else {
+                continue;
+            };
+            match name {
+                TPS_GAUGE => observed.tps = Some(value),
+                LATENCY_MEAN_GAUGE => observed.latency_mean_ms = Some(value),
+                LATENCY_STDDEV_GAUGE => observed.latency_stddev_ms = Some(value),
+                _ => {}
+            }
+        }
+        observed
+    }
+}
+
+/// `SimCalibrator` fits a `SimConfig` to observed runtime metrics.
+#[derive(Debug)]
+pub struct SimCalibrator {
+    config: SimConfig,
+    rate: f64,
+}
+
+impl SimCalibrator {
+    /// Construct a calibrator starting from the given simulation configuration.
+    pub fn new(config: &SimConfig) -> Self {
+        SimCalibrator {
+            config: config.clone(),
+            rate: DEFAULT_CALIBRATION_RATE,
+        }
+    }
+
+    /// Set the smoothing rate, clamped to `(0, 1]`; `1.0` adopts observations outright.
+    pub fn with_rate(mut self, rate: f64) -> Self {
+        self.rate = rate.clamp(f64::EPSILON, 1.0);
+        self
+    }
+
+    /// The current fitted configuration.
+    pub fn config(&self) -> &SimConfig {
+        &self.config
+    }
+
+    /// Move `target_tps`, `latency_mean_ms` and `latency_stddev_ms` toward the
+    /// observed values and return the fitted configuration.
+    ///
+    /// Missing, negative or non-finite observations are ignored.
+    pub fn calibrate(&mut self, observed: &ObservedMetrics) -> SimConfig {
+        let rate = self.rate;
+        let blend = |current: f64, obs: Option<f64>| match obs {
+            Some(v) if v.is_finite() && v >= 0.0 => current + rate * (v - current),
+            _ => current,
+        };
+
+        self.config.target_tps = blend(self.config.target_tps as f64, observed.tps).round() as u64;
+        self.config.latency_mean_ms = blend(self.config.latency_mean_ms, observed.latency_mean_ms);
+        self.config.latency_stddev_ms =
+            blend(self.config.latency_stddev_ms, observed.latency_stddev_ms);
+        self.config.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runtime_export() -> String {
+        format!(
+            "neuroflux_ticks 12\n{} 5000\n{} 40.0\n{} 4.0\n",
+            TPS_GAUGE, LATENCY_MEAN_GAUGE, LATENCY_STDDEV_GAUGE
+        )
+    }
+
+    #[test]
+    fn parses_runtime_prometheus_export() {
+        let observed = ObservedMetrics::from_prometheus(&runtime_export());
+        assert_eq!(observed.tps, Some(5000.0));
+        assert_eq!(observed.latency_mean_ms, Some(40.0));
+        assert_eq!(observed.latency_stddev_ms, Some(4.0));
+    }
+
+    #[test]
+    fn calibration_moves_config_toward_observed() {
+        let cfg = SimConfig::default();
+        let observed = ObservedMetrics::from_prometheus(&runtime_export());
+        let mut calibrator = SimCalibrator::new(&cfg);
+
+        let fitted = calibrator.calibrate(&observed);
+        assert!(fitted.target_tps < cfg.target_tps && fitted.target_tps > 5000);
+        assert!(fitted.latency_mean_ms < cfg.latency_mean_ms && fitted.latency_mean_ms > 40.0);
+        assert!(fitted.latency_stddev_ms < cfg.latency_stddev_ms && fitted.latency_stddev_ms > 4.0);
+
+        for _ in 0..40 {
+            calibrator.calibrate(&observed);
+        }
+        let fitted = calibrator.config();
+        // integer rounding can stall one transaction short of the target
+        assert!(fitted.target_tps.abs_diff(5000) <= 1);
+        assert!((fitted.latency_mean_ms - 40.0).abs() < 1e-6);
+        assert!((fitted.latency_stddev_ms - 4.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn missing_or_invalid_observations_leave_config() {
+        let cfg = SimConfig::default();
+        let observed = ObservedMetrics {
+            tps: None,
+            latency_mean_ms: Some(f64::NAN),
+            latency_stddev_ms: Some(-1.0),
+        };
+        let fitted = SimCalibrator::new(&cfg).with_rate(1.0).calibrate(&observed);
+        assert_eq!(fitted.target_tps, cfg.target_tps);
+        assert_eq!(fitted.latency_mean_ms, cfg.latency_mean_ms);
+        assert_eq!(fitted.latency_stddev_ms, cfg.latency_stddev_ms);
+    }
+}
@@ -1,9 +1,13 @@
 //! Simulation Configuration
 //!
 //! Defines the `SimConfig` struct for the Qublis-sim crate, including
-//! parameters for TPS simulation, latency modeling, NeuroFlux runs,
-//! network topology size, and report generation.  Supports loading from TOML.
+//! parameters for TPS simulation (including latency backpressure), latency
+//! modeling (including the latency distribution shape), NeuroFlux runs,
+//! network topology size, churn and partitioning, report generation and an
+//! optional RNG seed. Supports loading from TOML.
 
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use serde::{Deserialize, Serialize};
 use std::{fs, path::Path};
 use thiserror::Error;
@@ -23,6 +27,11 @@ fn default_dimensions() -> usize {
     4
 }
 
+/// Default fraction of the offered load kept after a congested second.
+fn default_backpressure_factor() -> f64 {
+    0.5
+}
+
 /// Default mean latency in milliseconds.
 fn default_latency_mean_ms() -> f64 {
     100.0
@@ -33,6 +42,32 @@ fn default_latency_stddev_ms() -> f64 {
     20.0
 }
 
+/// Shape of the per-second latency distribution sampled by `LatencyWave`.
+///
+/// In TOML, written as a table tagged by `kind`, e.g.
+/// `latency_distribution = { kind = "pareto", alpha = 1.5 }`.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LatencyDistribution {
+    /// Normal with `latency_mean_ms` and `latency_stddev_ms`.
+    #[default]
+    Normal,
+    /// Log-normal whose own mean and standard deviation are
+    /// `latency_mean_ms` and `latency_stddev_ms`.
+    LogNormal,
+    /// Pareto with tail index `alpha` (> 1), scaled so its mean is
+    /// `latency_mean_ms`; `latency_stddev_ms` is not used.
+    Pareto {
+        /// Tail index; smaller values give heavier tails.
+        alpha: f64,
+    },
+    /// Resample uniformly from observed latencies (ms).
+    Empirical {
+        /// Observed latency samples (ms).
+        samples: Vec<f64>,
+    },
+}
+
 /// Default toggle for NeuroFlux‐driven optimization.
 fn default_neuroflux_enabled() -> bool {
     false
@@ -43,12 +78,40 @@ fn default_neuroflux_iterations() -> usize {
     10_000
 }
 
+/// Default number of non-improving NeuroFlux iterations before stopping early.
+fn default_convergence_patience() -> usize {
+    500
+}
+
+/// Default smallest `best_metric` gain that counts as NeuroFlux progress.
+fn default_convergence_epsilon() -> f64 {
+    1e-6
+}
+
 /// Default size of the simulated network (number of nodes).
 fn default_network_size() -> usize {
     1_000
 }
 
-/// Default report format (`"json"` or `"csv"`).
+/// Default per-second node churn rate.
+fn default_churn_rate() -> f64 {
+    0.0
+}
+
+/// Default per-second probability that a healthy network partitions.
+fn default_partition_probability() -> f64 {
+    0.0
+}
+
+/// Default time (seconds) a partition lasts before healing.
+fn default_partition_duration_secs() -> u64 {
+    10
+}
+
+/// Report formats accepted by `report_format`.
+pub const REPORT_FORMATS: [&str; 4] = ["json", "csv", "markdown", "html"];
+
+/// Default report format (`"json"`, `"csv"`, `"markdown"` or `"html"`).
 fn default_report_format() -> String {
     "json".into()
 }
@@ -69,6 +132,17 @@ pub struct SimConfig {
     #[serde(default = "default_target_tps")]
     pub target_tps: u64,
 
+    /// Latency (ms) above which offered load is throttled in the following
+    /// second; `None` runs the TPS simulation open-loop.
+    #[serde(default)]
+    pub backpressure_threshold_ms: Option<f64>,
+
+    /// Multiplier in `(0, 1]` applied to the effective TPS target after each
+    /// second over `backpressure_threshold_ms`; the target recovers by the
+    /// same factor, up to `target_tps`, after each second under it.
+    #[serde(default = "default_backpressure_factor")]
+    pub backpressure_factor: f64,
+
     /// Number of dimensions (for multidimensional views).
     #[serde(default = "default_dimensions")]
     pub dimensions: usize,
@@ -81,6 +155,10 @@ pub struct SimConfig {
     #[serde(default = "default_latency_stddev_ms")]
     pub latency_stddev_ms: f64,
 
+    /// Distribution latency samples are drawn from.
+    #[serde(default)]
+    pub latency_distribution: LatencyDistribution,
+
     /// Whether to enable NeuroFlux optimization simulation.
     #[serde(default = "default_neuroflux_enabled")]
     pub neuroflux_enabled: bool,
@@ -89,17 +167,45 @@ pub struct SimConfig {
     #[serde(default = "default_neuroflux_iterations")]
     pub neuroflux_iterations: usize,
 
+    /// Stop NeuroFlux early once `best_metric` has improved by less than
+    /// `convergence_epsilon` for this many consecutive iterations; `0` always
+    /// runs all `neuroflux_iterations`.
+    #[serde(default = "default_convergence_patience")]
+    pub convergence_patience: usize,
+
+    /// Smallest `best_metric` gain that resets the convergence patience.
+    #[serde(default = "default_convergence_epsilon")]
+    pub convergence_epsilon: f64,
+
     /// Size of the network (number of simulated nodes).
     #[serde(default = "default_network_size")]
     pub network_size: usize,
 
-    /// Output report format: `"json"` or `"csv"`.
+    /// Per-second churn: each online node leaves with this probability, and
+    /// on average `churn_rate * network_size` new nodes join.
+    #[serde(default = "default_churn_rate")]
+    pub churn_rate: f64,
+
+    /// Probability, checked each second while the network is whole, that it
+    /// splits into two partitions.
+    #[serde(default = "default_partition_probability")]
+    pub partition_probability: f64,
+
+    /// Seconds a partition lasts before the network heals.
+    #[serde(default = "default_partition_duration_secs")]
+    pub partition_duration_secs: u64,
+
+    /// Output report format: `"json"`, `"csv"`, `"markdown"` or `"html"`.
     #[serde(default = "default_report_format")]
     pub report_format: String,
 
     /// Whether to generate plots (requires `plotting` feature).
     #[serde(default = "default_enable_plotting")]
     pub enable_plotting: bool,
+
+    /// Seed for the simulators' RNGs; `None` draws fresh entropy every run.
+    #[serde(default)]
+    pub seed: Option<u64>,
 }
 
 impl Default for SimConfig {
@@ -107,14 +213,23 @@ impl Default for SimConfig {
         SimConfig {
             duration_secs: default_duration_secs(),
             target_tps: default_target_tps(),
+            backpressure_threshold_ms: None,
+            backpressure_factor: default_backpressure_factor(),
             dimensions: default_dimensions(),
             latency_mean_ms: default_latency_mean_ms(),
             latency_stddev_ms: default_latency_stddev_ms(),
+            latency_distribution: LatencyDistribution::default(),
             neuroflux_enabled: default_neuroflux_enabled(),
             neuroflux_iterations: default_neuroflux_iterations(),
+            convergence_patience: default_convergence_patience(),
+            convergence_epsilon: default_convergence_epsilon(),
             network_size: default_network_size(),
+            churn_rate: default_churn_rate(),
+            partition_probability: default_partition_probability(),
+            partition_duration_secs: default_partition_duration_secs(),
             report_format: default_report_format(),
             enable_plotting: default_enable_plotting(),
+            seed: None,
         }
     }
 }
@@ -128,15 +243,111 @@ pub enum ConfigError {
     /// TOML parse error.
     #[error("TOML parse error: {0}")]
     Parse(#[from] toml::de::Error),
+    /// A parameter is outside its valid range.
+    #[error("invalid SimConfig: {0}")]
+    Invalid(String),
 }
 
 impl SimConfig {
     /// Load a `SimConfig` from the given TOML file path.
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
         let s = fs::read_to_string(path)?;
-        let cfg = toml::from_str(&s)?;
+        let cfg: SimConfig = toml::from_str(&s)?;
+        cfg.validate()?;
         Ok(cfg)
     }
+
+    /// Check that the parameters describe a runnable simulation, returning
+    /// `ConfigError::Invalid` naming the first offending field otherwise.
+    ///
+    /// Requires a non-zero duration, TPS target, dimension count and network
+    /// size, a finite non-negative latency standard deviation and
+    /// `convergence_epsilon`, a `churn_rate`
+    /// and `partition_probability` in `[0, 1]`, a `backpressure_factor` in
+    /// `(0, 1]` with a finite non-negative threshold, and a `report_format`
+    /// from [`REPORT_FORMATS`].
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let positive = [
+            ("duration_secs", self.duration_secs == 0),
+            ("target_tps", self.target_tps == 0),
+            ("dimensions", self.dimensions == 0),
+            ("network_size", self.network_size == 0),
+        ];
+        if let Some((field, _)) = positive.iter().find(|(_, zero)| *zero) {
+            return Err(ConfigError::Invalid(format!("{} must be at least 1", field)));
+        }
+        let non_negative = [
+            ("latency_mean_ms", self.latency_mean_ms),
+            ("latency_stddev_ms", self.latency_stddev_ms),
+            ("convergence_epsilon", self.convergence_epsilon),
+        ];
+        for (field, value) in non_negative {
+            if !(value.is_finite() && value >= 0.0) {
+                return Err(ConfigError::Invalid(format!(
+                    "{} must be finite and non-negative, got {}",
+                    field, value
+                )));
+            }
+        }
+        match &self.latency_distribution {
+            LatencyDistribution::Pareto { alpha } if !(alpha.is_finite() && *alpha > 1.0) => {
+                return Err(ConfigError::Invalid(format!(
+                    "latency_distribution pareto alpha must be finite and greater than 1, got {}",
+                    alpha
+                )));
+            }
+            LatencyDistribution::Empirical { samples } if samples.is_empty() => {
+                return Err(ConfigError::Invalid(
+                    "latency_distribution empirical samples must not be empty".into(),
+                ));
+            }
+            _ => {}
+        }
+        if let Some(threshold) = self.backpressure_threshold_ms {
+            if !(threshold.is_finite() && threshold >= 0.0) {
+                return Err(ConfigError::Invalid(format!(
+                    "backpressure_threshold_ms must be finite and non-negative, got {}",
+                    threshold
+                )));
+            }
+        }
+        if !(self.backpressure_factor > 0.0 && self.backpressure_factor <= 1.0) {
+            return Err(ConfigError::Invalid(format!(
+                "backpressure_factor must be in (0, 1], got {}",
+                self.backpressure_factor
+            )));
+        }
+        let probabilities = [
+            ("churn_rate", self.churn_rate),
+            ("partition_probability", self.partition_probability),
+        ];
+        for (field, p) in probabilities {
+            if !(0.0..=1.0).contains(&p) {
+                return Err(ConfigError::Invalid(format!(
+                    "{} must be in [0, 1], got {}",
+                    field, p
+                )));
+            }
+        }
+        if !REPORT_FORMATS.contains(&self.report_format.as_str()) {
+            return Err(ConfigError::Invalid(format!(
+                "report_format must be one of {}, got {:?}",
+                REPORT_FORMATS.join(", "),
+                self.report_format
+            )));
+        }
+        Ok(())
+    }
+
+    /// RNG for one simulator. With `seed` set, each `stream` gets its own
+    /// reproducible sequence, so a simulator draws the same values whether
+    /// it runs alone, sequentially or on its own thread.
+    pub(crate) fn rng(&self, stream: u64) -> StdRng {
+        match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed ^ stream.wrapping_mul(0x9E37_79B9_7F4A_7C15)),
+            None => StdRng::from_entropy(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -150,14 +361,23 @@ mod tests {
         let cfg = SimConfig::default();
         assert_eq!(cfg.duration_secs, 60);
         assert_eq!(cfg.target_tps, 1_000_000);
+        assert_eq!(cfg.backpressure_threshold_ms, None);
+        assert_eq!(cfg.backpressure_factor, 0.5);
         assert_eq!(cfg.dimensions, 4);
         assert!((cfg.latency_mean_ms - 100.0).abs() < 1e-12);
         assert!((cfg.latency_stddev_ms - 20.0).abs() < 1e-12);
+        assert_eq!(cfg.latency_distribution, LatencyDistribution::Normal);
         assert!(!cfg.neuroflux_enabled);
         assert_eq!(cfg.neuroflux_iterations, 10_000);
+        assert_eq!(cfg.convergence_patience, 500);
+        assert_eq!(cfg.convergence_epsilon, 1e-6);
         assert_eq!(cfg.network_size, 1_000);
+        assert_eq!(cfg.churn_rate, 0.0);
+        assert_eq!(cfg.partition_probability, 0.0);
+        assert_eq!(cfg.partition_duration_secs, 10);
         assert_eq!(cfg.report_format, "json");
         assert!(!cfg.enable_plotting);
+        assert_eq!(cfg.seed, None);
     }
 
     #[test]
@@ -165,29 +385,108 @@ mod tests {
         let toml = r#"
             duration_secs = 120
             target_tps = 5000000
+            backpressure_threshold_ms = 250.0
+            backpressure_factor = 0.8
             dimensions = 8
             latency_mean_ms = 50.5
             latency_stddev_ms = 5.2
+            latency_distribution = { kind = "pareto", alpha = 1.5 }
             neuroflux_enabled = true
             neuroflux_iterations = 20000
+            convergence_patience = 0
+            convergence_epsilon = 0.001
             network_size = 5000
+            churn_rate = 0.01
+            partition_probability = 0.25
+            partition_duration_secs = 30
             report_format = "csv"
             enable_plotting = true
+            seed = 7
         "#;
-        let mut file = NamedTempFile::new().unwrap();
+        let file = NamedTempFile::new().unwrap();
         fs::write(file.path(), toml).unwrap();
 
         let cfg = SimConfig::load(file.path()).unwrap();
         assert_eq!(cfg.duration_secs, 120);
         assert_eq!(cfg.target_tps, 5_000_000);
+        assert_eq!(cfg.backpressure_threshold_ms, Some(250.0));
+        assert!((cfg.backpressure_factor - 0.8).abs() < 1e-12);
         assert_eq!(cfg.dimensions, 8);
         assert!((cfg.latency_mean_ms - 50.5).abs() < 1e-12);
         assert!((cfg.latency_stddev_ms - 5.2).abs() < 1e-12);
+        assert_eq!(cfg.latency_distribution, LatencyDistribution::Pareto { alpha: 1.5 });
         assert!(cfg.neuroflux_enabled);
         assert_eq!(cfg.neuroflux_iterations, 20_000);
+        assert_eq!(cfg.convergence_patience, 0);
+        assert!((cfg.convergence_epsilon - 0.001).abs() < 1e-12);
         assert_eq!(cfg.network_size, 5_000);
+        assert!((cfg.churn_rate - 0.01).abs() < 1e-12);
+        assert!((cfg.partition_probability - 0.25).abs() < 1e-12);
+        assert_eq!(cfg.partition_duration_secs, 30);
         assert_eq!(cfg.report_format, "csv");
         assert!(cfg.enable_plotting);
+        assert_eq!(cfg.seed, Some(7));
+    }
+
+    #[test]
+    fn validate_rejects_each_invalid_field() {
+        assert!(SimConfig::default().validate().is_ok());
+
+        let cases = [
+            ("duration_secs = 0", "duration_secs must be at least 1"),
+            ("target_tps = 0", "target_tps must be at least 1"),
+            ("dimensions = 0", "dimensions must be at least 1"),
+            ("network_size = 0", "network_size must be at least 1"),
+            ("latency_stddev_ms = -2.5", "latency_stddev_ms must be finite and non-negative, got -2.5"),
+            ("latency_stddev_ms = nan", "latency_stddev_ms must be finite and non-negative, got NaN"),
+            ("convergence_epsilon = -1.0", "convergence_epsilon must be finite and non-negative, got -1"),
+            ("backpressure_factor = 0.0", "backpressure_factor must be in (0, 1], got 0"),
+            ("churn_rate = -0.1", "churn_rate must be in [0, 1], got -0.1"),
+            ("partition_probability = 1.5", "partition_probability must be in [0, 1], got 1.5"),
+            ("report_format = \"pdf\"", "report_format must be one of json, csv, markdown, html, got \"pdf\""),
+        ];
+        for (toml, message) in cases {
+            assert_eq!(invalid_message(toml), message, "for `{}`", toml);
+        }
+    }
+
+    #[test]
+    fn validate_rejects_pareto_alpha_at_most_one() {
+        assert_eq!(
+            invalid_message("latency_distribution = { kind = \"pareto\", alpha = 1.0 }"),
+            "latency_distribution pareto alpha must be finite and greater than 1, got 1"
+        );
+    }
+
+    #[test]
+    fn validate_rejects_empty_empirical_samples() {
+        assert_eq!(
+            invalid_message("latency_distribution = { kind = \"empirical\", samples = [] }"),
+            "latency_distribution empirical samples must not be empty"
+        );
+    }
+
+    #[test]
+    fn validate_rejects_bad_latency_mean() {
+        assert_eq!(
+            invalid_message("latency_mean_ms = -1.0"),
+            "latency_mean_ms must be finite and non-negative, got -1"
+        );
+        assert_eq!(
+            invalid_message("latency_mean_ms = inf"),
+            "latency_mean_ms must be finite and non-negative, got inf"
+        );
+    }
+
+    /// Load `toml` as a config, expecting `ConfigError::Invalid`, and return
+    /// its message.
+    fn invalid_message(toml: &str) -> String {
+        let file = NamedTempFile::new().unwrap();
+        fs::write(file.path(), toml).unwrap();
+        match SimConfig::load(file.path()) {
+            Err(ConfigError::Invalid(msg)) => msg,
+            other => panic!("`{}` should be invalid, got {:?}", toml, other),
+        }
     }
 
     #[test]
@@ -198,7 +497,7 @@ mod tests {
 
     #[test]
     fn invalid_toml_errs_parse() {
-        let mut file = NamedTempFile::new().unwrap();
+        let file = NamedTempFile::new().unwrap();
         fs::write(file.path(), "not = valid = toml").unwrap();
         let err = SimConfig::load(file.path()).unwrap_err();
         matches!(err, ConfigError::Parse(_));
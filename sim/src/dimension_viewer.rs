@@ -0,0 +1,147 @@
+//! Dimension Viewer for Qublis‐sim — Qublis v2.0
+//!
+//! Models resource utilization across the configured number of dimensions.
+//! Each dimension's utilization is a smoothed random walk in [0.0, 1.0),
+//! sampled once per second for `duration_secs` seconds. The viewer exposes
+//! the full timeline (for animating the multi-dimensional resource plot) and
+//! an aggregate snapshot, and records Prometheus‐style metrics.
+
+#![deny(missing_docs)]
+#![forbid(unsafe_code)]
+
+use rand::Rng;
+use crate::{config::SimConfig, metrics::SimMetrics, types::DimensionView};
+
+/// Stream of `SimConfig::rng` reserved for the dimension viewer.
+const RNG_STREAM: u64 = 5;
+
+/// Weight of the previous second's utilization in each new sample.
+const SMOOTHING: f64 = 0.8;
+
+/// `DimensionViewer` models per‐dimension resource utilization.
+#[derive(Debug)]
+pub struct DimensionViewer {
+    config: SimConfig,
+    metrics: SimMetrics,
+}
+
+impl DimensionViewer {
+    /// Create a new viewer with the given configuration.
+    pub fn new(config: &SimConfig) -> Self {
+        DimensionViewer {
+            config: config.clone(),
+            metrics: SimMetrics::new(),
+        }
+    }
+
+    /// Aggregate snapshot: each dimension's mean utilization over the
+    /// simulated timeline (0.0 for a zero-length simulation).
+    ///
+    /// Records one `dimension_views` metric.
+    pub fn view(&mut self) -> DimensionView {
+        let timeline = self.sample_timeline();
+        let dimensions = self.config.dimensions;
+        let intervals = timeline.len().max(1) as f64;
+        let utilization = (0..dimensions)
+            .map(|d| {
+                let total: f64 = timeline.iter().map(|view| view.utilization[&d]).sum();
+                (d, total / intervals)
+            })
+            .collect();
+        self.metrics.record_dimension_views();
+        DimensionView { dimensions, utilization }
+    }
+
+    /// One view per simulated second across `duration_secs`, each covering
+    /// every dimension.
+    ///
+    /// Records a `dimension_views` metric per view.
+    pub fn view_timeline(&mut self) -> Vec<DimensionView> {
+        let timeline = self.sample_timeline();
+        for _ in &timeline {
+            self.metrics.record_dimension_views();
+        }
+        timeline
+    }
+
+    /// Metrics recorded by this viewer so far.
+    pub fn metrics(&self) -> &SimMetrics {
+        &self.metrics
+    }
+
+    /// Export internal metrics in Prometheus text format.
+    pub fn export_metrics(&self) -> String {
+        self.metrics.export_prometheus()
+    }
+
+    /// Sample the utilization random walk, one view per second.
+    fn sample_timeline(&self) -> Vec<DimensionView> {
+        let dimensions = self.config.dimensions;
+        let mut rng = self.config.rng(RNG_STREAM);
+        let mut current: Vec<f64> = (0..dimensions).map(|_| rng.gen_range(0.0..1.0)).collect();
+        let mut timeline = Vec::with_capacity(self.config.duration_secs as usize);
+        for t in 0..self.config.duration_secs {
+            if t > 0 {
+                for u in &mut current {
+                    *u = SMOOTHING * *u + (1.0 - SMOOTHING) * rng.gen_range(0.0..1.0);
+                }
+            }
+            timeline.push(DimensionView {
+                dimensions,
+                utilization: current.iter().copied().enumerate().collect(),
+            });
+        }
+        timeline
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SimConfig;
+
+    #[test]
+    fn timeline_has_one_view_per_second() {
+        let mut cfg = SimConfig::default();
+        cfg.duration_secs = 12;
+        cfg.dimensions = 5;
+        let mut viewer = DimensionViewer::new(&cfg);
+        let timeline = viewer.view_timeline();
+        assert_eq!(timeline.len(), 12);
+        for view in &timeline {
+            assert_eq!(view.dimensions, 5);
+            assert_eq!(view.utilization.len(), 5);
+            for d in 0..5 {
+                let u = view.utilization[&d];
+                assert!((0.0..1.0).contains(&u));
+            }
+        }
+        assert!(viewer.export_metrics().contains("sim_dimension_views 12"));
+    }
+
+    #[test]
+    fn view_is_timeline_mean() {
+        let mut cfg = SimConfig::default();
+        cfg.duration_secs = 8;
+        cfg.seed = Some(11);
+        let mut viewer = DimensionViewer::new(&cfg);
+        let timeline = viewer.view_timeline();
+        let snapshot = viewer.view();
+        assert_eq!(snapshot.dimensions, cfg.dimensions);
+        for d in 0..cfg.dimensions {
+            let mean = timeline.iter().map(|v| v.utilization[&d]).sum::<f64>() / 8.0;
+            assert!((snapshot.utilization[&d] - mean).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn zero_duration_yields_empty_timeline() {
+        let mut cfg = SimConfig::default();
+        cfg.duration_secs = 0;
+        let mut viewer = DimensionViewer::new(&cfg);
+        assert!(viewer.view_timeline().is_empty());
+        let snapshot = viewer.view();
+        assert!(snapshot.utilization.values().all(|&u| u == 0.0));
+        assert_eq!(snapshot.utilization.len(), cfg.dimensions);
+    }
+}
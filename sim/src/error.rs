@@ -72,7 +72,7 @@ mod tests {
 
     #[test]
     fn from_config_error() {
-        let cfg_err = ConfigError::Parse(toml::de::Error::custom("bad toml"));
+        let cfg_err = ConfigError::Parse(<toml::de::Error as serde::de::Error>::custom("bad toml"));
         let err: SimError = cfg_err.into();
         assert!(err.to_string().starts_with("configuration error:"));
     }
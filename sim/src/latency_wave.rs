@@ -1,21 +1,26 @@
 //! Latency Waveform Simulator for Qublis‐sim — Qublis v2.0
 //!
-//! Models network latency over time by sampling once per second from a
-//! normal distribution with configured mean and standard deviation (ms).
-//! Records samples and exposes Prometheus‐style metrics.
+//! Models network latency over time by sampling once per second from the
+//! configured `LatencyDistribution` (normal by default, or heavy-tailed
+//! log-normal / Pareto, or resampled observations) with the configured mean
+//! and standard deviation (ms). Records samples, reports p50/p95/p99, and
+//! exposes Prometheus‐style metrics, including a `latency_ms` histogram.
 
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
 
 use rand::Rng;
-use rand_distr::{Normal, Distribution};
+use rand_distr::{Distribution, LogNormal, Normal, Pareto};
 use crate::{
-    config::SimConfig,
+    config::{LatencyDistribution, SimConfig},
     error::SimError,
     metrics::SimMetrics,
     types::LatencyProfile,
 };
 
+/// Stream of `SimConfig::rng` reserved for the latency simulator.
+const RNG_STREAM: u64 = 2;
+
 /// `LatencyWave` runs a latency profile simulation.
 #[derive(Debug)]
 pub struct LatencyWave {
@@ -34,35 +39,111 @@ impl LatencyWave {
 
     /// Simulate latency over each second of the configured duration.
     ///
-    /// Samples latency (ms) from Normal(mean_ms, stddev_ms) per second.
+    /// Samples latency (ms) per second from `config.latency_distribution`,
+    /// clamped at zero, and reports the sample percentiles.
     pub fn simulate(&mut self) -> Result<LatencyProfile, SimError> {
         let dur = self.config.duration_secs;
         let mean = self.config.latency_mean_ms;
         let stddev = self.config.latency_stddev_ms;
-        let mut rng = rand::thread_rng();
-        let normal = Normal::new(mean, stddev)
-            .map_err(|e| SimError::LatencyError(format!("invalid distribution: {}", e)))?;
+        let mut rng = self.config.rng(RNG_STREAM);
+        let sampler = self.sampler()?;
 
         let mut samples = Vec::with_capacity(dur as usize);
         for t in 0..dur {
-            let sample = normal.sample(&mut rng).max(0.0);
+            let sample = sampler.sample(&mut rng).max(0.0);
             samples.push((t, sample));
             self.metrics.record_latency_sample();
+            self.metrics.observe_histogram("latency_ms", sample);
         }
 
+        let mut sorted: Vec<f64> = samples.iter().map(|&(_, l)| l).collect();
+        sorted.sort_by(f64::total_cmp);
         Ok(LatencyProfile {
             mean_ms: mean,
             stddev_ms: stddev,
+            p50_ms: percentile(&sorted, 0.50),
+            p95_ms: percentile(&sorted, 0.95),
+            p99_ms: percentile(&sorted, 0.99),
             samples,
         })
     }
 
+    /// Build the sampler for the configured distribution.
+    fn sampler(&self) -> Result<Sampler, SimError> {
+        let mean = self.config.latency_mean_ms;
+        let stddev = self.config.latency_stddev_ms;
+        let invalid = |e: &dyn std::fmt::Display| {
+            SimError::LatencyError(format!("invalid distribution: {}", e))
+        };
+        Ok(match &self.config.latency_distribution {
+            LatencyDistribution::Normal => {
+                Sampler::Normal(Normal::new(mean, stddev).map_err(|e| invalid(&e))?)
+            }
+            LatencyDistribution::LogNormal => {
+                if mean <= 0.0 {
+                    return Err(invalid(&"log-normal mean must be positive"));
+                }
+                // match the log-normal's own mean and stddev to the config
+                let sigma2 = (1.0 + (stddev / mean).powi(2)).ln();
+                Sampler::LogNormal(
+                    LogNormal::new(mean.ln() - sigma2 / 2.0, sigma2.sqrt()).map_err(|e| invalid(&e))?,
+                )
+            }
+            LatencyDistribution::Pareto { alpha } => {
+                if *alpha <= 1.0 || mean <= 0.0 {
+                    return Err(invalid(&"Pareto needs alpha > 1 and a positive mean"));
+                }
+                // mean of Pareto(scale, α) is α·scale / (α − 1)
+                Sampler::Pareto(Pareto::new(mean * (alpha - 1.0) / alpha, *alpha).map_err(|e| invalid(&e))?)
+            }
+            LatencyDistribution::Empirical { samples } => {
+                if samples.is_empty() {
+                    return Err(invalid(&"no empirical samples"));
+                }
+                Sampler::Empirical(samples.clone())
+            }
+        })
+    }
+
+    /// Metrics recorded by this simulator so far.
+    pub fn metrics(&self) -> &SimMetrics {
+        &self.metrics
+    }
+
     /// Export internal metrics in Prometheus text format.
     pub fn export_metrics(&self) -> String {
         self.metrics.export_prometheus()
     }
 }
 
+/// A configured latency distribution, ready to sample.
+enum Sampler {
+    Normal(Normal<f64>),
+    LogNormal(LogNormal<f64>),
+    Pareto(Pareto<f64>),
+    Empirical(Vec<f64>),
+}
+
+impl Distribution<f64> for Sampler {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        match self {
+            Sampler::Normal(d) => d.sample(rng),
+            Sampler::LogNormal(d) => d.sample(rng),
+            Sampler::Pareto(d) => d.sample(rng),
+            Sampler::Empirical(samples) => samples[rng.gen_range(0..samples.len())],
+        }
+    }
+}
+
+/// Nearest-rank percentile `q` (in `[0, 1]`) of ascending `sorted`; 0 if empty.
+pub(crate) fn percentile(sorted: &[f64], q: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (q * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -104,6 +185,8 @@ mod tests {
         let prom = lw.export_metrics();
         // Expect "sim_latency_samples 4" entry
         assert!(prom.contains("sim_latency_samples 4"));
+        assert!(prom.contains("sim_latency_ms_count 4"));
+        assert!(prom.contains("sim_latency_ms_bucket{le=\"+Inf\"} 4"));
     }
 
     #[test]
@@ -117,4 +200,59 @@ mod tests {
         let prom = lw.export_metrics();
         assert!(!prom.contains("sim_latency_samples"));
     }
+
+    fn profile_for(distribution: LatencyDistribution) -> LatencyProfile {
+        let mut cfg = SimConfig::default();
+        cfg.duration_secs = 20_000;
+        cfg.latency_mean_ms = 100.0;
+        cfg.latency_stddev_ms = 10.0;
+        cfg.latency_distribution = distribution;
+        LatencyWave::new(&cfg).simulate().unwrap()
+    }
+
+    #[test]
+    fn pareto_tail_far_above_mean() {
+        let profile = profile_for(LatencyDistribution::Pareto { alpha: 1.5 });
+        assert!(profile.p50_ms <= profile.p95_ms && profile.p95_ms <= profile.p99_ms);
+        // p99 of Pareto(α = 1.5) sits at ~7× the mean
+        assert!(profile.p99_ms > 3.0 * profile.mean_ms, "p99 {}", profile.p99_ms);
+    }
+
+    #[test]
+    fn normal_percentiles_stay_near_mean() {
+        let profile = profile_for(LatencyDistribution::Normal);
+        assert!((profile.p50_ms - 100.0).abs() < 2.0, "p50 {}", profile.p50_ms);
+        // p99 of Normal(100, 10) is ~123
+        assert!(profile.p99_ms > profile.mean_ms && profile.p99_ms < 1.5 * profile.mean_ms);
+    }
+
+    #[test]
+    fn log_normal_and_empirical_distributions() {
+        let log_normal = profile_for(LatencyDistribution::LogNormal);
+        let mean: f64 = log_normal.samples.iter().map(|&(_, l)| l).sum::<f64>()
+            / log_normal.samples.len() as f64;
+        assert!((mean - 100.0).abs() < 2.0, "log-normal mean {}", mean);
+
+        let observed = vec![10.0, 20.0, 30.0];
+        let empirical = profile_for(LatencyDistribution::Empirical { samples: observed.clone() });
+        assert!(empirical.samples.iter().all(|(_, l)| observed.contains(l)));
+        assert_eq!((empirical.p50_ms, empirical.p99_ms), (20.0, 30.0));
+    }
+
+    #[test]
+    fn invalid_distributions_rejected() {
+        let mut cfg = SimConfig::default();
+        cfg.latency_distribution = LatencyDistribution::Pareto { alpha: 1.0 };
+        assert!(LatencyWave::new(&cfg).simulate().is_err());
+        cfg.latency_distribution = LatencyDistribution::Empirical { samples: vec![] };
+        assert!(LatencyWave::new(&cfg).simulate().is_err());
+    }
+
+    #[test]
+    fn percentile_uses_nearest_rank() {
+        let sorted: Vec<f64> = (1..=100).map(f64::from).collect();
+        assert_eq!(percentile(&sorted, 0.50), 50.0);
+        assert_eq!(percentile(&sorted, 0.99), 99.0);
+        assert_eq!(percentile(&[], 0.5), 0.0);
+    }
 }
@@ -6,6 +6,7 @@
 //! # Modules
 //!
 //! - `config`: simulation configuration loader  
+//! - `error`: the `SimError` type returned by simulators  
 //! - `types`: data types for simulation inputs and outputs  
 //! - `metrics`: internal metrics collector  
 //! - `tps_simulator`: TPS and throughput simulation  
@@ -14,12 +15,14 @@
 //! - `neuroflux_simulator`: NeuroFlux RL-driven optimization simulation  
 //! - `network_sim`: full network traffic and topology simulation  
 //! - `report_generator`: aggregation and export of simulation results  
+//! - `calibrator`: fitting simulator parameters to observed runtime metrics  
 //! - `prelude`: convenient re-exports  
 
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
 
 pub mod config;
+pub mod error;
 pub mod types;
 pub mod metrics;
 
@@ -29,10 +32,12 @@ pub mod latency_wave;
 pub mod neuroflux_simulator;
 pub mod network_sim;
 pub mod report_generator;
+pub mod calibrator;
 pub mod prelude;
 
 /// Re-export core configuration and metrics types.
-pub use config::SimConfig;
+pub use config::{LatencyDistribution, SimConfig};
+pub use error::SimError;
 pub use metrics::SimMetrics;
 
 /// Re-export all typed simulation results.
@@ -43,12 +48,14 @@ pub use types::{
     NeuroFluxResult,
     NetworkStats,
     ReportData,
+    ReportDiff,
 };
 
 /// Re-export main simulators and generators.
 pub use tps_simulator::TpsSimulator;
 pub use dimension_viewer::DimensionViewer;
 pub use latency_wave::LatencyWave;
-pub use neuroflux_simulator::NeuroFluxSimulator;
+pub use neuroflux_simulator::{NeuroFluxSimulator, NeuroFluxState, RewardFn, SampleReward};
 pub use network_sim::NetworkSimulator;
 pub use report_generator::ReportGenerator;
+pub use calibrator::{ObservedMetrics, SimCalibrator};
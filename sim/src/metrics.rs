@@ -2,12 +2,52 @@
 //!
 //! Collects and exports Prometheus‐style metrics for the simulation suite,
 //! covering TPS sampling, latency sampling, dimension views, NeuroFlux iterations,
-//! network events, and report generation.
+//! network events, and report generation. Besides counters and gauges it keeps
+//! histograms, exported as cumulative Prometheus `_bucket`/`_sum`/`_count`
+//! series.
 
 use std::collections::HashMap;
 use std::time::{Instant, SystemTime};
 use serde::Serialize;
 
+/// Default histogram bucket upper bounds, sized for latencies in milliseconds.
+pub const DEFAULT_HISTOGRAM_BUCKETS: [f64; 11] =
+    [1.0, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1_000.0, 2_500.0, 5_000.0];
+
+/// Observations bucketed by upper bound, plus an implicit `+Inf` bucket.
+#[derive(Clone, Debug, PartialEq)]
+struct Histogram {
+    /// Ascending bucket upper bounds (inclusive).
+    bounds: Vec<f64>,
+    /// Per-bucket (non-cumulative) counts; the last entry is `+Inf`.
+    counts: Vec<u64>,
+    /// Sum of all observed values.
+    sum: f64,
+}
+
+impl Histogram {
+    fn new(bounds: &[f64]) -> Self {
+        let mut bounds: Vec<f64> = bounds.iter().copied().filter(|b| !b.is_nan()).collect();
+        bounds.sort_by(f64::total_cmp);
+        bounds.dedup();
+        Histogram {
+            counts: vec![0; bounds.len() + 1],
+            bounds,
+            sum: 0.0,
+        }
+    }
+
+    fn observe(&mut self, value: f64) {
+        let bucket = self.bounds.partition_point(|&b| b < value);
+        self.counts[bucket] += 1;
+        self.sum += value;
+    }
+
+    fn count(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+}
+
 /// A snapshot of counters and gauges at a point in time.
 #[derive(Debug, Clone, Serialize)]
 struct MetricSnapshot {
@@ -25,6 +65,7 @@ pub struct SimMetrics {
     start: Instant,
     counters: HashMap<String, u64>,
     gauges: HashMap<String, f64>,
+    histograms: HashMap<String, Histogram>,
     snapshots: Vec<MetricSnapshot>,
 }
 
@@ -35,6 +76,7 @@ impl SimMetrics {
             start: Instant::now(),
             counters: HashMap::new(),
             gauges: HashMap::new(),
+            histograms: HashMap::new(),
             snapshots: Vec::new(),
         }
     }
@@ -49,6 +91,46 @@ impl SimMetrics {
         self.gauges.insert(name.to_string(), value);
     }
 
+    /// Set the bucket upper bounds of histogram `name`, discarding any
+    /// observations it already holds. Histograms observed without this use
+    /// [`DEFAULT_HISTOGRAM_BUCKETS`].
+    pub fn set_histogram_buckets(&mut self, name: &str, bounds: &[f64]) {
+        self.histograms.insert(name.to_string(), Histogram::new(bounds));
+    }
+
+    /// Add `value` to histogram `name`.
+    pub fn observe_histogram(&mut self, name: &str, value: f64) {
+        self.histograms
+            .entry(name.to_string())
+            .or_insert_with(|| Histogram::new(&DEFAULT_HISTOGRAM_BUCKETS))
+            .observe(value);
+    }
+
+    /// Fold `other` into this collector: counters are summed and gauges take
+    /// `other`'s values. Histograms with the same buckets are summed; otherwise
+    /// `other`'s replaces this one's. Snapshots are not merged.
+    pub fn merge(&mut self, other: &SimMetrics) {
+        for (name, value) in &other.counters {
+            self.inc_counter(name, *value);
+        }
+        for (name, value) in &other.gauges {
+            self.set_gauge(name, *value);
+        }
+        for (name, theirs) in &other.histograms {
+            match self.histograms.get_mut(name) {
+                Some(ours) if ours.bounds == theirs.bounds => {
+                    for (count, add) in ours.counts.iter_mut().zip(&theirs.counts) {
+                        *count += add;
+                    }
+                    ours.sum += theirs.sum;
+                }
+                _ => {
+                    self.histograms.insert(name.clone(), theirs.clone());
+                }
+            }
+        }
+    }
+
     /// Record a snapshot of current counters and gauges.
     pub fn record_snapshot(&mut self) {
         let ts = SystemTime::now()
@@ -68,7 +150,8 @@ impl SimMetrics {
             .map_err(|e| format!("Metrics JSON export failed: {}", e))
     }
 
-    /// Export current counters and gauges in Prometheus text format.
+    /// Export current counters, gauges and histograms in Prometheus text
+    /// format; histogram buckets are cumulative.
     pub fn export_prometheus(&self) -> String {
         let mut out = String::new();
         for (k, v) in &self.counters {
@@ -77,6 +160,16 @@ impl SimMetrics {
         for (k, v) in &self.gauges {
             out.push_str(&format!("sim_{} {}\n", k, v));
         }
+        for (k, h) in &self.histograms {
+            let mut cumulative = 0;
+            for (bound, count) in h.bounds.iter().zip(&h.counts) {
+                cumulative += count;
+                out.push_str(&format!("sim_{}_bucket{{le=\"{}\"}} {}\n", k, bound, cumulative));
+            }
+            out.push_str(&format!("sim_{}_bucket{{le=\"+Inf\"}} {}\n", k, h.count()));
+            out.push_str(&format!("sim_{}_sum {}\n", k, h.sum));
+            out.push_str(&format!("sim_{}_count {}\n", k, h.count()));
+        }
         let uptime = self.start.elapsed().as_secs_f64();
         out.push_str(&format!("sim_uptime_seconds {:.3}\n", uptime));
         out
@@ -155,4 +248,59 @@ mod tests {
         assert_eq!(m.counters["network_events"], 1);
         assert_eq!(m.counters["reports_generated"], 1);
     }
+
+    #[test]
+    fn histogram_exports_cumulative_buckets() {
+        let mut m = SimMetrics::new();
+        m.set_histogram_buckets("latency_ms", &[100.0, 10.0, 50.0]);
+        for v in [5.0, 10.0, 20.0, 60.0, 60.0, 200.0] {
+            m.observe_histogram("latency_ms", v);
+        }
+
+        let prom = m.export_prometheus();
+        let expected = "sim_latency_ms_bucket{le=\"10\"} 2\n\
+                        sim_latency_ms_bucket{le=\"50\"} 3\n\
+                        sim_latency_ms_bucket{le=\"100\"} 5\n\
+                        sim_latency_ms_bucket{le=\"+Inf\"} 6\n\
+                        sim_latency_ms_sum 355\n\
+                        sim_latency_ms_count 6\n";
+        assert!(prom.contains(expected), "got:\n{}", prom);
+
+        // unconfigured histograms fall back to the default buckets
+        m.observe_histogram("other", 3.0);
+        let prom = m.export_prometheus();
+        assert!(prom.contains("sim_other_bucket{le=\"1\"} 0\n"));
+        assert!(prom.contains("sim_other_bucket{le=\"5\"} 1\n"));
+        assert!(prom.contains("sim_other_bucket{le=\"5000\"} 1\n"));
+    }
+
+    #[test]
+    fn merge_sums_matching_histograms() {
+        let mut a = SimMetrics::new();
+        let mut b = SimMetrics::new();
+        a.observe_histogram("latency_ms", 3.0);
+        b.observe_histogram("latency_ms", 30.0);
+        b.observe_histogram("latency_ms", 300.0);
+
+        a.merge(&b);
+        let h = &a.histograms["latency_ms"];
+        assert_eq!(h.count(), 3);
+        assert_eq!(h.sum, 333.0);
+    }
+
+    #[test]
+    fn merge_sums_counters_and_takes_gauges() {
+        let mut a = SimMetrics::new();
+        a.inc_counter("tps_samples", 3);
+        a.set_gauge("load", 0.5);
+        let mut b = SimMetrics::new();
+        b.inc_counter("tps_samples", 2);
+        b.inc_counter("latency_samples", 1);
+        b.set_gauge("load", 0.75);
+
+        a.merge(&b);
+        assert_eq!(a.counters["tps_samples"], 5);
+        assert_eq!(a.counters["latency_samples"], 1);
+        assert_eq!(a.gauges["load"], 0.75);
+    }
 }
@@ -3,11 +3,19 @@
 //! Simulates end‐to‐end network behavior by combining TPS and latency models,
 //! aggregating over a configured number of nodes, and computing total messages,
 //! average latency, and packet drop rate.
+//!
+//! With a non-zero `churn_rate`, nodes leave and join every second, so the
+//! number online drifts around `network_size`. With a non-zero
+//! `partition_probability`, each second the whole network may
+//! split the node set in two at random; for `partition_duration_secs` every
+//! message between the halves is dropped, after which the network heals.
 
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
 
+use rand::distributions::Bernoulli;
 use rand::Rng;
+use rand_distr::{Binomial, Distribution};
 use crate::{
     config::SimConfig,
     error::SimError,
@@ -17,6 +25,9 @@ use crate::{
 use crate::tps_simulator::TpsSimulator;
 use crate::latency_wave::LatencyWave;
 
+/// Stream of `SimConfig::rng` reserved for the network simulator.
+const RNG_STREAM: u64 = 4;
+
 /// `NetworkSimulator` runs a combined network simulation.
 #[derive(Debug)]
 pub struct NetworkSimulator {
@@ -35,19 +46,24 @@ impl NetworkSimulator {
 
     /// Run the full network simulation.
     ///
-    /// - Uses `TpsSimulator` to simulate message rates.
     /// - Uses `LatencyWave` to simulate per‐second latencies.
+    /// - Uses `TpsSimulator` to simulate message rates, throttled by those
+    ///   latencies when backpressure is configured.
     /// - Aggregates total messages, computes average latency, and simulates
-    ///   a random packet drop rate between 0% and 5%.
+    ///   a random background packet drop rate between 0% and 5%.
+    /// - Churns nodes each second and tracks the final and peak node counts.
+    /// - While partitioned, additionally drops the share of each second's
+    ///   messages that crosses the split, assuming uniformly random
+    ///   sender/receiver pairs.
     pub fn simulate(&mut self) -> Result<NetworkStats, SimError> {
-        // Simulate transactions‐per‐second
-        let mut tps_sim = TpsSimulator::new(&self.config);
-        let tps_res = tps_sim.simulate()?;
-
         // Simulate latency profile
         let mut lat_sim = LatencyWave::new(&self.config);
         let latency_profile = lat_sim.simulate()?;
 
+        // Simulate transactions‐per‐second under latency feedback
+        let mut tps_sim = TpsSimulator::new(&self.config).with_latency_feedback(&latency_profile);
+        let tps_res = tps_sim.simulate()?;
+
         // Aggregate total messages routed (sum of per‐second TPS samples)
         let messages_routed: u64 = tps_res
             .samples
@@ -67,9 +83,64 @@ impl NetworkSimulator {
             sum / (latency_profile.samples.len() as f64)
         };
 
-        // Simulate a random drop rate in [0.0, 0.05)
-        let mut rng = rand::thread_rng();
-        let drop_rate = rng.gen_range(0.0..0.05);
+        let p = self.config.partition_probability;
+        let partition_chance = Bernoulli::new(p).map_err(|_| {
+            SimError::NetworkError(format!("partition_probability must be in [0, 1], got {}", p))
+        })?;
+
+        // Simulate a random background drop rate in [0.0, 0.05)
+        let mut rng = self.config.rng(RNG_STREAM);
+        let base_drop_rate = rng.gen_range(0.0..0.05);
+
+        // Churn nodes, then partition and heal per second, dropping
+        // cross-partition messages
+        let churn = self.config.churn_rate;
+        let mut online = self.config.network_size;
+        let mut peak_node_count = online;
+        let mut partition: Option<Partition> = None;
+        let mut partitions_observed = 0;
+        let mut max_partition_duration_secs = 0;
+        let mut dropped = 0.0;
+        for &(_, inst) in &tps_res.samples {
+            if churn > 0.0 {
+                let leaving = binomial(online, churn)?.sample(&mut rng) as usize;
+                let joining = binomial(self.config.network_size, churn)?.sample(&mut rng) as usize;
+                online = online - leaving + joining;
+                peak_node_count = peak_node_count.max(online);
+            }
+            if partition.is_none()
+                && online >= 2
+                && self.config.partition_duration_secs > 0
+                && partition_chance.sample(&mut rng)
+            {
+                let minority = rng.gen_range(1..=online / 2);
+                partition = Some(Partition {
+                    cross_fraction: cross_fraction(online, minority),
+                    elapsed_secs: 0,
+                });
+                partitions_observed += 1;
+            }
+            let cross = match &mut partition {
+                Some(p) => {
+                    p.elapsed_secs += 1;
+                    max_partition_duration_secs = max_partition_duration_secs.max(p.elapsed_secs);
+                    p.cross_fraction
+                }
+                None => 0.0,
+            };
+            dropped += inst * (base_drop_rate + (1.0 - base_drop_rate) * cross);
+            if partition
+                .as_ref()
+                .is_some_and(|p| p.elapsed_secs >= self.config.partition_duration_secs)
+            {
+                partition = None;
+            }
+        }
+        let drop_rate = if messages_routed == 0 {
+            base_drop_rate
+        } else {
+            (dropped / messages_routed as f64).min(1.0)
+        };
 
         // Record a network‐event metric per simulated second
         for _ in 0..self.config.duration_secs {
@@ -78,18 +149,51 @@ impl NetworkSimulator {
 
         Ok(NetworkStats {
             node_count: self.config.network_size,
+            final_node_count: online,
+            peak_node_count,
             messages_routed,
             average_latency_ms,
             drop_rate,
+            partitions_observed,
+            max_partition_duration_secs,
         })
     }
 
+    /// Metrics recorded by this simulator so far.
+    pub fn metrics(&self) -> &SimMetrics {
+        &self.metrics
+    }
+
     /// Export internal metrics (Prometheus text format).
     pub fn export_metrics(&self) -> String {
         self.metrics.export_prometheus()
     }
 }
 
+/// An ongoing split of the node set into two halves.
+struct Partition {
+    /// Share of sender/receiver pairs that straddle the split.
+    cross_fraction: f64,
+    /// Seconds since the split began.
+    elapsed_secs: u64,
+}
+
+/// Distribution of how many of `nodes` independent nodes churn in a second.
+///
+/// Fails if `rate` is not a probability.
+fn binomial(nodes: usize, rate: f64) -> Result<Binomial, SimError> {
+    Binomial::new(nodes as u64, rate).map_err(|_| {
+        SimError::NetworkError(format!("churn_rate must be in [0, 1], got {}", rate))
+    })
+}
+
+/// Fraction of ordered node pairs with one end among `minority` of `nodes`
+/// and the other end outside it.
+fn cross_fraction(nodes: usize, minority: usize) -> f64 {
+    let (n, k) = (nodes as f64, minority as f64);
+    2.0 * k * (n - k) / (n * (n - 1.0))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -109,6 +213,8 @@ mod tests {
 
         // Basic field checks
         assert_eq!(stats.node_count, 5);
+        assert_eq!(stats.final_node_count, 5);
+        assert_eq!(stats.peak_node_count, 5);
         assert!(stats.messages_routed > 0);
         assert!(stats.average_latency_ms >= 0.0);
         assert!(stats.drop_rate >= 0.0 && stats.drop_rate < 1.0);
@@ -118,6 +224,48 @@ mod tests {
         assert!(prom.contains(&format!("sim_network_events {}", cfg.duration_secs)));
     }
 
+    #[test]
+    fn partitions_inflate_drop_rate() {
+        let mut cfg = SimConfig::default();
+        cfg.duration_secs = 60;
+        cfg.network_size = 20;
+        cfg.target_tps = 1_000;
+        cfg.partition_duration_secs = 5;
+        cfg.seed = Some(3);
+
+        let healthy = NetworkSimulator::new(&cfg).simulate().unwrap();
+        assert_eq!(healthy.partitions_observed, 0);
+        assert_eq!(healthy.max_partition_duration_secs, 0);
+
+        cfg.partition_probability = 0.9;
+        let split = NetworkSimulator::new(&cfg).simulate().unwrap();
+        assert!(split.partitions_observed > 1);
+        assert_eq!(split.max_partition_duration_secs, 5);
+        // at least one in ten pairs straddles any split of 20 nodes
+        assert!(
+            split.drop_rate > healthy.drop_rate + 0.05,
+            "partitioned {} vs healthy {}",
+            split.drop_rate,
+            healthy.drop_rate
+        );
+    }
+
+    #[test]
+    fn churn_varies_node_count() {
+        let mut cfg = SimConfig::default();
+        cfg.duration_secs = 30;
+        cfg.network_size = 100;
+        cfg.target_tps = 100;
+        cfg.churn_rate = 0.5;
+        cfg.seed = Some(9);
+
+        let stats = NetworkSimulator::new(&cfg).simulate().unwrap();
+        assert_eq!(stats.node_count, 100);
+        assert_ne!(stats.final_node_count, 100);
+        assert!(stats.peak_node_count > 100);
+        assert!(stats.peak_node_count >= stats.final_node_count);
+    }
+
     #[test]
     fn zero_duration_yields_zero_messages_and_latency() {
         let mut cfg = SimConfig::default();
@@ -133,4 +281,23 @@ mod tests {
         let prom = sim.export_metrics();
         assert!(!prom.contains("sim_network_events"));
     }
+    #[test]
+    fn invalid_churn_rate_is_an_error() {
+        let mut cfg = SimConfig::default();
+        cfg.duration_secs = 3;
+        cfg.network_size = 10;
+        cfg.churn_rate = 2.0;
+        let err = NetworkSimulator::new(&cfg).simulate().unwrap_err();
+        assert!(matches!(err, SimError::NetworkError(_)), "unexpected error {}", err);
+    }
+
+    #[test]
+    fn invalid_partition_probability_is_an_error() {
+        let mut cfg = SimConfig::default();
+        cfg.duration_secs = 3;
+        cfg.network_size = 10;
+        cfg.partition_probability = 1.5;
+        let err = NetworkSimulator::new(&cfg).simulate().unwrap_err();
+        assert!(matches!(err, SimError::NetworkError(_)), "unexpected error {}", err);
+    }
 }
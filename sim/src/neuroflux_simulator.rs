@@ -2,19 +2,64 @@
 //!
 //! Runs a reinforcement‐learning inspired, quantum‐enhanced optimization simulation
 //! over a configured number of iterations (`neuroflux_iterations`).  
-//! Records per‐iteration performance metrics and tracks the best metric found.
+//! Records per‐iteration performance metrics and tracks the best metric found,
+//! stopping early once it stops improving (`convergence_patience`).
 
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
 
 use crate::{config::SimConfig, error::SimError, metrics::SimMetrics, types::NeuroFluxResult};
 use rand::Rng;
+use std::fmt;
+
+/// Candidate evaluated by a NeuroFlux iteration.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NeuroFluxState {
+    /// Zero-based iteration index.
+    pub iteration: usize,
+    /// Random-search draw in [0.0, 1.0).
+    pub sample: f64,
+}
+
+/// Objective scored for each iteration; `best_metric` is its maximum.
+pub trait RewardFn {
+    /// Score `state`; higher is better.
+    fn reward(&self, state: &NeuroFluxState) -> f64;
+}
+
+impl<F: Fn(&NeuroFluxState) -> f64> RewardFn for F {
+    fn reward(&self, state: &NeuroFluxState) -> f64 {
+        self(state)
+    }
+}
+
+/// Default objective: the iteration's random draw itself.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SampleReward;
+
+impl RewardFn for SampleReward {
+    fn reward(&self, state: &NeuroFluxState) -> f64 {
+        state.sample
+    }
+}
+
+/// Stream of `SimConfig::rng` reserved for the NeuroFlux simulator.
+const RNG_STREAM: u64 = 3;
 
 /// `NeuroFluxSimulator` executes NeuroFlux optimization loops.
-#[derive(Debug)]
 pub struct NeuroFluxSimulator {
     config: SimConfig,
     metrics: SimMetrics,
+    reward: Box<dyn RewardFn>,
+}
+
+impl fmt::Debug for NeuroFluxSimulator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NeuroFluxSimulator")
+            .field("config", &self.config)
+            .field("metrics", &self.metrics)
+            .finish_non_exhaustive()
+    }
 }
 
 impl NeuroFluxSimulator {
@@ -23,42 +68,74 @@ impl NeuroFluxSimulator {
         NeuroFluxSimulator {
             config: config.clone(),
             metrics: SimMetrics::new(),
+            reward: Box::new(SampleReward),
         }
     }
 
+    /// Replace the default [`SampleReward`] objective with `reward`.
+    pub fn with_reward(mut self, reward: Box<dyn RewardFn>) -> Self {
+        self.reward = reward;
+        self
+    }
+
     /// Run the NeuroFlux simulation.
     ///
     /// If `config.neuroflux_enabled` is false, returns an error.
-    /// Otherwise performs `neuroflux_iterations` random‐search iterations,
-    /// recording a random performance metric in [0.0, 1.0) each time,
-    /// tracking the best value, and returning a `NeuroFluxResult`.
+    /// Otherwise performs up to `neuroflux_iterations` random‐search
+    /// iterations, scoring each random draw in [0.0, 1.0) with the reward
+    /// function (by default the draw itself), tracking the best value, and
+    /// returning a `NeuroFluxResult` with the iterations actually run.
+    ///
+    /// With a non-zero `convergence_patience`, stops once that many
+    /// consecutive iterations have each raised `best_metric` by less than
+    /// `convergence_epsilon`.
     pub fn simulate(&mut self) -> Result<NeuroFluxResult, SimError> {
         if !self.config.neuroflux_enabled {
             return Err(SimError::NeuroFluxError(
                 "NeuroFlux simulation disabled".into(),
             ));
         }
-        let iterations = self.config.neuroflux_iterations;
-        let mut rng = rand::thread_rng();
-        let mut progress = Vec::with_capacity(iterations);
+        let max_iterations = self.config.neuroflux_iterations;
+        let patience = self.config.convergence_patience;
+        let epsilon = self.config.convergence_epsilon;
+        let mut rng = self.config.rng(RNG_STREAM);
+        let mut progress = Vec::with_capacity(max_iterations);
         let mut best_metric = f64::NEG_INFINITY;
+        let mut stale = 0;
 
-        for i in 0..iterations {
-            let metric: f64 = rng.gen_range(0.0..1.0);
+        for i in 0..max_iterations {
+            let state = NeuroFluxState {
+                iteration: i,
+                sample: rng.gen_range(0.0..1.0),
+            };
+            let metric = self.reward.reward(&state);
+            if metric > best_metric + epsilon {
+                stale = 0;
+            } else {
+                stale += 1;
+            }
             if metric > best_metric {
                 best_metric = metric;
             }
             progress.push((i, metric));
             self.metrics.record_neuroflux_iteration();
+            if patience > 0 && stale >= patience {
+                break;
+            }
         }
 
         Ok(NeuroFluxResult {
-            iterations,
+            iterations: progress.len(),
             best_metric: if best_metric.is_finite() { best_metric } else { 0.0 },
             progress,
         })
     }
 
+    /// Metrics recorded by this simulator so far.
+    pub fn metrics(&self) -> &SimMetrics {
+        &self.metrics
+    }
+
     /// Export internal metrics in Prometheus text format.
     pub fn export_metrics(&self) -> String {
         self.metrics.export_prometheus()
@@ -111,4 +188,43 @@ mod tests {
         let prom = sim.export_metrics();
         assert!(prom.contains("sim_neuroflux_iterations 7"));
     }
+
+    #[test]
+    fn stops_early_once_converged() {
+        let mut cfg = SimConfig::default();
+        cfg.neuroflux_enabled = true;
+        cfg.neuroflux_iterations = 10_000;
+        cfg.convergence_patience = 5;
+        let constant = |_: &NeuroFluxState| 0.5;
+
+        let mut sim = NeuroFluxSimulator::new(&cfg).with_reward(Box::new(constant));
+        let res = sim.simulate().unwrap();
+        // one improving iteration, then five without progress
+        assert_eq!(res.iterations, 6);
+        assert_eq!(res.progress.len(), 6);
+        assert_eq!(res.best_metric, 0.5);
+        assert!(sim.export_metrics().contains("sim_neuroflux_iterations 6"));
+
+        cfg.convergence_patience = 0;
+        let mut sim = NeuroFluxSimulator::new(&cfg).with_reward(Box::new(constant));
+        assert_eq!(sim.simulate().unwrap().iterations, 10_000);
+    }
+
+    #[test]
+    fn custom_reward_overrides_default() {
+        struct Constant;
+        impl RewardFn for Constant {
+            fn reward(&self, _state: &NeuroFluxState) -> f64 {
+                1.0
+            }
+        }
+
+        let mut cfg = SimConfig::default();
+        cfg.neuroflux_enabled = true;
+        cfg.neuroflux_iterations = 20;
+        let mut sim = NeuroFluxSimulator::new(&cfg).with_reward(Box::new(Constant));
+        let res = sim.simulate().unwrap();
+        assert_eq!(res.best_metric, 1.0);
+        assert!(res.progress.iter().all(|&(_, m)| m == 1.0));
+    }
 }
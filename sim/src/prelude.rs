@@ -4,7 +4,7 @@
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
 
-pub use crate::config::SimConfig;
+pub use crate::config::{LatencyDistribution, SimConfig};
 pub use crate::metrics::SimMetrics;
 pub use crate::types::{
     TpsResult,
@@ -13,14 +13,16 @@ pub use crate::types::{
     NeuroFluxResult,
     NetworkStats,
     ReportData,
+    ReportDiff,
 };
 
 pub use crate::tps_simulator::TpsSimulator;
 pub use crate::dimension_viewer::DimensionViewer;
 pub use crate::latency_wave::LatencyWave;
-pub use crate::neuroflux_simulator::NeuroFluxSimulator;
+pub use crate::neuroflux_simulator::{NeuroFluxSimulator, NeuroFluxState, RewardFn, SampleReward};
 pub use crate::network_sim::NetworkSimulator;
 pub use crate::report_generator::ReportGenerator;
+pub use crate::calibrator::{ObservedMetrics, SimCalibrator};
 
 #[cfg(test)]
 mod tests {
@@ -41,19 +43,26 @@ mod tests {
         let tps = TpsResult {
             target_tps: cfg.target_tps,
             average_tps: 0.0,
+            min_tps: 0.0,
+            max_tps: 0.0,
+            p95_tps: 0.0,
+            jitter: 0.0,
             samples: vec![],
         };
         assert_eq!(tps.target_tps, cfg.target_tps);
 
         let dim_view = DimensionView {
             dimensions: cfg.dimensions,
-            utilization: std::collections::HashMap::new(),
+            utilization: std::collections::BTreeMap::new(),
         };
         assert_eq!(dim_view.dimensions, cfg.dimensions);
 
         let latency = LatencyProfile {
             mean_ms: cfg.latency_mean_ms,
             stddev_ms: cfg.latency_stddev_ms,
+            p50_ms: 0.0,
+            p95_ms: 0.0,
+            p99_ms: 0.0,
             samples: vec![],
         };
         assert!((latency.mean_ms - cfg.latency_mean_ms).abs() < 1e-12);
@@ -67,9 +76,13 @@ mod tests {
 
         let net_stats = NetworkStats {
             node_count: cfg.network_size,
+            final_node_count: cfg.network_size,
+            peak_node_count: cfg.network_size,
             messages_routed: 0,
             average_latency_ms: 0.0,
             drop_rate: 0.0,
+            partitions_observed: 0,
+            max_partition_duration_secs: 0,
         };
         assert_eq!(net_stats.node_count, cfg.network_size);
 
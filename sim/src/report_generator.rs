@@ -1,19 +1,23 @@
 //! Report Generator for Qublis‐sim — Qublis v2.0
 //!
 //! Aggregates results from all simulators into a final `ReportData`,
-//! then serializes to JSON or CSV according to configuration.  
-//! Records a metric for report generation.
+//! then serializes to JSON, CSV, Markdown or HTML according to configuration.  
+//! The simulators are independent and run on their own threads; their metrics
+//! are merged into the generator's collector, which also records a metric for
+//! report generation.
 
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
 
+use std::collections::HashMap;
 use std::fmt::Write as FmtWrite;
+use std::thread::{self, ScopedJoinHandle};
 
 use crate::{
-    config::SimConfig,
+    config::{SimConfig, REPORT_FORMATS},
     error::SimError,
     metrics::SimMetrics,
-    types::{ReportData, TpsResult, DimensionView, LatencyProfile, NeuroFluxResult, NetworkStats},
+    types::{ReportData, ReportDiff, TpsResult, DimensionView, LatencyProfile, NeuroFluxResult, NetworkStats},
 };
 use crate::tps_simulator::TpsSimulator;
 use crate::dimension_viewer::DimensionViewer;
@@ -37,44 +41,110 @@ impl ReportGenerator {
         }
     }
 
-    /// Run all simulations and collect the `ReportData`.
+    /// Run all simulations concurrently, one thread each, and collect the
+    /// `ReportData`.
+    ///
+    /// With `config.seed` set the result is identical to
+    /// [`generate_sequential`](Self::generate_sequential).
     pub fn generate(&mut self) -> Result<ReportData, SimError> {
-        // TPS
-        let mut tps_sim = TpsSimulator::new(&self.config);
-        let tps = tps_sim.simulate()?;
-        // Dimension view
-        let mut dim_sim = DimensionViewer::new(&self.config);
-        let dimensions = dim_sim.view();
-        // Latency
-        let mut lat_sim = LatencyWave::new(&self.config);
-        let latency = lat_sim.simulate()?;
-        // NeuroFlux (optional)
-        let neuroflux = if self.config.neuroflux_enabled {
-            let mut nf_sim = NeuroFluxSimulator::new(&self.config);
-            Some(nf_sim.simulate()?)
-        } else {
-            None
-        };
-        // Network
-        let mut net_sim = NetworkSimulator::new(&self.config);
-        let network = net_sim.simulate()?;
+        let config = &self.config;
+        let (tps, dimensions, latency, neuroflux, network) = thread::scope(|s| {
+            let tps = s.spawn(|| run_tps(config));
+            let dimensions = s.spawn(|| run_dimensions(config));
+            let latency = s.spawn(|| run_latency(config));
+            let neuroflux = s.spawn(|| run_neuroflux(config));
+            let network = s.spawn(|| run_network(config));
+            (join(tps), join(dimensions), join(latency), join(neuroflux), join(network))
+        });
+        Ok(self.assemble(tps?, dimensions, latency?, neuroflux?, network?))
+    }
+
+    /// Run all simulations one after another on the calling thread and
+    /// collect the `ReportData`.
+    pub fn generate_sequential(&mut self) -> Result<ReportData, SimError> {
+        let tps = run_tps(&self.config)?;
+        let dimensions = run_dimensions(&self.config);
+        let latency = run_latency(&self.config)?;
+        let neuroflux = run_neuroflux(&self.config)?;
+        let network = run_network(&self.config)?;
+        Ok(self.assemble(tps, dimensions, latency, neuroflux, network))
+    }
 
+    /// Merge each simulator's metrics, record the report, and build it.
+    fn assemble(
+        &mut self,
+        (tps, tps_metrics): (TpsResult, SimMetrics),
+        (dimensions, dim_metrics): (DimensionView, SimMetrics),
+        (latency, lat_metrics): (LatencyProfile, SimMetrics),
+        (neuroflux, nf_metrics): (Option<NeuroFluxResult>, SimMetrics),
+        (network, net_metrics): (NetworkStats, SimMetrics),
+    ) -> ReportData {
+        for metrics in [&tps_metrics, &dim_metrics, &lat_metrics, &nf_metrics, &net_metrics] {
+            self.metrics.merge(metrics);
+        }
         // Record report generation metric
         self.metrics.record_reports_generated();
 
-        Ok(ReportData {
+        ReportData {
             tps,
             dimensions,
             latency,
             neuroflux,
             network,
-        })
+        }
     }
 
-    /// Serialize `ReportData` to JSON or CSV according to `config.report_format`.
+    /// Per-metric deltas from run `a` to run `b` (each `b - a`), for
+    /// comparing configurations.
+    pub fn diff(a: &ReportData, b: &ReportData) -> ReportDiff {
+        let signed = |x: u64, y: u64| y as i64 - x as i64;
+        ReportDiff {
+            target_tps: signed(a.tps.target_tps, b.tps.target_tps),
+            average_tps: b.tps.average_tps - a.tps.average_tps,
+            latency_mean_ms: b.latency.mean_ms - a.latency.mean_ms,
+            latency_stddev_ms: b.latency.stddev_ms - a.latency.stddev_ms,
+            latency_p50_ms: b.latency.p50_ms - a.latency.p50_ms,
+            latency_p95_ms: b.latency.p95_ms - a.latency.p95_ms,
+            latency_p99_ms: b.latency.p99_ms - a.latency.p99_ms,
+            neuroflux_best_metric: a
+                .neuroflux
+                .as_ref()
+                .zip(b.neuroflux.as_ref())
+                .map(|(x, y)| y.best_metric - x.best_metric),
+            messages_routed: signed(a.network.messages_routed, b.network.messages_routed),
+            network_latency_ms: b.network.average_latency_ms - a.network.average_latency_ms,
+            drop_rate: b.network.drop_rate - a.network.drop_rate,
+            partitions_observed: signed(
+                a.network.partitions_observed as u64,
+                b.network.partitions_observed as u64,
+            ),
+            final_node_count: signed(
+                a.network.final_node_count as u64,
+                b.network.final_node_count as u64,
+            ),
+        }
+    }
+
+    /// Serialize `ReportData` according to `config.report_format`: `"csv"`,
+    /// `"markdown"`, `"html"`, or JSON for anything else.
     pub fn export(&self, report: &ReportData) -> Result<String, SimError> {
-        match self.config.report_format.as_str() {
+        self.export_as(report, &self.config.report_format)
+    }
+
+    /// Serialize `ReportData` in every supported format, keyed by file
+    /// extension (`"json"`, `"csv"`, `"md"`, `"html"`).
+    pub fn export_all(&self, report: &ReportData) -> Result<HashMap<String, String>, SimError> {
+        REPORT_FORMATS
+            .iter()
+            .map(|format| Ok((extension(format).to_string(), self.export_as(report, format)?)))
+            .collect()
+    }
+
+    fn export_as(&self, report: &ReportData, format: &str) -> Result<String, SimError> {
+        match format {
             "csv" => self.export_csv(report).map_err(|e| SimError::ReportError(e)),
+            "markdown" => Ok(self.export_markdown(report)),
+            "html" => Ok(self.export_html(report)),
             _ => {
                 serde_json::to_string_pretty(report)
                     .map_err(SimError::Serialization)
@@ -88,14 +158,20 @@ impl ReportGenerator {
         writeln!(&mut w, "section,field,value").unwrap();
         writeln!(&mut w, "tps,target_tps,{}", report.tps.target_tps).unwrap();
         writeln!(&mut w, "tps,average_tps,{}", report.tps.average_tps).unwrap();
+        writeln!(&mut w, "tps,min_tps,{}", report.tps.min_tps).unwrap();
+        writeln!(&mut w, "tps,max_tps,{}", report.tps.max_tps).unwrap();
+        writeln!(&mut w, "tps,p95_tps,{}", report.tps.p95_tps).unwrap();
+        writeln!(&mut w, "tps,jitter,{}", report.tps.jitter).unwrap();
         // Dimension utilizations
-        for (dim, util) in &report.dimensions.utilization {
-            writeln!(&mut w, "dimension,utilization_{}", dim,).unwrap();
-            writeln!(&mut w, "dimension_{}_utilization,{}", dim, util).unwrap();
+        for (dim, util) in sorted_utilization(&report.dimensions) {
+            writeln!(&mut w, "dimension,utilization_{},{}", dim, util).unwrap();
         }
         // Latency
         writeln!(&mut w, "latency,mean_ms,{}", report.latency.mean_ms).unwrap();
         writeln!(&mut w, "latency,stddev_ms,{}", report.latency.stddev_ms).unwrap();
+        writeln!(&mut w, "latency,p50_ms,{}", report.latency.p50_ms).unwrap();
+        writeln!(&mut w, "latency,p95_ms,{}", report.latency.p95_ms).unwrap();
+        writeln!(&mut w, "latency,p99_ms,{}", report.latency.p99_ms).unwrap();
         // NeuroFlux
         if let Some(nf) = &report.neuroflux {
             writeln!(&mut w, "neuroflux,iterations,{}", nf.iterations).unwrap();
@@ -103,19 +179,186 @@ impl ReportGenerator {
         }
         // Network
         writeln!(&mut w, "network,node_count,{}", report.network.node_count).unwrap();
+        writeln!(&mut w, "network,final_node_count,{}", report.network.final_node_count).unwrap();
+        writeln!(&mut w, "network,peak_node_count,{}", report.network.peak_node_count).unwrap();
         writeln!(&mut w, "network,messages_routed,{}", report.network.messages_routed).unwrap();
         writeln!(&mut w, "network,average_latency_ms,{}", report.network.average_latency_ms).unwrap();
         writeln!(&mut w, "network,drop_rate,{}", report.network.drop_rate).unwrap();
+        writeln!(&mut w, "network,partitions_observed,{}", report.network.partitions_observed).unwrap();
+        writeln!(
+            &mut w,
+            "network,max_partition_duration_secs,{}",
+            report.network.max_partition_duration_secs
+        )
+        .unwrap();
 
         Ok(w)
     }
 
+    /// Render the report as Markdown, one `## Section` with a field table each.
+    pub fn export_markdown(&self, report: &ReportData) -> String {
+        let mut w = String::new();
+        writeln!(&mut w, "# {}", REPORT_TITLE).unwrap();
+        for (title, rows) in report_sections(report) {
+            writeln!(&mut w, "\n## {}\n", title).unwrap();
+            writeln!(&mut w, "| Field | Value |").unwrap();
+            writeln!(&mut w, "|---|---|").unwrap();
+            for (field, value) in rows {
+                writeln!(&mut w, "| {} | {} |", field, value).unwrap();
+            }
+        }
+        w
+    }
+
+    /// Render the report as a standalone HTML page with a styled table per section.
+    pub fn export_html(&self, report: &ReportData) -> String {
+        let mut w = String::new();
+        writeln!(&mut w, "<!DOCTYPE html>").unwrap();
+        writeln!(&mut w, "<html>\n<head>\n<meta charset=\"utf-8\">").unwrap();
+        writeln!(&mut w, "<title>{}</title>", REPORT_TITLE).unwrap();
+        writeln!(&mut w, "<style>{}</style>", HTML_STYLE).unwrap();
+        writeln!(&mut w, "</head>\n<body>\n<h1>{}</h1>", REPORT_TITLE).unwrap();
+        for (title, rows) in report_sections(report) {
+            writeln!(&mut w, "<h2>{}</h2>\n<table>", title).unwrap();
+            writeln!(&mut w, "<tr><th>Field</th><th>Value</th></tr>").unwrap();
+            for (field, value) in rows {
+                writeln!(&mut w, "<tr><td>{}</td><td>{}</td></tr>", field, value).unwrap();
+            }
+            writeln!(&mut w, "</table>").unwrap();
+        }
+        writeln!(&mut w, "</body>\n</html>").unwrap();
+        w
+    }
+
     /// Export internal metrics in Prometheus text format.
     pub fn export_metrics(&self) -> String {
         self.metrics.export_prometheus()
     }
 }
 
+/// Wait for a simulator thread, re-raising its panic if it had one.
+fn join<T>(handle: ScopedJoinHandle<'_, T>) -> T {
+    handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+}
+
+/// With backpressure configured, TPS is throttled by its own latency run.
+fn run_tps(config: &SimConfig) -> Result<(TpsResult, SimMetrics), SimError> {
+    let mut sim = TpsSimulator::new(config);
+    if config.backpressure_threshold_ms.is_some() {
+        sim = sim.with_latency_feedback(&LatencyWave::new(config).simulate()?);
+    }
+    let tps = sim.simulate()?;
+    Ok((tps, sim.metrics().clone()))
+}
+
+fn run_dimensions(config: &SimConfig) -> (DimensionView, SimMetrics) {
+    let mut viewer = DimensionViewer::new(config);
+    let view = viewer.view();
+    (view, viewer.metrics().clone())
+}
+
+fn run_latency(config: &SimConfig) -> Result<(LatencyProfile, SimMetrics), SimError> {
+    let mut sim = LatencyWave::new(config);
+    let latency = sim.simulate()?;
+    Ok((latency, sim.metrics().clone()))
+}
+
+/// NeuroFlux is optional: disabled configs yield `None` and no metrics.
+fn run_neuroflux(config: &SimConfig) -> Result<(Option<NeuroFluxResult>, SimMetrics), SimError> {
+    if !config.neuroflux_enabled {
+        return Ok((None, SimMetrics::new()));
+    }
+    let mut sim = NeuroFluxSimulator::new(config);
+    let result = sim.simulate()?;
+    Ok((Some(result), sim.metrics().clone()))
+}
+
+fn run_network(config: &SimConfig) -> Result<(NetworkStats, SimMetrics), SimError> {
+    let mut sim = NetworkSimulator::new(config);
+    let network = sim.simulate()?;
+    Ok((network, sim.metrics().clone()))
+}
+
+/// Heading used by the Markdown and HTML reports.
+const REPORT_TITLE: &str = "Qublis Simulation Report";
+
+/// Inline stylesheet for the HTML report.
+const HTML_STYLE: &str = "body{font-family:sans-serif;margin:2em;color:#222}\
+table{border-collapse:collapse;margin-bottom:1.5em}\
+th,td{border:1px solid #ccc;padding:4px 12px;text-align:left}\
+th{background:#f0f0f0}";
+
+/// File extension for a `report_format` value.
+fn extension(format: &str) -> &str {
+    match format {
+        "markdown" => "md",
+        other => other,
+    }
+}
+
+/// Dimension utilizations ordered by dimension index.
+fn sorted_utilization(view: &DimensionView) -> Vec<(usize, f64)> {
+    view.utilization.iter().map(|(&d, &u)| (d, u)).collect()
+}
+
+/// The report as titled sections of `(field, value)` rows, in export order.
+fn report_sections(report: &ReportData) -> Vec<(&'static str, Vec<(String, String)>)> {
+    let row = |field: &str, value: &dyn std::fmt::Display| (field.to_string(), value.to_string());
+    let mut sections = vec![
+        (
+            "TPS",
+            vec![
+                row("target_tps", &report.tps.target_tps),
+                row("average_tps", &report.tps.average_tps),
+                row("min_tps", &report.tps.min_tps),
+                row("max_tps", &report.tps.max_tps),
+                row("p95_tps", &report.tps.p95_tps),
+                row("jitter", &report.tps.jitter),
+            ],
+        ),
+        (
+            "Dimensions",
+            sorted_utilization(&report.dimensions)
+                .into_iter()
+                .map(|(dim, util)| row(&format!("utilization_{}", dim), &util))
+                .collect(),
+        ),
+        (
+            "Latency",
+            vec![
+                row("mean_ms", &report.latency.mean_ms),
+                row("stddev_ms", &report.latency.stddev_ms),
+                row("p50_ms", &report.latency.p50_ms),
+                row("p95_ms", &report.latency.p95_ms),
+                row("p99_ms", &report.latency.p99_ms),
+            ],
+        ),
+    ];
+    if let Some(nf) = &report.neuroflux {
+        sections.push((
+            "NeuroFlux",
+            vec![
+                row("iterations", &nf.iterations),
+                row("best_metric", &nf.best_metric),
+            ],
+        ));
+    }
+    sections.push((
+        "Network",
+        vec![
+            row("node_count", &report.network.node_count),
+            row("final_node_count", &report.network.final_node_count),
+            row("peak_node_count", &report.network.peak_node_count),
+            row("messages_routed", &report.network.messages_routed),
+            row("average_latency_ms", &report.network.average_latency_ms),
+            row("drop_rate", &report.network.drop_rate),
+            row("partitions_observed", &report.network.partitions_observed),
+            row("max_partition_duration_secs", &report.network.max_partition_duration_secs),
+        ],
+    ));
+    sections
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -155,6 +398,135 @@ mod tests {
         assert!(csv.contains("tps,target_tps,50"));
     }
 
+    #[test]
+    fn export_all_covers_every_format() {
+        let mut cfg = SimConfig::default();
+        cfg.duration_secs = 1;
+        cfg.target_tps = 50;
+
+        let mut rg = ReportGenerator::new(&cfg);
+        let report = rg.generate().unwrap();
+        let exports = rg.export_all(&report).unwrap();
+        let mut keys: Vec<_> = exports.keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        assert_eq!(keys, ["csv", "html", "json", "md"]);
+
+        let de: ReportData = serde_json::from_str(&exports["json"]).unwrap();
+        assert_eq!(de.tps.target_tps, 50);
+        assert!(exports["csv"].starts_with("section,field,value"));
+        assert!(exports["csv"].contains("tps,target_tps,50"));
+    }
+
+    #[test]
+    fn diff_reports_tps_delta() {
+        let mut cfg = SimConfig::default();
+        cfg.duration_secs = 5;
+        cfg.target_tps = 1_000;
+        cfg.seed = Some(4);
+        let slow = ReportGenerator::new(&cfg).generate().unwrap();
+        cfg.target_tps = 5_000;
+        let fast = ReportGenerator::new(&cfg).generate().unwrap();
+
+        let diff = ReportGenerator::diff(&slow, &fast);
+        assert_eq!(diff.target_tps, 4_000);
+        assert!(diff.average_tps > 0.0);
+        assert!(diff.messages_routed > 0);
+        assert!(ReportGenerator::diff(&fast, &slow).average_tps < 0.0);
+        assert_eq!(diff.neuroflux_best_metric, None);
+
+        let same = ReportGenerator::diff(&fast, &fast);
+        assert_eq!(same.average_tps, 0.0);
+        assert_eq!(same.drop_rate, 0.0);
+        let json = serde_json::to_string(&diff).unwrap();
+        let de: ReportDiff = serde_json::from_str(&json).unwrap();
+        assert_eq!(de.target_tps, 4_000);
+        assert!((de.average_tps - diff.average_tps).abs() < 1e-9);
+    }
+
+    #[test]
+    fn csv_dimension_rows_are_well_formed() {
+        let mut cfg = SimConfig::default();
+        cfg.duration_secs = 1;
+        cfg.report_format = "csv".into();
+
+        let mut rg = ReportGenerator::new(&cfg);
+        let report = rg.generate().unwrap();
+        let csv = rg.export(&report).unwrap();
+        for line in csv.lines() {
+            assert_eq!(line.split(',').count(), 3, "malformed row {:?}", line);
+        }
+    }
+
+    #[test]
+    fn generate_and_export_markdown() {
+        let mut cfg = SimConfig::default();
+        cfg.duration_secs = 1;
+        cfg.neuroflux_enabled = false;
+        cfg.report_format = "markdown".into();
+
+        let mut rg = ReportGenerator::new(&cfg);
+        let report = rg.generate().unwrap();
+        let md = rg.export(&report).unwrap();
+        assert!(md.contains("## TPS"));
+        assert!(md.contains("## Network"));
+        assert!(md.contains(&format!("| node_count | {} |", cfg.network_size)));
+        assert!(!md.contains("## NeuroFlux"));
+    }
+
+    #[test]
+    fn generate_and_export_html() {
+        let mut cfg = SimConfig::default();
+        cfg.duration_secs = 1;
+        cfg.report_format = "html".into();
+
+        let mut rg = ReportGenerator::new(&cfg);
+        let report = rg.generate().unwrap();
+        let html = rg.export(&report).unwrap();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<h2>TPS</h2>"));
+        assert!(html.contains(&format!("<td>node_count</td><td>{}</td>", cfg.network_size)));
+        assert!(html.trim_end().ends_with("</html>"));
+    }
+
+    #[test]
+    fn parallel_matches_sequential_for_fixed_seed() {
+        let mut cfg = SimConfig::default();
+        cfg.duration_secs = 5;
+        cfg.neuroflux_enabled = true;
+        cfg.neuroflux_iterations = 100;
+        cfg.seed = Some(2024);
+
+        let mut parallel = ReportGenerator::new(&cfg);
+        let mut sequential = ReportGenerator::new(&cfg);
+        let report = parallel.generate().unwrap();
+        assert_eq!(report, sequential.generate_sequential().unwrap());
+
+        let prom = parallel.export_metrics();
+        assert!(prom.contains("sim_reports_generated 1\n"));
+        assert!(prom.contains("sim_neuroflux_iterations 100\n"));
+        assert!(prom.contains("sim_dimension_views 1\n"));
+    }
+
+    #[test]
+    fn fixed_seed_reproduces_report_json() {
+        let mut cfg = SimConfig::default();
+        cfg.duration_secs = 5;
+        cfg.neuroflux_enabled = true;
+        cfg.neuroflux_iterations = 50;
+        cfg.seed = Some(77);
+
+        let run = |cfg: &SimConfig| {
+            let mut rg = ReportGenerator::new(cfg);
+            let report = rg.generate().unwrap();
+            serde_json::to_string(&report).unwrap()
+        };
+        let first = run(&cfg);
+        assert_eq!(first, run(&cfg));
+
+        cfg.seed = Some(78);
+        assert_ne!(first, run(&cfg));
+    }
+
     #[test]
     fn metrics_recorded() {
         let cfg = SimConfig::default();
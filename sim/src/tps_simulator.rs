@@ -2,6 +2,12 @@
 //!
 //! Simulates transactions‐per‐second over a configured duration,
 //! sampling once per second with random jitter around the target TPS.
+//! With `backpressure_threshold_ms` set and a latency profile supplied
+//! (`with_latency_feedback`), each second over the threshold throttles the
+//! next second's offered load, modeling congestion control.
+//! Samples can be streamed as they are drawn (`iter_samples`) or
+//! aggregated into a `TpsResult` (`simulate`) with the range, 95th
+//! percentile and jitter of the per-second series.
 
 #![deny(missing_docs)]
 #![forbid(unsafe_code)]
@@ -11,14 +17,20 @@ use crate::{
     config::SimConfig,
     error::SimError,
     metrics::SimMetrics,
-    types::TpsResult,
+    types::{LatencyProfile, TpsResult},
 };
+use crate::latency_wave::percentile;
+
+/// Stream of `SimConfig::rng` reserved for the TPS simulator.
+const RNG_STREAM: u64 = 1;
 
 /// `TpsSimulator` runs a simple per‐second TPS simulation.
 #[derive(Debug)]
 pub struct TpsSimulator {
     config: SimConfig,
     metrics: SimMetrics,
+    /// Per‐second latencies (ms) driving backpressure, if supplied.
+    latency_feedback: Option<Vec<f64>>,
 }
 
 impl TpsSimulator {
@@ -27,40 +39,76 @@ impl TpsSimulator {
         TpsSimulator {
             config: config.clone(),
             metrics: SimMetrics::new(),
+            latency_feedback: None,
         }
     }
 
-    /// Run the TPS simulation.
+    /// Throttle offered load from `latency`'s per‐second samples whenever
+    /// `config.backpressure_threshold_ms` is set.
+    pub fn with_latency_feedback(mut self, latency: &LatencyProfile) -> Self {
+        self.latency_feedback = Some(latency.samples.iter().map(|&(_, ms)| ms).collect());
+        self
+    }
+
+    /// Stream the simulation as `(second, tps)` pairs, one per simulated
+    /// second for `duration_secs` seconds.
     ///
-    /// Samples instantaneous TPS once per second, with ±10% random jitter.
-    /// Returns a `TpsResult` with target, average, and time‐series samples.
-    pub fn simulate(&mut self) -> Result<TpsResult, SimError> {
-        let target = self.config.target_tps;
-        let duration = self.config.duration_secs;
-        let mut rng = rand::thread_rng();
-        let mut samples = Vec::with_capacity(duration as usize);
-        let mut sum = 0.0;
+    /// Instantaneous TPS is the effective target with ±10% random jitter,
+    /// rounded to a whole transaction count. The effective target is
+    /// `target_tps` unless latency feedback throttles it: after a second whose
+    /// latency exceeds `backpressure_threshold_ms` it is multiplied by
+    /// `backpressure_factor`, and after any other second it is divided by it,
+    /// up to `target_tps`. Samples are drawn lazily, and a `tps_samples`
+    /// metric is recorded as each one is yielded.
+    pub fn iter_samples(&mut self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        self.raw_samples().map(|(t, tps)| (t, tps.round() as u64))
+    }
 
-        for t in 0..duration {
-            // instantaneous TPS = target * random factor in [0.9,1.1)
+    /// Draw the samples `iter_samples` yields, before rounding.
+    fn raw_samples(&mut self) -> impl Iterator<Item = (u64, f64)> + '_ {
+        let target = self.config.target_tps as f64;
+        let duration = self.config.duration_secs;
+        let backpressure = self.config.backpressure_factor;
+        let feedback = self
+            .config
+            .backpressure_threshold_ms
+            .zip(self.latency_feedback.as_deref());
+        let metrics = &mut self.metrics;
+        let mut rng = self.config.rng(RNG_STREAM);
+        let mut effective = target;
+        (0..duration).map(move |t| {
+            // congestion in the previous second throttles this one
+            if let Some((threshold, latencies)) = feedback {
+                if let Some(&prev) = (t as usize).checked_sub(1).and_then(|i| latencies.get(i)) {
+                    effective = if prev > threshold {
+                        effective * backpressure
+                    } else {
+                        (effective / backpressure).min(target)
+                    };
+                }
+            }
+            // instantaneous TPS = effective target * random factor in [0.9,1.1)
             let factor: f64 = rng.gen_range(0.9..1.1);
-            let inst = (target as f64) * factor;
-            samples.push((t, inst));
-            sum += inst;
-            self.metrics.record_tps_sample();
-        }
+            metrics.record_tps_sample();
+            (t, effective * factor)
+        })
+    }
 
-        let average = if duration > 0 {
-            sum / (duration as f64)
-        } else {
-            0.0
-        };
+    /// Run the TPS simulation.
+    ///
+    /// Draws the same series as [`iter_samples`](Self::iter_samples), but
+    /// keeps each sample unrounded so the statistics carry no rounding error.
+    /// Returns a `TpsResult` with target, summary statistics, and
+    /// time‐series samples.
+    pub fn simulate(&mut self) -> Result<TpsResult, SimError> {
+        let target = self.config.target_tps;
+        let samples: Vec<(u64, f64)> = self.raw_samples().collect();
+        Ok(summarize(target, samples))
+    }
 
-        Ok(TpsResult {
-            target_tps: target,
-            average_tps: average,
-            samples,
-        })
+    /// Metrics recorded by this simulator so far.
+    pub fn metrics(&self) -> &SimMetrics {
+        &self.metrics
     }
 
     /// Export internal metrics in Prometheus text format.
@@ -69,6 +117,26 @@ impl TpsSimulator {
     }
 }
 
+/// Build a `TpsResult` from per‐second samples; every statistic is 0 when
+/// there are none.
+fn summarize(target_tps: u64, samples: Vec<(u64, f64)>) -> TpsResult {
+    let mut sorted: Vec<f64> = samples.iter().map(|&(_, tps)| tps).collect();
+    sorted.sort_by(f64::total_cmp);
+    let n = sorted.len().max(1) as f64;
+    let average = sorted.iter().sum::<f64>() / n;
+    let variance = sorted.iter().map(|tps| (tps - average).powi(2)).sum::<f64>() / n;
+
+    TpsResult {
+        target_tps,
+        average_tps: average,
+        min_tps: sorted.first().copied().unwrap_or(0.0),
+        max_tps: sorted.last().copied().unwrap_or(0.0),
+        p95_tps: percentile(&sorted, 0.95),
+        jitter: variance.sqrt(),
+        samples,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -108,5 +176,103 @@ mod tests {
         let res = sim.simulate().unwrap();
         assert_eq!(res.samples.len(), 0);
         assert_eq!(res.average_tps, 0.0);
+        assert_eq!((res.min_tps, res.max_tps, res.p95_tps, res.jitter), (0.0, 0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn bursty_profile_shows_spread_and_jitter() {
+        // steady at 100 TPS with one 1000 TPS burst
+        let samples: Vec<(u64, f64)> = (0..10)
+            .map(|t| (t, if t == 4 { 1_000.0 } else { 100.0 }))
+            .collect();
+        let res = summarize(100, samples);
+        assert_eq!(res.average_tps, 190.0);
+        assert_eq!(res.min_tps, 100.0);
+        assert_eq!(res.max_tps, 1_000.0);
+        assert!(res.max_tps > res.average_tps);
+        assert_eq!(res.p95_tps, 1_000.0);
+        assert!((res.jitter - 270.0).abs() < 1e-9);
+
+        let smooth = summarize(100, (0..10).map(|t| (t, 190.0)).collect());
+        assert_eq!(smooth.average_tps, res.average_tps);
+        assert_eq!(smooth.jitter, 0.0);
+    }
+
+    #[test]
+    fn simulated_stats_stay_within_jitter_band() {
+        let mut cfg = SimConfig::default();
+        cfg.duration_secs = 30;
+        cfg.target_tps = 1_000;
+        let res = TpsSimulator::new(&cfg).simulate().unwrap();
+        assert!(res.min_tps >= 900.0 && res.max_tps <= 1_100.0);
+        assert!(res.min_tps <= res.average_tps && res.average_tps <= res.max_tps);
+        assert!(res.p95_tps <= res.max_tps);
+        assert!(res.jitter > 0.0);
+    }
+
+    fn flat_latency(secs: u64, ms: f64) -> LatencyProfile {
+        LatencyProfile {
+            mean_ms: ms,
+            stddev_ms: 0.0,
+            p50_ms: ms,
+            p95_ms: ms,
+            p99_ms: ms,
+            samples: (0..secs).map(|t| (t, ms)).collect(),
+        }
+    }
+
+    #[test]
+    fn high_latency_throttles_offered_load() {
+        let mut cfg = SimConfig::default();
+        cfg.duration_secs = 10;
+        cfg.target_tps = 1_000;
+        cfg.seed = Some(5);
+        let open_loop = TpsSimulator::new(&cfg).simulate().unwrap();
+
+        cfg.backpressure_threshold_ms = Some(200.0);
+        let congested = TpsSimulator::new(&cfg)
+            .with_latency_feedback(&flat_latency(10, 500.0))
+            .simulate()
+            .unwrap();
+        assert!(congested.average_tps < open_loop.average_tps / 2.0);
+        // the first second has no prior latency to react to
+        assert_eq!(congested.samples[0], open_loop.samples[0]);
+        assert!(congested.samples[1].1 < congested.samples[0].1);
+
+        let calm = TpsSimulator::new(&cfg)
+            .with_latency_feedback(&flat_latency(10, 50.0))
+            .simulate()
+            .unwrap();
+        assert_eq!(calm, open_loop);
+    }
+
+    #[test]
+    fn iter_samples_yields_one_item_per_second() {
+        let mut cfg = SimConfig::default();
+        cfg.duration_secs = 7;
+        cfg.target_tps = 1000;
+        let mut sim = TpsSimulator::new(&cfg);
+        let samples: Vec<(u64, u64)> = sim.iter_samples().collect();
+        assert_eq!(samples.len(), 7);
+        for (i, &(second, tps)) in samples.iter().enumerate() {
+            assert_eq!(second, i as u64);
+            assert!((900..=1100).contains(&tps), "tps {} outside jitter band", tps);
+        }
+        assert!(sim.export_metrics().contains("sim_tps_samples 7"));
+    }
+    #[test]
+    fn simulate_keeps_unrounded_samples() {
+        let mut cfg = SimConfig::default();
+        cfg.duration_secs = 20;
+        cfg.target_tps = 3;
+        cfg.seed = Some(11);
+        let res = TpsSimulator::new(&cfg).simulate().unwrap();
+        assert!(res.samples.iter().any(|&(_, tps)| tps.fract() != 0.0));
+
+        // the streamed series is the same draw, rounded
+        let rounded: Vec<(u64, u64)> = TpsSimulator::new(&cfg).iter_samples().collect();
+        let expected: Vec<(u64, u64)> =
+            res.samples.iter().map(|&(t, tps)| (t, tps.round() as u64)).collect();
+        assert_eq!(rounded, expected);
     }
 }
@@ -1,44 +1,63 @@
 //! Simulation result types for Qublis‐sim — Qublis v2.0
 //!
 //! Defines the core data structures returned by the various simulators,
-//! and the aggregated `ReportData`.
+//! the aggregated `ReportData`, and the `ReportDiff` between two reports.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 /// Result of a TPS (transactions‐per‐second) simulation.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct TpsResult {
     /// Target TPS from the configuration.
     pub target_tps: u64,
     /// Achieved average TPS over the run.
     pub average_tps: f64,
+    /// Lowest per‐second TPS.
+    #[serde(default)]
+    pub min_tps: f64,
+    /// Highest per‐second TPS.
+    #[serde(default)]
+    pub max_tps: f64,
+    /// 95th-percentile per‐second TPS.
+    #[serde(default)]
+    pub p95_tps: f64,
+    /// Standard deviation of the per‐second TPS samples.
+    #[serde(default)]
+    pub jitter: f64,
     /// Time‐series of (timestamp_sec, instantaneous_tps).
     pub samples: Vec<(u64, f64)>,
 }
 
 /// Multi‐dimensional view of resource utilization.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct DimensionView {
     /// Number of dimensions modeled.
     pub dimensions: usize,
-    /// Map from a dimension index (0..dimensions) to its utilization metric.
-    pub utilization: HashMap<usize, f64>,
+    /// Map from a dimension index (0..dimensions) to its utilization metric,
+    /// ordered by index so serialized reports are stable.
+    pub utilization: BTreeMap<usize, f64>,
 }
 
 /// Latency profile generated by the latency_wave simulator.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct LatencyProfile {
     /// Mean latency (ms) used for the model.
     pub mean_ms: f64,
     /// Standard deviation (ms) used for the model.
     pub stddev_ms: f64,
+    /// Median observed latency (ms).
+    pub p50_ms: f64,
+    /// 95th-percentile observed latency (ms).
+    pub p95_ms: f64,
+    /// 99th-percentile observed latency (ms).
+    pub p99_ms: f64,
     /// Samples of (timestamp_sec, observed_latency_ms).
     pub samples: Vec<(u64, f64)>,
 }
 
 /// Result of a NeuroFlux optimization simulation.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct NeuroFluxResult {
     /// Number of iterations performed.
     pub iterations: usize,
@@ -49,20 +68,32 @@ pub struct NeuroFluxResult {
 }
 
 /// Aggregated network statistics from the full network simulator.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct NetworkStats {
-    /// Total number of nodes simulated.
+    /// Number of nodes the network started with.
     pub node_count: usize,
+    /// Number of nodes online at the end of the run.
+    #[serde(default)]
+    pub final_node_count: usize,
+    /// Largest number of nodes online at once.
+    #[serde(default)]
+    pub peak_node_count: usize,
     /// Total messages routed.
     pub messages_routed: u64,
     /// Average end‐to‐end latency (ms).
     pub average_latency_ms: f64,
-    /// Packet drop rate (0.0–1.0).
+    /// Packet drop rate (0.0–1.0), including messages lost across partitions.
     pub drop_rate: f64,
+    /// Number of network partitions that began during the run.
+    #[serde(default)]
+    pub partitions_observed: usize,
+    /// Longest partition (seconds), cut off at the end of the run.
+    #[serde(default)]
+    pub max_partition_duration_secs: u64,
 }
 
 /// Final report data bundling all simulation components.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ReportData {
     /// TPS simulation results.
     pub tps: TpsResult,
@@ -76,6 +107,37 @@ pub struct ReportData {
     pub network: NetworkStats,
 }
 
+/// Per-metric change between two reports, each field `b - a`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReportDiff {
+    /// Change in target TPS.
+    pub target_tps: i64,
+    /// Change in achieved average TPS.
+    pub average_tps: f64,
+    /// Change in mean latency (ms).
+    pub latency_mean_ms: f64,
+    /// Change in latency standard deviation (ms).
+    pub latency_stddev_ms: f64,
+    /// Change in median latency (ms).
+    pub latency_p50_ms: f64,
+    /// Change in 95th-percentile latency (ms).
+    pub latency_p95_ms: f64,
+    /// Change in 99th-percentile latency (ms).
+    pub latency_p99_ms: f64,
+    /// Change in the best NeuroFlux metric; `None` unless both reports ran NeuroFlux.
+    pub neuroflux_best_metric: Option<f64>,
+    /// Change in total messages routed.
+    pub messages_routed: i64,
+    /// Change in average end‐to‐end network latency (ms).
+    pub network_latency_ms: f64,
+    /// Change in packet drop rate.
+    pub drop_rate: f64,
+    /// Change in the number of network partitions.
+    pub partitions_observed: i64,
+    /// Change in the number of nodes online at the end of the run.
+    pub final_node_count: i64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -85,6 +147,10 @@ mod tests {
         let res = TpsResult {
             target_tps: 1_000_000,
             average_tps: 950_000.5,
+            min_tps: 900_000.0,
+            max_tps: 1_000_000.0,
+            p95_tps: 1_000_000.0,
+            jitter: 50_000.0,
             samples: vec![(0, 900_000.0), (1, 1_000_000.0)],
         };
         let json = serde_json::to_string(&res).unwrap();
@@ -92,6 +158,8 @@ mod tests {
         assert_eq!(de.target_tps, res.target_tps);
         assert!((de.average_tps - res.average_tps).abs() < 1e-6);
         assert_eq!(de.samples.len(), 2);
+        assert_eq!(de.max_tps, res.max_tps);
+        assert_eq!(de.jitter, res.jitter);
     }
 
     #[test]
@@ -100,6 +168,10 @@ mod tests {
             tps: TpsResult {
                 target_tps: 100,
                 average_tps: 95.0,
+                min_tps: 0.0,
+                max_tps: 0.0,
+                p95_tps: 0.0,
+                jitter: 0.0,
                 samples: vec![],
             },
             dimensions: DimensionView {
@@ -110,14 +182,21 @@ mod tests {
             latency: LatencyProfile {
                 mean_ms: 50.0,
                 stddev_ms: 10.0,
+                p50_ms: 0.0,
+                p95_ms: 0.0,
+                p99_ms: 0.0,
                 samples: vec![],
             },
             neuroflux: None,
             network: NetworkStats {
                 node_count: 10,
+                final_node_count: 12,
+                peak_node_count: 13,
                 messages_routed: 1000,
                 average_latency_ms: 55.0,
                 drop_rate: 0.01,
+                partitions_observed: 1,
+                max_partition_duration_secs: 5,
             },
         };
         let json = serde_json::to_string(&report).unwrap();